@@ -20,3 +20,168 @@ settings-fix-indicator-size = Fix Indicator Size
 settings-fix-indicator-size-helper = Fixes the width to the max possible text size to prevent jitter in the panel/dock. Turn this off if you prefer a higher density UI.
 settings-use-iec-units = Use IEC units instead of SI
 settings-use-iec-units-helper = Toggle between 1000-based SI units (MB, GB) and 1024-based IEC units (MiB, GiB) for all indicators.
+settings-file-title = File Sensor Settings
+settings-file-path = File Path
+settings-file-path-helper = Any sysfs or procfs file to read, i.e. /sys/class/backlight/intel_backlight/brightness. The indicator stays hidden until a path is set.
+settings-file-path-error = Must be an absolute path
+settings-file-pattern = Match Pattern
+settings-file-pattern-helper = Optional regex to find the value. The first capture group is used if there is one.
+settings-file-pattern-error = Must be a valid regular expression
+settings-file-field-index = Field Index
+settings-file-field-index-helper = Optional zero-based index of the whitespace separated field to read.
+settings-file-field-index-error = Must be empty or a whole number
+settings-file-scale = Scale Factor
+settings-file-scale-helper = Each value is multiplied by this, i.e. 0.001 to convert millivolts to volts.
+settings-file-scale-error = Must be a non-zero number
+settings-file-unit = Unit
+settings-file-unit-error = Must be shorter than { $max_length } chars
+settings-file-rate-mode = Show Rate
+settings-file-rate-mode-helper = Show the change per second instead of the value itself. Use this for ever increasing counters.
+settings-power-title = Power Settings
+settings-power-show-all-domains = Show All Domains
+settings-power-show-all-domains-helper = Show the core, uncore and memory power alongside the CPU package, where available.
+indicator-power-unsupported = N/A
+indicator-power-core = C
+indicator-power-uncore = U
+indicator-power-dram = M
+settings-paging-title = Swap Activity Settings
+settings-paging-show-major-faults = Show Major Faults
+settings-paging-show-major-faults-helper = Show the number of page faults per second that had to wait on disk.
+indicator-paging-major-faults = { $count } flt/s
+indicator-paging-oom-kills = OOM { $count }
+settings-arc-title = ZFS ARC Settings
+settings-arc-show-target = Show Target Size
+settings-arc-show-target-helper = Show the size the ARC is aiming for alongside its current size.
+settings-memory-count-zfs-arc = Count ZFS ARC As Available
+settings-memory-count-zfs-arc-helper = Treat the part of the ZFS ARC the kernel can shrink as available memory rather than used.
+settings-memory-show-compression = Show Compression
+settings-memory-show-compression-helper = Show how much data zram and zswap hold and how well it compresses.
+indicator-memory-zram = zram { $size } { $ratio }x
+indicator-memory-zswap = zswap { $size } { $ratio }x
+settings-raid-title = RAID Settings
+indicator-raid-inactive = { $name } inactive
+settings-sensor-root = Sensor Root
+settings-sensor-root-helper = The directory /proc and /sys are read from. Use / normally, or the host mount, i.e. /run/host, inside a sandbox.
+settings-sensor-root-error = Must be an absolute path
+indicator-unavailable = n/a
+indicator-failing-since = { $error } (last read { $seconds }s ago, { $failures } failed reads since)
+settings-smoothing = Smoothing
+settings-smoothing-error = Must be one of the listed smoothing strategies
+settings-smoothing-moving-average = Moving average
+settings-smoothing-exponential-moving-average = Exponential moving average
+settings-smoothing-median = Median
+settings-smoothing-peak-hold = Peak hold
+settings-smoothing-window = Smoothing Window (seconds)
+settings-smoothing-window-helper = Combine the samples from this many seconds instead of Max Samples. Leave empty to use Max Samples.
+settings-smoothing-window-error = Must be a number of seconds greater than 0, or empty
+settings-display-mode = Display
+settings-display-mode-error = Must be one of the listed display modes
+settings-display-mode-number = Number
+settings-display-mode-graph = Graph
+settings-display-mode-both = Number and graph
+dashboard-title = Vitals
+dashboard-range-one-minute = 1 min
+dashboard-range-ten-minutes = 10 min
+dashboard-range-one-hour = 1 h
+dashboard-cpu = CPU
+dashboard-cpu-usage = Usage
+dashboard-memory = Memory
+dashboard-memory-used = Used
+dashboard-network = Network
+dashboard-network-receive = Receive
+dashboard-network-transmit = Transmit
+dashboard-disk = Disk
+dashboard-disk-read = Read
+dashboard-disk-write = Write
+dashboard-readout = { $name }: { $current } now, min { $min }, max { $max }, avg { $average }
+dashboard-no-data = { $name }: no data yet
+tooltip-cpu = CPU usage { $usage }
+tooltip-memory = { $used } of { $total } ({ $percent }%), { $swap } swap
+tooltip-memory-no-swap = { $used } of { $total } ({ $percent }%), no swap
+tooltip-memory-zram = zram: { $original } compressed to { $compressed } ({ $ratio }x)
+tooltip-memory-zswap = zswap: { $original } compressed to { $compressed } ({ $ratio }x)
+tooltip-network = Receiving { $rx }, sending { $tx }
+tooltip-network-interface = { $name }: { $rx } down, { $tx } up
+tooltip-disk = Reading { $read }, writing { $write }
+tooltip-file = { $path }: { $value }
+tooltip-power-package = Package
+tooltip-power-core = Cores
+tooltip-power-uncore = Uncore
+tooltip-power-dram = Memory
+tooltip-power-unsupported = No RAPL energy counters were found
+tooltip-paging = Swapping in { $swap_in }, out { $swap_out }
+tooltip-paging-major-faults = { $count } major page faults/s
+tooltip-paging-oom-kills = { $count ->
+    [one] The OOM killer has killed 1 process since the applet started
+   *[other] The OOM killer has killed { $count } processes since the applet started
+}
+tooltip-arc = ARC { $size } of { $target } target, { $ratio }% hit ratio
+tooltip-raid-array = { $name }: { $health } [{ $members }]
+tooltip-raid-healthy = healthy
+tooltip-raid-rebuilding = rebuilding
+tooltip-raid-rebuilding-progress = rebuilding, { $progress }% done
+tooltip-raid-degraded = degraded
+tooltip-raid-inactive = inactive
+detail-no-data = Waiting for the next reading
+detail-cpu-title = CPU
+detail-cpu-total = Total
+detail-cpu-cores = Cores
+detail-cpu-core = Core { $index }
+detail-cpu-processes = Top processes
+detail-cpu-process = { $name } ({ $pid })
+detail-memory-title = Memory
+detail-memory-total = Total
+detail-memory-used = Used
+detail-memory-available = Available
+detail-memory-free = Free
+detail-memory-buffers = Buffers
+detail-memory-cache = Cache
+detail-memory-shared = Shared
+detail-memory-swap = Swap
+detail-network-title = Network interfaces
+detail-network-rates = { $rx } down, { $tx } up
+detail-disk-title = Disks
+detail-disk-rates = { $read } read, { $write } written
+settings-warning-threshold = Warning threshold
+settings-critical-threshold = Critical threshold
+settings-threshold-percent-helper = In percent. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-memory-helper = In percent of the memory used. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-rate-helper = In KiB/s of the busier direction. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-file-helper = In the unit of the scaled value. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-power-helper = In watts of the package. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-arc-helper = In MiB of the ARC size. The value turns yellow at the warning threshold and red at the critical one. Leave empty to turn off.
+settings-threshold-error = Must be a whole number, or empty
+settings-threshold-order-error = The warning threshold must not be above the critical threshold
+settings-threshold-hysteresis = Threshold hysteresis
+settings-threshold-hysteresis-helper = How far below a threshold the value has to fall before its colour is cleared
+settings-threshold-hysteresis-error = Must be a whole number
+settings-threshold-colour-label = Colour the label too
+settings-alerts-title = Alerts
+settings-alerts-enabled = Raise alerts
+settings-alerts-enabled-helper = Sends a desktop notification, and runs the rule's hook, when a rule is breached. Rules only watch monitors whose indicator is shown.
+settings-alert-rule = Rule { $number }
+settings-alert-rule-helper = i.e. "cpu > 95% for 30s", "memory-available < 1GiB" or "filesystem:/ > 90%". Also memory, network-rx, network-tx, disk-read and disk-write. Leave empty to turn off.
+settings-alert-rule-error = Must be a rule like "cpu > 95% for 30s", or empty
+settings-alert-cooldown = Cooldown
+settings-alert-cooldown-helper = The seconds before a rule can notify again
+settings-alert-cooldown-error = Must be a whole number of seconds
+settings-alert-hysteresis = Hysteresis
+settings-alert-hysteresis-helper = How far back past its threshold a value has to go before the rule can be raised again, in percent of the threshold
+settings-alert-hook = Hook { $number }
+settings-alert-hook-helper = A shell command run when the rule above it is raised, with VITALS_METRIC, VITALS_VALUE and VITALS_RULE set. Leave empty to only notify.
+settings-alert-hook-rule-error = The hook needs a rule in the same slot
+settings-alert-hook-cooldown = Hook cooldown
+settings-alert-hook-cooldown-helper = The seconds before a hook can run again. A hook is never started while its last run is still going.
+alert-notification-summary = Alert: { $rule }
+alert-notification-body = The value is now { $value }
+settings-memory-value-mode = Show
+settings-memory-value-mode-error = Must be one of the listed memory readings
+settings-memory-value-mode-used = Used
+settings-memory-value-mode-percent-used = Percent used
+settings-memory-value-mode-used-of-total = Used / total
+settings-memory-value-mode-available = Available
+settings-format-template = Format
+settings-format-template-helper = i.e. "{"{"}used:.1{"}"}/{"{"}total:.0{"}"} {"{"}unit{"}"}". The fields are { $fields }, and a number can be given its decimals with ":.1". Leave empty for the default format.
+settings-format-template-error = Fields must be written as {"{"}name{"}"} or {"{"}name:.1{"}"}. Write {"{{"} or {"}}"} to show a brace.
+settings-format-template-unknown-field = There is no field {"{"}{ $field }{"}"}, use { $fields }
+settings-format-template-text-precision = {"{"}{ $field }{"}"} is text and can't be given decimals
@@ -20,3 +20,168 @@ settings-fix-indicator-size = Fixad indikatorstorlek
 settings-fix-indicator-size-helper = FJusterar bredden till maximal möjlig textstorlek för att förhindra flimmer i panel/dock. Stäng av detta om du föredrar ett gränssnitt med högre densitet.
 settings-use-iec-units = Använd IEC-enheter istället för SI
 settings-use-iec-units-helper = Växla mellan 1000-baserade SI-enheter (MB, GB) och 1024-baserade IEC-enheter (MiB, GiB) för alla indikatorer.
+settings-file-title = Filsensorinställningar
+settings-file-path = Filsökväg
+settings-file-path-helper = Valfri sysfs- eller procfs-fil att läsa, t.ex. /sys/class/backlight/intel_backlight/brightness. Indikatorn är dold tills en sökväg har angetts.
+settings-file-path-error = Måste vara en absolut sökväg
+settings-file-pattern = Matchningsmönster
+settings-file-pattern-helper = Valfritt reguljärt uttryck för att hitta värdet. Den första fångstgruppen används om det finns en.
+settings-file-pattern-error = Måste vara ett giltigt reguljärt uttryck
+settings-file-field-index = Fältindex
+settings-file-field-index-helper = Valfritt nollbaserat index för det blankstegsseparerade fält som ska läsas.
+settings-file-field-index-error = Måste vara tomt eller ett heltal
+settings-file-scale = Skalfaktor
+settings-file-scale-helper = Varje värde multipliceras med detta, t.ex. 0.001 för att omvandla millivolt till volt.
+settings-file-scale-error = Måste vara ett tal skilt från noll
+settings-file-unit = Enhet
+settings-file-unit-error = Måste vara kortare än { $max_length } tecken
+settings-file-rate-mode = Visa hastighet
+settings-file-rate-mode-helper = Visa förändringen per sekund istället för själva värdet. Använd detta för räknare som ständigt ökar.
+settings-power-title = Effektinställningar
+settings-power-show-all-domains = Visa alla domäner
+settings-power-show-all-domains-helper = Visa effekten för kärnor, uncore och minne bredvid CPU-paketet, där det finns.
+indicator-power-unsupported = E/T
+indicator-power-core = K
+indicator-power-uncore = U
+indicator-power-dram = M
+settings-paging-title = Inställningar för växlingsaktivitet
+settings-paging-show-major-faults = Visa större sidfel
+settings-paging-show-major-faults-helper = Visa antalet sidfel per sekund som behövde vänta på disken.
+indicator-paging-major-faults = { $count } fel/s
+indicator-paging-oom-kills = OOM { $count }
+settings-arc-title = Inställningar för ZFS ARC
+settings-arc-show-target = Visa målstorlek
+settings-arc-show-target-helper = Visa storleken som ARC siktar på bredvid dess nuvarande storlek.
+settings-memory-count-zfs-arc = Räkna ZFS ARC som tillgängligt
+settings-memory-count-zfs-arc-helper = Behandla den del av ZFS ARC som kärnan kan krympa som tillgängligt minne i stället för använt.
+settings-memory-show-compression = Visa komprimering
+settings-memory-show-compression-helper = Visa hur mycket data zram och zswap innehåller och hur väl den komprimeras.
+indicator-memory-zram = zram { $size } { $ratio }x
+indicator-memory-zswap = zswap { $size } { $ratio }x
+settings-raid-title = RAID-inställningar
+indicator-raid-inactive = { $name } inaktiv
+settings-sensor-root = Sensorrot
+settings-sensor-root-helper = Katalogen som /proc och /sys läses från. Använd / normalt, eller värdens montering, t.ex. /run/host, i en sandlåda.
+settings-sensor-root-error = Måste vara en absolut sökväg
+indicator-unavailable = e/t
+indicator-failing-since = { $error } (senast läst för { $seconds }s sedan, { $failures } misslyckade läsningar sedan dess)
+settings-smoothing = Utjämning
+settings-smoothing-error = Måste vara en av de listade utjämningsmetoderna
+settings-smoothing-moving-average = Glidande medelvärde
+settings-smoothing-exponential-moving-average = Exponentiellt glidande medelvärde
+settings-smoothing-median = Median
+settings-smoothing-peak-hold = Topphållning
+settings-smoothing-window = Utjämningsfönster (sekunder)
+settings-smoothing-window-helper = Kombinera proverna från så här många sekunder i stället för Max antal prover. Lämna tomt för att använda Max antal prover.
+settings-smoothing-window-error = Måste vara ett antal sekunder större än 0, eller tomt
+settings-display-mode = Visning
+settings-display-mode-error = Måste vara ett av de listade visningslägena
+settings-display-mode-number = Siffra
+settings-display-mode-graph = Graf
+settings-display-mode-both = Siffra och graf
+dashboard-title = Vitals
+dashboard-range-one-minute = 1 min
+dashboard-range-ten-minutes = 10 min
+dashboard-range-one-hour = 1 h
+dashboard-cpu = CPU
+dashboard-cpu-usage = Användning
+dashboard-memory = Minne
+dashboard-memory-used = Använt
+dashboard-network = Nätverk
+dashboard-network-receive = Mottaget
+dashboard-network-transmit = Skickat
+dashboard-disk = Disk
+dashboard-disk-read = Läsning
+dashboard-disk-write = Skrivning
+dashboard-readout = { $name }: { $current } nu, min { $min }, max { $max }, medel { $average }
+dashboard-no-data = { $name }: ingen data än
+tooltip-cpu = CPU-användning { $usage }
+tooltip-memory = { $used } av { $total } ({ $percent }%), { $swap } växling
+tooltip-memory-no-swap = { $used } av { $total } ({ $percent }%), ingen växling
+tooltip-memory-zram = zram: { $original } komprimerat till { $compressed } ({ $ratio }x)
+tooltip-memory-zswap = zswap: { $original } komprimerat till { $compressed } ({ $ratio }x)
+tooltip-network = Tar emot { $rx }, skickar { $tx }
+tooltip-network-interface = { $name }: { $rx } ner, { $tx } upp
+tooltip-disk = Läser { $read }, skriver { $write }
+tooltip-file = { $path }: { $value }
+tooltip-power-package = Paket
+tooltip-power-core = Kärnor
+tooltip-power-uncore = Uncore
+tooltip-power-dram = Minne
+tooltip-power-unsupported = Inga RAPL-energiräknare hittades
+tooltip-paging = Växlar in { $swap_in }, ut { $swap_out }
+tooltip-paging-major-faults = { $count } större sidfel/s
+tooltip-paging-oom-kills = { $count ->
+    [one] OOM-killern har dödat 1 process sedan appleten startade
+   *[other] OOM-killern har dödat { $count } processer sedan appleten startade
+}
+tooltip-arc = ARC { $size } av { $target } mål, { $ratio }% träffar
+tooltip-raid-array = { $name }: { $health } [{ $members }]
+tooltip-raid-healthy = frisk
+tooltip-raid-rebuilding = återuppbyggs
+tooltip-raid-rebuilding-progress = återuppbyggs, { $progress }% klart
+tooltip-raid-degraded = degraderad
+tooltip-raid-inactive = inaktiv
+detail-no-data = Väntar på nästa avläsning
+detail-cpu-title = CPU
+detail-cpu-total = Totalt
+detail-cpu-cores = Kärnor
+detail-cpu-core = Kärna { $index }
+detail-cpu-processes = Mest aktiva processer
+detail-cpu-process = { $name } ({ $pid })
+detail-memory-title = Minne
+detail-memory-total = Totalt
+detail-memory-used = Använt
+detail-memory-available = Tillgängligt
+detail-memory-free = Ledigt
+detail-memory-buffers = Buffertar
+detail-memory-cache = Cache
+detail-memory-shared = Delat
+detail-memory-swap = Växlingsutrymme
+detail-network-title = Nätverksgränssnitt
+detail-network-rates = { $rx } ned, { $tx } upp
+detail-disk-title = Diskar
+detail-disk-rates = { $read } läst, { $write } skrivet
+settings-warning-threshold = Varningsgräns
+settings-critical-threshold = Kritisk gräns
+settings-threshold-percent-helper = I procent. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-memory-helper = I procent av använt minne. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-rate-helper = I KiB/s för den mest belastade riktningen. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-file-helper = I det skalade värdets enhet. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-power-helper = I watt för paketet. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-arc-helper = I MiB av ARC-storleken. Värdet blir gult vid varningsgränsen och rött vid den kritiska. Lämna tomt för att stänga av.
+settings-threshold-error = Måste vara ett heltal, eller tomt
+settings-threshold-order-error = Varningsgränsen får inte vara över den kritiska gränsen
+settings-threshold-hysteresis = Hysteres för gränser
+settings-threshold-hysteresis-helper = Hur långt under en gräns värdet måste sjunka innan färgen tas bort
+settings-threshold-hysteresis-error = Måste vara ett heltal
+settings-threshold-colour-label = Färga även etiketten
+settings-alerts-title = Larm
+settings-alerts-enabled = Utlös larm
+settings-alerts-enabled-helper = Skickar en skrivbordsavisering, och kör regelns krok, när en regel bryts. Regler bevakar bara övervakare vars indikator visas.
+settings-alert-rule = Regel { $number }
+settings-alert-rule-helper = t.ex. "cpu > 95% for 30s", "memory-available < 1GiB" eller "filesystem:/ > 90%". Även memory, network-rx, network-tx, disk-read och disk-write. Lämna tomt för att stänga av.
+settings-alert-rule-error = Måste vara en regel som "cpu > 95% for 30s", eller tomt
+settings-alert-cooldown = Vilotid
+settings-alert-cooldown-helper = Antal sekunder innan en regel kan avisera igen
+settings-alert-cooldown-error = Måste vara ett heltal av sekunder
+settings-alert-hysteresis = Hysteres
+settings-alert-hysteresis-helper = Hur långt tillbaka förbi gränsen ett värde måste gå innan regeln kan utlösas igen, i procent av gränsen
+settings-alert-hook = Krok { $number }
+settings-alert-hook-helper = Ett skalkommando som körs när regeln ovanför utlöses, med VITALS_METRIC, VITALS_VALUE och VITALS_RULE satta. Lämna tomt för att bara avisera.
+settings-alert-hook-rule-error = Kroken behöver en regel på samma plats
+settings-alert-hook-cooldown = Vilotid för krokar
+settings-alert-hook-cooldown-helper = Antal sekunder innan en krok kan köras igen. En krok startas aldrig medan den förra körningen pågår.
+alert-notification-summary = Larm: { $rule }
+alert-notification-body = Värdet är nu { $value }
+settings-memory-value-mode = Visa
+settings-memory-value-mode-error = Måste vara en av de listade minnesavläsningarna
+settings-memory-value-mode-used = Använt
+settings-memory-value-mode-percent-used = Procent använt
+settings-memory-value-mode-used-of-total = Använt / totalt
+settings-memory-value-mode-available = Tillgängligt
+settings-format-template = Format
+settings-format-template-helper = t.ex. "{"{"}used:.1{"}"}/{"{"}total:.0{"}"} {"{"}unit{"}"}". Fälten är { $fields }, och ett tal kan ges sina decimaler med ":.1". Lämna tomt för standardformatet.
+settings-format-template-error = Fält måste skrivas som {"{"}namn{"}"} eller {"{"}namn:.1{"}"}. Skriv {"{{"} eller {"}}"} för att visa en klammer.
+settings-format-template-unknown-field = Det finns inget fält {"{"}{ $field }{"}"}, använd { $fields }
+settings-format-template-text-precision = {"{"}{ $field }{"}"} är text och kan inte ges decimaler
@@ -0,0 +1,253 @@
+use crate::configuration::alert_rule::{AlertMetric, AlertRule};
+use crate::configuration::alerts::AlertsConfiguration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A value reported by a monitor that the alert rules are checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertValue {
+    pub metric: AlertMetric,
+    /// The value in the metric's unit: percent, bytes or bytes per second
+    pub value: f64,
+}
+
+impl AlertValue {
+    pub fn new(metric: AlertMetric, value: f64) -> Self {
+        Self { metric, value }
+    }
+}
+
+/// A rule that has been raised, with the value that raised it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub rule: AlertRule,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleState {
+    /// When the value started breaching the threshold, while it still is
+    breached_since: Option<Instant>,
+    /// Whether the rule has been raised and the value hasn't gone back past the hysteresis since
+    raised: bool,
+    last_notified: Option<Instant>,
+}
+
+/// Checks the values from each monitor update against the alert rules, and decides which rules
+/// are raised. A rule is raised once its threshold has been breached for the rule's duration, and
+/// not again until the value has gone back past the threshold by the hysteresis and the cooldown
+/// since its last notification has passed.
+#[derive(Debug, Default)]
+pub struct AlertEvaluator {
+    rules: Vec<(AlertRule, RuleState)>,
+    cooldown: Duration,
+    hysteresis_percent: u64,
+}
+
+impl AlertEvaluator {
+    pub fn new(configuration: &AlertsConfiguration) -> Self {
+        let rules = match configuration.enabled {
+            true => configuration
+                .rules
+                .iter()
+                .flatten()
+                .map(|rule| (rule.clone(), RuleState::default()))
+                .collect(),
+            false => Vec::new(),
+        };
+
+        Self {
+            rules,
+            cooldown: configuration.cooldown,
+            hysteresis_percent: configuration.hysteresis_percent,
+        }
+    }
+
+    /// Applies a changed configuration. A rule that is still configured keeps its state, found by
+    /// its condition, so saving the settings neither forgets how long a rule has been breached nor
+    /// raises a rule that is already raised again.
+    pub fn update_configuration(&mut self, configuration: &AlertsConfiguration) {
+        let mut previous_states: HashMap<String, RuleState> = self
+            .rules
+            .drain(..)
+            .map(|(rule, state)| (rule.to_string(), state))
+            .collect();
+        let updated = Self::new(configuration);
+
+        self.rules = updated
+            .rules
+            .into_iter()
+            .map(|(rule, state)| {
+                let state = previous_states.remove(&rule.to_string()).unwrap_or(state);
+                (rule, state)
+            })
+            .collect();
+        self.cooldown = updated.cooldown;
+        self.hysteresis_percent = updated.hysteresis_percent;
+    }
+
+    /// Checks the values from a monitor update at `now`, and returns the alerts to notify
+    pub fn observe(&mut self, now: Instant, values: &[AlertValue]) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+
+        for value in values {
+            for (rule, state) in self.rules.iter_mut().filter(|(rule, _)| rule.metric == value.metric) {
+                if state.raised {
+                    if rule.is_cleared(value.value, self.hysteresis_percent) {
+                        state.raised = false;
+                        state.breached_since = None;
+                    }
+                    continue;
+                }
+
+                if !rule.is_breached(value.value) {
+                    state.breached_since = None;
+                    continue;
+                }
+
+                let breached_since = *state.breached_since.get_or_insert(now);
+
+                if now.saturating_duration_since(breached_since) < rule.duration {
+                    continue;
+                }
+
+                state.raised = true;
+
+                // A rule raised again during its cooldown stays quiet until it clears and is raised again
+                let is_cooled_down = match state.last_notified {
+                    Some(last_notified) => now.saturating_duration_since(last_notified) >= self.cooldown,
+                    None => true,
+                };
+
+                if is_cooled_down {
+                    state.last_notified = Some(now);
+                    alerts.push(Alert {
+                        rule: rule.clone(),
+                        value: value.value,
+                    });
+                }
+            }
+        }
+
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluator(rules: &[&str], cooldown: u64) -> AlertEvaluator {
+        AlertEvaluator::new(&AlertsConfiguration {
+            enabled: true,
+            rules: rules.iter().map(|rule| Some(rule.parse().unwrap())).collect(),
+            cooldown: Duration::from_secs(cooldown),
+            hysteresis_percent: 10,
+            ..Default::default()
+        })
+    }
+
+    /// Observes a CPU usage each second from `start`, and returns the seconds an alert was raised at
+    fn observe_cpu(evaluator: &mut AlertEvaluator, start: Instant, values: &[f64]) -> Vec<usize> {
+        values
+            .iter()
+            .enumerate()
+            .filter(|(second, value)| {
+                let now = start + Duration::from_secs(*second as u64);
+                !evaluator
+                    .observe(now, &[AlertValue::new(AlertMetric::CpuUsage, **value)])
+                    .is_empty()
+            })
+            .map(|(second, _)| second)
+            .collect()
+    }
+
+    #[test]
+    fn rule_is_raised_once_breached_for_its_duration() {
+        let mut evaluator = evaluator(&["cpu > 90% for 2s"], 0);
+
+        let raised = observe_cpu(&mut evaluator, Instant::now(), &[95.0, 95.0, 95.0, 95.0]);
+
+        assert_eq!(raised, vec![2]);
+    }
+
+    #[test]
+    fn dip_below_the_threshold_restarts_the_duration() {
+        let mut evaluator = evaluator(&["cpu > 90% for 2s"], 0);
+
+        let raised = observe_cpu(&mut evaluator, Instant::now(), &[95.0, 95.0, 50.0, 95.0, 95.0, 95.0]);
+
+        assert_eq!(raised, vec![5]);
+    }
+
+    #[test]
+    fn raised_rule_is_not_raised_again_until_cleared_past_the_hysteresis() {
+        let mut evaluator = evaluator(&["cpu > 90%"], 0);
+
+        // 85% is below the threshold but within 10% of it, so only the drop to 80% clears the rule
+        let raised = observe_cpu(&mut evaluator, Instant::now(), &[95.0, 85.0, 95.0, 80.0, 95.0]);
+
+        assert_eq!(raised, vec![0, 4]);
+    }
+
+    #[test]
+    fn cooldown_silences_a_rule_raised_again_soon_after() {
+        let mut evaluator = evaluator(&["cpu > 90%"], 3);
+
+        let raised = observe_cpu(&mut evaluator, Instant::now(), &[95.0, 50.0, 95.0, 50.0, 95.0]);
+
+        assert_eq!(raised, vec![0, 4]);
+    }
+
+    #[test]
+    fn rules_only_check_values_of_their_metric() {
+        let mut evaluator = evaluator(&["filesystem:/home > 90%", "memory-available < 1GiB"], 0);
+
+        let alerts = evaluator.observe(
+            Instant::now(),
+            &[
+                AlertValue::new(AlertMetric::FilesystemUsed("/".to_string()), 95.0),
+                AlertValue::new(AlertMetric::FilesystemUsed("/home".to_string()), 95.0),
+                AlertValue::new(AlertMetric::MemoryAvailable, 2.0 * (1 << 30) as f64),
+            ],
+        );
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule.metric, AlertMetric::FilesystemUsed("/home".to_string()));
+        assert_eq!(alerts[0].value, 95.0);
+    }
+
+    #[test]
+    fn updating_the_configuration_keeps_the_state_of_unchanged_rules() {
+        let mut evaluator = evaluator(&["cpu > 90%", "memory-available < 1GiB"], 0);
+        let start = Instant::now();
+
+        assert_eq!(observe_cpu(&mut evaluator, start, &[95.0]), vec![0]);
+
+        evaluator.update_configuration(&AlertsConfiguration {
+            enabled: true,
+            rules: vec!["cpu > 90%".parse().ok(), None, "cpu > 50%".parse().ok()],
+            cooldown: Duration::ZERO,
+            hysteresis_percent: 10,
+            ..Default::default()
+        });
+
+        // The raised rule stays raised, while the new rule starts out unraised
+        let cpu_usage = AlertValue::new(AlertMetric::CpuUsage, 95.0);
+        let alerts = evaluator.observe(start + Duration::from_secs(1), &[cpu_usage]);
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].rule.to_string(), "cpu > 50%");
+    }
+
+    #[test]
+    fn disabled_alerts_are_never_raised() {
+        let mut evaluator = AlertEvaluator::new(&AlertsConfiguration {
+            enabled: false,
+            rules: vec!["cpu > 90%".parse().ok()],
+            ..Default::default()
+        });
+
+        assert!(observe_cpu(&mut evaluator, Instant::now(), &[95.0]).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::alerts::notifier::{AlertNotification, Notifier, NotifyFuture};
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+use zbus::Connection;
+
+const NOTIFICATIONS_DESTINATION: &str = "org.freedesktop.Notifications";
+const NOTIFICATIONS_PATH: &str = "/org/freedesktop/Notifications";
+const NOTIFICATIONS_INTERFACE: &str = "org.freedesktop.Notifications";
+const APP_NAME: &str = "Vitals";
+const APP_ICON: &str = "dialog-warning-symbolic";
+/// The critical urgency, as a breached alert rule is worth interrupting for
+const URGENCY_CRITICAL: u8 = 2;
+/// Lets the notification server decide how long the notification is shown
+const DEFAULT_EXPIRE_TIMEOUT: i32 = -1;
+
+/// Sends notifications to the desktop's notification service over D-Bus
+pub struct DbusNotifier {
+    /// The bus to send on, or None to connect to the session bus for each notification. Alerts
+    /// are rare enough that holding a connection open between them isn't worth it.
+    connection: Option<Connection>,
+}
+
+impl DbusNotifier {
+    pub fn session() -> Self {
+        Self { connection: None }
+    }
+
+    /// Sends on an existing connection, i.e. to a private bus standing in for the session bus
+    pub fn with_connection(connection: Connection) -> Self {
+        Self {
+            connection: Some(connection),
+        }
+    }
+}
+
+impl Notifier for DbusNotifier {
+    fn notify(&self, notification: AlertNotification) -> NotifyFuture {
+        let connection = self.connection.clone();
+
+        Box::pin(async move {
+            let connection = match connection {
+                Some(connection) => connection,
+                None => Connection::session().await.map_err(|err| err.to_string())?,
+            };
+            let hints = HashMap::from([("urgency", Value::from(URGENCY_CRITICAL))]);
+
+            connection
+                .call_method(
+                    Some(NOTIFICATIONS_DESTINATION),
+                    NOTIFICATIONS_PATH,
+                    Some(NOTIFICATIONS_INTERFACE),
+                    "Notify",
+                    &(
+                        APP_NAME,
+                        0u32,
+                        APP_ICON,
+                        notification.summary.as_str(),
+                        notification.body.as_str(),
+                        Vec::<&str>::new(),
+                        hints,
+                        DEFAULT_EXPIRE_TIMEOUT,
+                    ),
+                )
+                .await
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::UnixStream;
+    use zbus::connection::Builder;
+    use zbus::zvariant::OwnedValue;
+    use zbus::Guid;
+
+    #[derive(Debug, PartialEq)]
+    struct ReceivedNotification {
+        app_name: String,
+        summary: String,
+        body: String,
+        urgency: u8,
+    }
+
+    /// Stands in for the desktop's notification service, recording what it is sent
+    #[derive(Default, Clone)]
+    struct FakeNotifications {
+        received: Arc<Mutex<Vec<ReceivedNotification>>>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl FakeNotifications {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            app_name: String,
+            _replaces_id: u32,
+            _app_icon: String,
+            summary: String,
+            body: String,
+            _actions: Vec<String>,
+            hints: HashMap<String, OwnedValue>,
+            _expire_timeout: i32,
+        ) -> u32 {
+            let urgency = hints
+                .get("urgency")
+                .and_then(|urgency| u8::try_from(urgency).ok())
+                .unwrap_or_default();
+            let mut received = self.received.lock().unwrap();
+            received.push(ReceivedNotification {
+                app_name,
+                summary,
+                body,
+                urgency,
+            });
+            received.len() as u32
+        }
+    }
+
+    /// Connects a notifier over a private peer to peer bus, with the fake notification service on
+    /// the other end if there is one
+    async fn connect(service: Option<FakeNotifications>) -> (DbusNotifier, Connection) {
+        let (client, server) = UnixStream::pair().unwrap();
+        let mut server = Builder::unix_stream(server).server(Guid::generate()).unwrap().p2p();
+        if let Some(service) = service {
+            server = server.serve_at(NOTIFICATIONS_PATH, service).unwrap();
+        }
+        let server = server.build();
+        let client = Builder::unix_stream(client).p2p().build();
+
+        let (server, client) = tokio::join!(server, client);
+
+        (DbusNotifier::with_connection(client.unwrap()), server.unwrap())
+    }
+
+    #[tokio::test]
+    async fn notification_is_sent_to_the_notification_service() {
+        let service = FakeNotifications::default();
+        let (notifier, _server) = connect(Some(service.clone())).await;
+
+        let result = notifier
+            .notify(AlertNotification {
+                summary: "cpu > 95% for 30s".to_string(),
+                body: "97.0%".to_string(),
+            })
+            .await;
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            *service.received.lock().unwrap(),
+            vec![ReceivedNotification {
+                app_name: APP_NAME.to_string(),
+                summary: "cpu > 95% for 30s".to_string(),
+                body: "97.0%".to_string(),
+                urgency: URGENCY_CRITICAL,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn failure_is_reported_when_the_bus_has_gone() {
+        let (notifier, server) = connect(None).await;
+        server.close().await.unwrap();
+
+        let result = notifier
+            .notify(AlertNotification {
+                summary: "cpu > 95%".to_string(),
+                body: "97.0%".to_string(),
+            })
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::alerts::alert_evaluator::Alert;
+use log::{error, info, warn};
+use std::collections::HashMap;
+use std::io;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// The key of the raised rule's metric, i.e. "cpu" or "filesystem:/home"
+pub const HOOK_METRIC_VARIABLE: &str = "VITALS_METRIC";
+/// The value that raised the rule in the metric's unit: percent, bytes or bytes per second
+pub const HOOK_VALUE_VARIABLE: &str = "VITALS_VALUE";
+/// The raised rule, i.e. "cpu > 95% for 30s"
+pub const HOOK_RULE_VARIABLE: &str = "VITALS_RULE";
+
+#[derive(Debug, Default)]
+struct HookState {
+    last_started: Option<Instant>,
+    /// Set while the hook's process is running, and cleared by the thread waiting for it to exit
+    running: Arc<AtomicBool>,
+}
+
+/// Runs the hook commands of raised alerts with the shell, with the alert in their environment.
+/// A hook isn't started again within its cooldown, nor while its last run is still going, so a
+/// flapping rule or a hook that hangs can't pile up processes.
+#[derive(Debug, Default)]
+pub struct HookRunner {
+    /// The state of each hook by its command, so rules sharing a command share its rate limit
+    hooks: HashMap<String, HookState>,
+    cooldown: Duration,
+}
+
+impl HookRunner {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            hooks: HashMap::new(),
+            cooldown,
+        }
+    }
+
+    /// Changes the cooldown, keeping track of the hooks that have already run
+    pub fn set_cooldown(&mut self, cooldown: Duration) {
+        self.cooldown = cooldown;
+    }
+
+    /// Starts the hooks of the alerts raised at `now` without waiting for them. Each hook is waited
+    /// for on its own thread, which logs and returns its exit code.
+    pub fn run(&mut self, now: Instant, alerts: &[Alert]) -> Vec<JoinHandle<Option<i32>>> {
+        let mut runs = Vec::new();
+
+        for alert in alerts {
+            let Some(hook) = &alert.rule.hook else {
+                continue;
+            };
+            let state = self.hooks.entry(hook.clone()).or_default();
+
+            if state.running.load(Ordering::Acquire) {
+                warn!("Not running hook `{}` as its last run hasn't exited", hook);
+                continue;
+            }
+
+            let is_cooled_down = match state.last_started {
+                Some(last_started) => now.saturating_duration_since(last_started) >= self.cooldown,
+                None => true,
+            };
+
+            if !is_cooled_down {
+                info!("Not running hook `{}` as it ran within its cooldown", hook);
+                continue;
+            }
+
+            match start_hook(hook, alert, state.running.clone()) {
+                Ok(run) => {
+                    state.last_started = Some(now);
+                    runs.push(run);
+                }
+                Err(err) => error!("Failed to start hook `{}`: {}", hook, err),
+            }
+        }
+
+        runs
+    }
+}
+
+fn start_hook(hook: &str, alert: &Alert, running: Arc<AtomicBool>) -> io::Result<JoinHandle<Option<i32>>> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(hook)
+        .env(HOOK_METRIC_VARIABLE, alert.rule.metric.key())
+        .env(HOOK_VALUE_VARIABLE, alert.value.to_string())
+        .env(HOOK_RULE_VARIABLE, alert.rule.to_string())
+        .stdin(Stdio::null())
+        .spawn()?;
+
+    info!("Started hook `{}` for alert {}", hook, alert.rule);
+    running.store(true, Ordering::Release);
+
+    let hook = hook.to_string();
+
+    Ok(thread::spawn(move || {
+        let code = match child.wait() {
+            Ok(status) => {
+                match status.code() {
+                    Some(0) => info!("Hook `{}` exited with code 0", hook),
+                    Some(code) => warn!("Hook `{}` exited with code {}", hook, code),
+                    None => warn!("Hook `{}` was terminated by a signal", hook),
+                }
+                status.code()
+            }
+            Err(err) => {
+                error!("Failed to wait for hook `{}`: {}", hook, err);
+                None
+            }
+        };
+
+        running.store(false, Ordering::Release);
+        code
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::alert_rule::AlertRule;
+
+    fn alert(rule: &str, hook: &str, value: f64) -> Alert {
+        let rule: AlertRule = rule.parse().unwrap();
+
+        Alert {
+            rule: AlertRule {
+                hook: Some(hook.to_string()),
+                ..rule
+            },
+            value,
+        }
+    }
+
+    fn exit_codes(runs: Vec<JoinHandle<Option<i32>>>) -> Vec<Option<i32>> {
+        runs.into_iter().map(|run| run.join().unwrap()).collect()
+    }
+
+    #[test]
+    fn hook_is_run_with_the_alert_in_its_environment() {
+        let mut runner = HookRunner::new(Duration::ZERO);
+        let hook = r#"test "$VITALS_METRIC" = cpu && test "$VITALS_VALUE" = 97.5 && test "$VITALS_RULE" = "cpu > 95% for 30s""#;
+
+        let runs = runner.run(Instant::now(), &[alert("cpu > 95% for 30s", hook, 97.5)]);
+
+        assert_eq!(exit_codes(runs), vec![Some(0)]);
+    }
+
+    #[test]
+    fn exit_code_is_returned() {
+        let mut runner = HookRunner::new(Duration::ZERO);
+
+        let runs = runner.run(Instant::now(), &[alert("cpu > 95%", "exit 3", 97.5)]);
+
+        assert_eq!(exit_codes(runs), vec![Some(3)]);
+    }
+
+    #[test]
+    fn hook_is_not_run_again_within_its_cooldown() {
+        let mut runner = HookRunner::new(Duration::from_secs(60));
+        let start = Instant::now();
+        let alerts = [alert("cpu > 95%", "true", 97.5)];
+
+        assert_eq!(exit_codes(runner.run(start, &alerts)), vec![Some(0)]);
+        assert!(runner.run(start + Duration::from_secs(30), &alerts).is_empty());
+        assert_eq!(
+            exit_codes(runner.run(start + Duration::from_secs(60), &alerts)),
+            vec![Some(0)]
+        );
+    }
+
+    #[test]
+    fn hook_is_not_run_again_while_still_running() {
+        let mut runner = HookRunner::new(Duration::ZERO);
+        let alerts = [alert("cpu > 95%", "sleep 1", 97.5)];
+
+        let runs = runner.run(Instant::now(), &alerts);
+        assert_eq!(runs.len(), 1);
+        assert!(runner.run(Instant::now(), &alerts).is_empty());
+
+        assert_eq!(exit_codes(runs), vec![Some(0)]);
+        assert_eq!(runner.run(Instant::now(), &alerts).len(), 1);
+    }
+
+    #[test]
+    fn alerts_without_a_hook_run_nothing() {
+        let mut runner = HookRunner::new(Duration::ZERO);
+        let alerts = [Alert {
+            rule: "cpu > 95%".parse().unwrap(),
+            value: 97.5,
+        }];
+
+        assert!(runner.run(Instant::now(), &alerts).is_empty());
+    }
+}
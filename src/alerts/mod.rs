@@ -0,0 +1,4 @@
+pub mod alert_evaluator;
+pub mod dbus_notifier;
+pub mod hook_runner;
+pub mod notifier;
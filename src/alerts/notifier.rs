@@ -0,0 +1,88 @@
+use crate::alerts::alert_evaluator::Alert;
+use crate::fl;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A notification being sent, which resolves to an error message if it couldn't be delivered
+pub type NotifyFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+
+/// The text of the desktop notification for an alert
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AlertNotification {
+    pub summary: String,
+    pub body: String,
+}
+
+impl From<&Alert> for AlertNotification {
+    fn from(alert: &Alert) -> Self {
+        AlertNotification {
+            summary: fl!("alert-notification-summary", rule = alert.rule.to_string()),
+            body: fl!(
+                "alert-notification-body",
+                value = alert.rule.metric.format_value(alert.value)
+            ),
+        }
+    }
+}
+
+/// Delivers alert notifications to the user, i.e. over D-Bus to the desktop's notification
+/// service. The app only holds a `dyn Notifier`, so tests can stand in their own.
+pub trait Notifier: Send + Sync {
+    fn notify(&self, notification: AlertNotification) -> NotifyFuture;
+}
+
+/// Starts a notification for each alert. The futures are only awaited by the caller, so sending
+/// them doesn't hold up the monitor updates.
+pub fn notify_alerts(notifier: &dyn Notifier, alerts: &[Alert]) -> Vec<NotifyFuture> {
+    alerts
+        .iter()
+        .map(|alert| notifier.notify(AlertNotification::from(alert)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        notifications: Mutex<Vec<AlertNotification>>,
+    }
+
+    impl Notifier for RecordingNotifier {
+        fn notify(&self, notification: AlertNotification) -> NotifyFuture {
+            self.notifications.lock().unwrap().push(notification);
+            Box::pin(std::future::ready(Ok(())))
+        }
+    }
+
+    #[test]
+    fn each_alert_is_notified() {
+        let notifier = RecordingNotifier::default();
+        let alerts = vec![
+            Alert {
+                rule: "cpu > 95% for 30s".parse().unwrap(),
+                value: 97.0,
+            },
+            Alert {
+                rule: "filesystem:/ > 90%".parse().unwrap(),
+                value: 93.5,
+            },
+        ];
+
+        let futures = notify_alerts(&notifier, &alerts);
+
+        let expected: Vec<AlertNotification> = alerts.iter().map(AlertNotification::from).collect();
+        assert_eq!(futures.len(), 2);
+        assert_eq!(*notifier.notifications.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn no_alerts_send_no_notifications() {
+        let notifier = RecordingNotifier::default();
+
+        assert!(notify_alerts(&notifier, &[]).is_empty());
+        assert!(notifier.notifications.lock().unwrap().is_empty());
+    }
+}
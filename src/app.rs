@@ -1,22 +1,29 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::alerts::alert_evaluator::{Alert, AlertEvaluator};
+use crate::alerts::dbus_notifier::DbusNotifier;
+use crate::alerts::hook_runner::HookRunner;
+use crate::alerts::notifier::{notify_alerts, Notifier};
 use crate::configuration::app_configuration::{
-    AppConfiguration, CPU_SETTINGS_WINDOW_ID, DISK_SETTINGS_WINDOW_ID, GENERAL_SETTINGS_WINDOW_ID,
-    MAIN_SETTINGS_WINDOW_ID, MEMORY_SETTINGS_WINDOW_ID, NETWORK_SETTINGS_WINDOW_ID,
+    AppConfiguration, ALERTS_SETTINGS_WINDOW_ID, DASHBOARD_WINDOW_ID, GENERAL_SETTINGS_WINDOW_ID,
+    MAIN_SETTINGS_WINDOW_ID,
 };
-use crate::core::app_colours::AppColours;
+use crate::core::app_colours::{AppColours, EXT_WARM_GREY};
 use crate::core::app_icons::{AppIcons, APP_LOGO_ICON};
 use crate::core::app_text_measurements::AppTextMeasurements;
+use crate::core::metric_history::MetricHistory;
+use crate::core::monitor_health::{MonitorHealth, MonitorStatus};
 use crate::core::settings::{SettingsForm, SettingsFormEvent};
-use crate::monitors::cpu_monitor::{CpuMonitor, CpuStats};
-use crate::monitors::disk_monitor::{DiskMonitor, DiskStats};
-use crate::monitors::memory_monitor::{MemoryMonitor, MemoryStats};
-use crate::monitors::network_monitor::{NetworkMonitor, NetworkStats};
-use crate::sensors::proc_disk_stats_reader::ProcDiskStatsReader;
-use crate::sensors::proc_meminfo_reader::ProcMemInfoSensorReader;
-use crate::sensors::proc_net_dev_reader::ProcNetDevReader;
-use crate::sensors::proc_stat_reader::ProcStatSensorReader;
+use crate::fl;
+use crate::monitors::monitor::{MonitorResult, MonitorStats};
+use crate::monitors::monitor_registry::MonitorRegistry;
+use crate::monitors::suspend_detector::SuspendDetector;
+use crate::sensors::proc_uptime_reader::ProcUptimeReader;
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::ui::components::indicator::IndicatorPlaceholder;
 use crate::ui::components::no_indicator::{no_indicators_content, NoIndicatorProps};
+use crate::ui::dashboard::{Dashboard, DashboardRange};
 use crate::ui::settings_forms::main_settings_form::MainSettingsForm;
 use cosmic::app::{Core, Task};
 use cosmic::applet::cosmic_panel_config::{PanelAnchor, PanelSize};
@@ -24,15 +31,17 @@ use cosmic::config::{CosmicTk, COSMIC_TK};
 use cosmic::cosmic_config::{Config, CosmicConfigEntry};
 use cosmic::font::Font;
 use cosmic::iced::{window, Subscription};
-use cosmic::iced::{Alignment, Limits};
+use cosmic::iced::{Alignment, Color, Limits};
 use cosmic::iced_widget::{row, Column, Row};
-use cosmic::iced_winit::commands::popup::get_popup;
+use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 use cosmic::widget;
 use cosmic::widget::{autosize, container, divider, Id};
 use cosmic::{cosmic_config, Application, Element};
-use log::{error, info};
+use log::{error, info, warn};
 use once_cell::sync::Lazy;
 use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Instant;
 use tokio_util::sync::CancellationToken;
 
 pub const GLOBAL_APP_ID: &'static str = "dev.eidolon.cosmic-ext-applet-vitals";
@@ -61,14 +70,22 @@ pub struct AppState {
     configuration: AppConfiguration,
     /// The settings forms that are available for configuration of the monitors.
     settings_forms: BTreeMap<window::Id, SettingsForm>,
-    /// The current memory usage stats
-    memory: MemoryStats,
-    /// The current cpu usage stats
-    cpu: CpuStats,
-    /// The current network usage stats
-    network: NetworkStats,
-    /// The current disk usage stats   
-    disk: DiskStats,
+    /// The monitors available to the applet
+    monitor_registry: MonitorRegistry,
+    /// The latest stats of each monitor, by its settings window id
+    stats: BTreeMap<window::Id, Arc<dyn MonitorStats>>,
+    /// The poll health of each running monitor, by its settings window id
+    monitor_health: BTreeMap<window::Id, MonitorHealth>,
+    /// The recent values of every metric the monitors report
+    metric_history: MetricHistory,
+    /// The span of history shown by the dashboard
+    dashboard_range: DashboardRange,
+    /// Checks the monitor updates against the alert rules
+    alert_evaluator: AlertEvaluator,
+    /// Sends the notifications for raised alerts
+    notifier: Option<Arc<dyn Notifier>>,
+    /// Runs the hook commands of raised alerts
+    hook_runner: HookRunner,
     /// The popup id.
     popup: Option<window::Id>,
 }
@@ -76,20 +93,22 @@ pub struct AppState {
 /// The messages processed by the application update
 #[derive(Debug, Clone)]
 pub enum Message {
+    /// Toggle the dashboard popup
+    DashboardPopupOpened,
+    /// Toggle the detail popup of a monitor, identified by its settings window id
+    DetailPopupOpened(window::Id),
+    /// A span of history was selected in the dashboard
+    DashboardRangeSelected(DashboardRange),
     /// Toggle the main settings popup
     SettingsPopupOpened(window::Id),
     /// A settings popup was closed
     SettingsPopupClosed(window::Id),
     /// Start monitoring the system resources
     StartMonitoring,
-    /// The memory usage stats were updated
-    MemoryUpdate(MemoryStats),
-    /// The cpu usage stats were updated
-    CpuUpdate(CpuStats),
-    /// The network usage stats were updated
-    NetworkUpdate(NetworkStats),
-    /// The disk usage stats were updated
-    DiskUpdate(DiskStats),
+    /// A monitor was polled, identified by its settings window id
+    MonitorUpdate(window::Id, MonitorResult),
+    /// The notification for an alert was sent, or failed to send
+    AlertNotificationSent(Result<(), String>),
     /// The user has updated the settings form
     SettingsFormUpdate(SettingsFormEvent),
     /// The configuration file was changed externally
@@ -123,7 +142,12 @@ impl Application for AppState {
             })
             .unwrap_or_default();
 
-        let settings_forms = configuration.settings_form_options();
+        let monitor_registry = MonitorRegistry::default();
+        let settings_forms = configuration.settings_form_options(&monitor_registry);
+        let stats = monitor_registry
+            .definitions()
+            .map(|definition| (definition.id(), definition.default_stats()))
+            .collect();
         let app_colours = AppColours::from(&core.system_theme().cosmic().palette);
         let app_icons = AppIcons::new();
         let app_text_measurements = AppTextMeasurements::new();
@@ -142,6 +166,9 @@ impl Application for AppState {
             app_icons,
             app_text_measurements,
             configuration,
+            monitor_registry,
+            stats,
+            notifier: Some(Arc::new(DbusNotifier::session())),
             ..Default::default()
         };
 
@@ -172,20 +199,103 @@ impl Application for AppState {
 
     fn update(&mut self, message: Self::Message) -> Task<Self::Message> {
         match message {
+            Message::DashboardPopupOpened => {
+                // The dashboard button in a detail popup switches across to the dashboard
+                let close_detail = match self.popup {
+                    Some(content_id) if self.is_detail_window(&content_id) => {
+                        self.popup = None;
+                        self.monitor_registry.set_detail_visible(&content_id, false);
+                        destroy_popup(content_id)
+                    }
+                    Some(_) => return self.close_popup(),
+                    None => Task::none(),
+                };
+
+                info!("Opening dashboard popup");
+                self.popup = Some(DASHBOARD_WINDOW_ID.clone());
+
+                // The dashboard shares the settings popup, so the gear button can switch to them in place
+                let mut popup_settings = self.core.applet.get_popup_settings(
+                    self.core.main_window_id().unwrap(),
+                    MAIN_SETTINGS_WINDOW_ID.clone(),
+                    None,
+                    None,
+                    None,
+                );
+
+                popup_settings.positioner.size_limits = Limits::NONE
+                    .max_width(420.0)
+                    .min_width(380.0)
+                    .min_height(200.0)
+                    .max_height(1080.0);
+
+                return Task::batch([close_detail, get_popup(popup_settings)]);
+            }
+            Message::DetailPopupOpened(monitor_id) => {
+                if self.popup.is_some() {
+                    return self.close_popup();
+                }
+
+                let detail_window_id = self
+                    .monitor_registry
+                    .get(&monitor_id)
+                    .and_then(|definition| definition.detail_window_id());
+
+                // Monitors without details open the dashboard instead
+                let Some(detail_window_id) = detail_window_id else {
+                    return cosmic::task::message(Message::DashboardPopupOpened);
+                };
+
+                info!("Opening detail popup with id: {}", detail_window_id);
+                self.popup = Some(detail_window_id);
+                self.monitor_registry.set_detail_visible(&detail_window_id, true);
+
+                let mut popup_settings = self.core.applet.get_popup_settings(
+                    self.core.main_window_id().unwrap(),
+                    detail_window_id,
+                    None,
+                    None,
+                    None,
+                );
+
+                popup_settings.positioner.size_limits = Limits::NONE
+                    .max_width(372.0)
+                    .min_width(300.0)
+                    .min_height(100.0)
+                    .max_height(1080.0);
+
+                return get_popup(popup_settings);
+            }
+            Message::DashboardRangeSelected(range) => {
+                self.dashboard_range = range;
+            }
             Message::SettingsPopupOpened(target_id) => {
                 info!("Opening settings popup with id: {}", target_id);
 
+                // A detail popup has its own surface, so it is closed and the settings open on theirs
+                let close_detail = match self.popup {
+                    Some(content_id) if self.is_detail_window(&content_id) => {
+                        self.popup = None;
+                        self.monitor_registry.set_detail_visible(&content_id, false);
+                        destroy_popup(content_id)
+                    }
+                    _ => Task::none(),
+                };
+
+                let from_dashboard = self.popup == Some(DASHBOARD_WINDOW_ID.clone());
+
                 match self.popup {
-                    None => {
+                    Some(_) if !from_dashboard => self.popup = Some(target_id),
+                    _ => {
                         self.popup = Some(MAIN_SETTINGS_WINDOW_ID.clone());
                         // Ensure the configuration is up to date when we open the settings form.
                         self.refresh_configuration_from_disk();
                     }
-                    Some(_) => self.popup = Some(target_id),
                 };
 
-                if target_id != MAIN_SETTINGS_WINDOW_ID.clone() {
-                    return Task::none();
+                // The dashboard's popup is already open and is reused for the settings
+                if target_id != MAIN_SETTINGS_WINDOW_ID.clone() || from_dashboard {
+                    return close_detail;
                 }
 
                 let mut popup_settings = self.core.applet.get_popup_settings(
@@ -202,14 +312,24 @@ impl Application for AppState {
                     .min_height(200.0)
                     .max_height(1080.0);
 
-                return get_popup(popup_settings);
+                return Task::batch([close_detail, get_popup(popup_settings)]);
             }
             Message::SettingsPopupClosed(id) => {
                 if id == MAIN_SETTINGS_WINDOW_ID.clone() {
-                    info!("Closing main settings window");
+                    match self.popup.take() {
+                        // Nothing is changed from the dashboard, so there is nothing to save
+                        None => {}
+                        Some(content_id) if content_id == DASHBOARD_WINDOW_ID.clone() => {}
+                        Some(_) => {
+                            info!("Closing main settings window");
+                            self.save_configuration();
+                            return cosmic::task::message(Message::StartMonitoring);
+                        }
+                    }
+                } else if self.popup == Some(id) {
+                    // A detail popup has nothing to save
                     self.popup = None;
-                    self.save_configuration();
-                    return cosmic::task::message(Message::StartMonitoring);
+                    self.monitor_registry.set_detail_visible(&id, false);
                 }
             }
             Message::StartMonitoring => {
@@ -222,66 +342,54 @@ impl Application for AppState {
 
                 let cancellation_token = CancellationToken::new();
                 self.monitor_cancellation_token = Some(cancellation_token.clone());
+                self.monitor_health.clear();
+                self.alert_evaluator.update_configuration(&self.configuration.alerts);
+                self.hook_runner.set_cooldown(self.configuration.alerts.hook_cooldown);
 
-                let config = self.configuration.clone();
+                let sensor_root = SensorRoot::new(&self.configuration.general.sensor_root);
+                let mut schedule = self
+                    .monitor_registry
+                    .start_monitors(&self.configuration, &sensor_root, Instant::now());
 
                 return cosmic::Task::stream(async_stream::stream! {
-                    let mut memory_update_interval = tokio::time::interval(config.memory.update_interval);
-                    let mut cpu_update_interval = tokio::time::interval(config.cpu.update_interval);
-                    let mut network_update_interval = tokio::time::interval(config.network.update_interval);
-                    let mut disk_update_interval = tokio::time::interval(config.disk.update_interval);
-
-                    let mut memory_monitor = (!config.memory.hide_indicator)
-                        .then(|| MemoryMonitor::new(ProcMemInfoSensorReader, &config));
-                    let mut cpuinfo_reader = (!config.cpu.hide_indicator)
-                        .then(|| CpuMonitor::new(ProcStatSensorReader, &config));
-                    let mut network_monitor = (!config.network.hide_indicator)
-                        .then(|| NetworkMonitor::new(ProcNetDevReader, &config));
-                    let mut disk_monitor = (!config.disk.hide_indicator)
-                        .then(|| DiskMonitor::new(ProcDiskStatsReader, &config));
-
-                    loop {
+                    let mut suspend_detector = SuspendDetector::new(ProcUptimeReader::new(sensor_root));
+
+                    while let Some(next_poll) = schedule.next_poll() {
                         tokio::select! {
-                            _ = memory_update_interval.tick(), if !config.memory.hide_indicator => {
-                                if let Some(memory_monitor) = memory_monitor.as_mut() {
-                                    yield Message::MemoryUpdate(memory_monitor.poll().unwrap_or_default());
-                                }
-                            },
-                            _ = cpu_update_interval.tick(), if !config.cpu.hide_indicator => {
-                                if let Some(cpu_monitor) = cpuinfo_reader.as_mut() {
-                                    yield Message::CpuUpdate(cpu_monitor.poll().unwrap_or_default());
-                                }
-                            },
-                            _ = network_update_interval.tick(), if !config.network.hide_indicator => {
-                                if let Some(network_monitor) = network_monitor.as_mut() {
-                                    yield Message::NetworkUpdate(network_monitor.poll().unwrap_or_default());
-                                }
-                            },
-                            _ = disk_update_interval.tick(), if !config.disk.hide_indicator => {
-                                if let Some(disk_monitor) = disk_monitor.as_mut() {
-                                    yield Message::DiskUpdate(disk_monitor.poll().unwrap_or_default());
-                                }
-                            },
-                            _ = cancellation_token.cancelled() => {
-                                break;
-                            }
+                            _ = tokio::time::sleep_until(next_poll.into()) => {},
+                            _ = cancellation_token.cancelled() => break,
+                        };
+
+                        // Timers don't count time spent suspended, so check before every poll rather than on a
+                        // timer of its own, which could fire after the first poll following a resume.
+                        if suspend_detector.has_resumed() {
+                            info!("Resetting monitors after resume");
+                            schedule.reset();
                         }
 
+                        for (monitor_id, result) in schedule.poll_due(Instant::now()) {
+                            yield Message::MonitorUpdate(monitor_id, result);
+                        }
                     }
                 })
                 .map(cosmic::Action::App);
             }
-            Message::MemoryUpdate(memory_usage) => {
-                self.memory = memory_usage;
-            }
-            Message::CpuUpdate(cpu_usage) => {
-                self.cpu = cpu_usage;
-            }
-            Message::NetworkUpdate(network_usage) => {
-                self.network = network_usage;
+            Message::MonitorUpdate(monitor_id, result) => {
+                if let Some(stats) = self.apply_sensor_result(monitor_id, result) {
+                    let now = Instant::now();
+                    self.metric_history.record(now, stats.metrics());
+                    let alerts = self.alert_evaluator.observe(now, &stats.alert_values());
+                    self.stats.insert(monitor_id, stats);
+                    // The hooks log their own exit codes, so there is nothing to wait for
+                    self.hook_runner.run(now, &alerts);
+
+                    return self.send_alert_notifications(alerts);
+                }
             }
-            Message::DiskUpdate(disk_usage) => {
-                self.disk = disk_usage;
+            Message::AlertNotificationSent(result) => {
+                if let Err(err) = result {
+                    warn!("Failed to send alert notification: {}", err);
+                }
             }
             Message::ConfigFileChanged(configuration) => {
                 self.configuration = configuration;
@@ -320,27 +428,32 @@ impl Application for AppState {
 
         elements.push(divider::vertical::default().into());
 
-        if let Some(element) = self.cpu.draw(&self, is_horizontal) {
-            elements.push(element);
-            elements.push(divider::vertical::default().into())
-        }
-        if let Some(element) = self.memory.draw(&self, is_horizontal) {
-            elements.push(element);
-            elements.push(divider::vertical::default().into())
-        }
-        if let Some(element) = self.network.draw(&self, is_horizontal) {
-            elements.push(element);
-            elements.push(divider::vertical::default().into())
-        }
-        if let Some(element) = self.disk.draw(&self, is_horizontal) {
-            elements.push(element);
-            elements.push(divider::vertical::default().into());
+        for definition in self.monitor_registry.definitions() {
+            let element = self
+                .stats
+                .get(&definition.id())
+                .and_then(|stats| stats.draw(self, is_horizontal));
+
+            if let Some(element) = element {
+                let button = widget::button::custom(element)
+                    .class(cosmic::theme::Button::AppletIcon)
+                    .padding(0)
+                    .on_press(Message::DetailPopupOpened(definition.id()));
+
+                elements.push(button.into());
+                elements.push(divider::vertical::default().into());
+            }
         }
         if elements.len() <= 1 {
-            elements.push(no_indicators_content(NoIndicatorProps {
+            let button = widget::button::custom(no_indicators_content(NoIndicatorProps {
                 icon: self.app_icons.get(APP_LOGO_ICON),
                 size: self.icon_size(),
-            }));
+            }))
+            .class(cosmic::theme::Button::AppletIcon)
+            .padding(0)
+            .on_press(Message::DashboardPopupOpened);
+
+            elements.push(button.into());
             elements.push(divider::vertical::default().into());
         }
 
@@ -362,19 +475,29 @@ impl Application for AppState {
             [self.core.applet.suggested_padding(false), 1]
         };
 
-        let button = widget::button::custom(wrapper)
-            .class(cosmic::theme::Button::AppletIcon)
-            .padding(padding)
-            .on_press(Message::SettingsPopupOpened(MAIN_SETTINGS_WINDOW_ID.clone()));
+        // Each indicator opens its own details, while a right click anywhere goes straight to the settings
+        let indicators = widget::mouse_area(container(wrapper).padding(padding))
+            .on_right_press(Message::SettingsPopupOpened(MAIN_SETTINGS_WINDOW_ID.clone()));
 
-        autosize::autosize(container(button), AUTOSIZE_MAIN_ID.clone()).into()
+        autosize::autosize(container(indicators), AUTOSIZE_MAIN_ID.clone()).into()
     }
 
     fn view_window(&'_ self, _id: window::Id) -> Element<'_, Self::Message> {
         let content_id = self.popup.unwrap_or_else(|| MAIN_SETTINGS_WINDOW_ID.clone());
 
-        let content = if content_id == MAIN_SETTINGS_WINDOW_ID.clone() {
-            MainSettingsForm::draw(self.configuration())
+        let detail = self
+            .monitor_registry
+            .definitions()
+            .find(|definition| definition.detail_window_id() == Some(content_id))
+            .and_then(|definition| self.stats.get(&definition.id()))
+            .and_then(|stats| stats.draw_detail(self));
+
+        let content = if let Some(detail) = detail {
+            container(detail)
+        } else if content_id == DASHBOARD_WINDOW_ID.clone() {
+            Dashboard::draw(self)
+        } else if content_id == MAIN_SETTINGS_WINDOW_ID.clone() {
+            MainSettingsForm::draw(self.configuration(), &self.monitor_registry)
         } else {
             match self.settings_forms.get(&content_id) {
                 None => container(row!["No settings window configured."]),
@@ -411,42 +534,119 @@ impl AppState {
         &self.active_interface_font
     }
 
-    fn update_configuration(&mut self) {
-        info!("Updating configuration: {:?}", self.configuration);
+    pub fn monitor_health(&self, monitor_id: &window::Id) -> Option<&MonitorHealth> {
+        self.monitor_health.get(monitor_id)
+    }
 
-        let memory_settings_form = self
-            .settings_forms
-            .get(&MEMORY_SETTINGS_WINDOW_ID.clone())
-            .expect("No memory settings form configured.");
+    pub fn metric_history(&self) -> &MetricHistory {
+        &self.metric_history
+    }
 
-        let cpu_settings_form = self
-            .settings_forms
-            .get(&CPU_SETTINGS_WINDOW_ID.clone())
-            .expect("No cpu settings form configured.");
+    pub fn dashboard_range(&self) -> DashboardRange {
+        self.dashboard_range
+    }
 
-        let network_settings_form = self
-            .settings_forms
-            .get(&NETWORK_SETTINGS_WINDOW_ID.clone())
-            .expect("No network settings form configured.");
+    /// The placeholder for a failing monitor's indicator. A stale indicator keeps its last values,
+    /// dimmed, while an unavailable one replaces them. Both show the error on hover.
+    pub fn indicator_placeholder(&self, monitor_id: &window::Id) -> Option<IndicatorPlaceholder> {
+        let health = self.monitor_health(monitor_id)?;
+        let error = health.last_error.as_ref()?.to_string();
 
-        let disk_settings_form = self
-            .settings_forms
-            .get(&DISK_SETTINGS_WINDOW_ID.clone())
-            .expect("No disk settings form configured.");
+        let text = match health.status() {
+            MonitorStatus::Healthy => return None,
+            MonitorStatus::Stale => None,
+            MonitorStatus::Unavailable => Some(fl!("indicator-unavailable")),
+        };
+
+        let tooltip = match health.last_success {
+            Some(last_success) => fl!(
+                "indicator-failing-since",
+                error = error,
+                seconds = last_success.elapsed().as_secs(),
+                failures = health.consecutive_failures
+            ),
+            None => error,
+        };
+
+        let colour = self
+            .app_colours
+            .get(EXT_WARM_GREY)
+            .map_or(Color::from_rgb(0.5, 0.5, 0.5), |c| Color::new(c.red, c.green, c.blue, c.alpha));
+
+        Some(IndicatorPlaceholder { text, colour, tooltip })
+    }
+
+    /// Records a monitor update in its health and unwraps it. A failure keeps the last stats, so a
+    /// stale indicator can still show them.
+    fn apply_sensor_result<T>(&mut self, monitor_id: window::Id, result: Result<T, SensorError>) -> Option<T> {
+        let health = self.monitor_health.entry(monitor_id).or_default();
+
+        match result {
+            Ok(stats) => {
+                health.record_success(Instant::now());
+                Some(stats)
+            }
+            Err(err) => {
+                if err.is_permanent() {
+                    error!("{}", err);
+                } else {
+                    warn!("{}", err);
+                }
+
+                health.record_failure(err);
+                None
+            }
+        }
+    }
+
+    /// Sends a notification for each raised alert without waiting for them to be delivered
+    fn send_alert_notifications(&self, alerts: Vec<Alert>) -> Task<Message> {
+        let Some(notifier) = &self.notifier else {
+            return Task::none();
+        };
+
+        for alert in &alerts {
+            info!("Alert raised: {} at {}", alert.rule, alert.rule.metric.format_value(alert.value));
+        }
+
+        Task::batch(
+            notify_alerts(notifier.as_ref(), &alerts)
+                .into_iter()
+                .map(|notification| {
+                    cosmic::task::future(async move { Message::AlertNotificationSent(notification.await) })
+                }),
+        )
+    }
+
+    fn update_configuration(&mut self) {
+        info!("Updating configuration: {:?}", self.configuration);
 
         let general_settings_form = self
             .settings_forms
             .get(&GENERAL_SETTINGS_WINDOW_ID.clone())
             .expect("No general settings form configured.");
 
-        self.configuration = AppConfiguration {
+        let alerts_settings_form = self
+            .settings_forms
+            .get(&ALERTS_SETTINGS_WINDOW_ID.clone())
+            .expect("No alerts settings form configured.");
+
+        let mut configuration = AppConfiguration {
             general: self.configuration.general.update(general_settings_form),
-            memory: self.configuration.memory.update(memory_settings_form),
-            cpu: self.configuration.cpu.update(cpu_settings_form),
-            network: self.configuration.network.update(network_settings_form),
-            disk: self.configuration.disk.update(disk_settings_form),
-            ..Default::default()
+            alerts: self.configuration.alerts.update(alerts_settings_form),
+            ..self.configuration.clone()
+        };
+
+        for definition in self.monitor_registry.definitions() {
+            let settings_form = self
+                .settings_forms
+                .get(&definition.id())
+                .expect(format!("No settings form configured with key: {}", definition.id()).as_str());
+
+            definition.update_configuration(&mut configuration, settings_form);
         }
+
+        self.configuration = configuration;
     }
 
     fn refresh_configuration_from_disk(&mut self) {
@@ -457,7 +657,7 @@ impl AppState {
             });
 
             self.configuration = loaded;
-            self.settings_forms = self.configuration.settings_form_options();
+            self.settings_forms = self.configuration.settings_form_options(&self.monitor_registry);
         } else {
             error!("Failed to create config context for reload");
         }
@@ -473,6 +673,28 @@ impl AppState {
         }
     }
 
+    /// Whether a popup content id is one of the monitors' detail popups, which have their own surface
+    fn is_detail_window(&self, content_id: &window::Id) -> bool {
+        self.monitor_registry
+            .definitions()
+            .any(|definition| definition.detail_window_id().as_ref() == Some(content_id))
+    }
+
+    /// Closes whichever popup is open, on the surface it was opened on
+    fn close_popup(&self) -> Task<Message> {
+        info!("Closing popup");
+
+        let surface_id = match self.popup {
+            Some(content_id) if self.is_detail_window(&content_id) => content_id,
+            _ => MAIN_SETTINGS_WINDOW_ID.clone(),
+        };
+
+        Task::batch([
+            destroy_popup(surface_id),
+            cosmic::task::message(Message::SettingsPopupClosed(surface_id)),
+        ])
+    }
+
     pub fn font_size(&self, horizontal: bool) -> u16 {
         let configuration = self.configuration();
 
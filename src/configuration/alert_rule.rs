@@ -0,0 +1,381 @@
+use crate::fl;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+const FILESYSTEM_METRIC_PREFIX: &str = "filesystem:";
+
+/// The binary units a byte threshold can be written in, largest first
+const BYTE_UNITS: [(&str, u64); 4] = [("TiB", 1 << 40), ("GiB", 1 << 30), ("MiB", 1 << 20), ("KiB", 1 << 10)];
+
+/// i.e. "cpu > 95% for 30s", "memory-available < 1GiB" or "filesystem:/home > 90%"
+static ALERT_RULE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?P<metric>[a-z-]+(?::\S+)?)\s*(?P<comparison>[<>])\s*(?P<value>\d+(?:\.\d+)?)\s*(?P<unit>%|[A-Za-z]+)?(?P<rate>/s)?(?:\s+for\s+(?P<duration>\d+)\s*(?P<duration_unit>s|m|h))?\s*$",
+    )
+    .expect("Alert rule pattern is valid")
+});
+
+/// What an alert rule's threshold is measured in
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlertUnit {
+    Percent,
+    Bytes,
+    BytesPerSecond,
+}
+
+/// A value the monitors report that an alert rule can watch
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AlertMetric {
+    /// The CPU usage in percent
+    CpuUsage,
+    /// The memory in use, in percent of the total
+    MemoryUsed,
+    /// The memory still available, in bytes
+    MemoryAvailable,
+    NetworkReceived,
+    NetworkTransmitted,
+    DiskRead,
+    DiskWritten,
+    /// How full the filesystem mounted at the path is, in percent
+    FilesystemUsed(String),
+}
+
+impl AlertMetric {
+    /// The name the metric is written as in a rule, and passed to anything acting on an alert
+    pub fn key(&self) -> String {
+        match self {
+            AlertMetric::CpuUsage => "cpu".to_string(),
+            AlertMetric::MemoryUsed => "memory".to_string(),
+            AlertMetric::MemoryAvailable => "memory-available".to_string(),
+            AlertMetric::NetworkReceived => "network-rx".to_string(),
+            AlertMetric::NetworkTransmitted => "network-tx".to_string(),
+            AlertMetric::DiskRead => "disk-read".to_string(),
+            AlertMetric::DiskWritten => "disk-write".to_string(),
+            AlertMetric::FilesystemUsed(path) => format!("{}{}", FILESYSTEM_METRIC_PREFIX, path),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "cpu" => Some(AlertMetric::CpuUsage),
+            "memory" => Some(AlertMetric::MemoryUsed),
+            "memory-available" => Some(AlertMetric::MemoryAvailable),
+            "network-rx" => Some(AlertMetric::NetworkReceived),
+            "network-tx" => Some(AlertMetric::NetworkTransmitted),
+            "disk-read" => Some(AlertMetric::DiskRead),
+            "disk-write" => Some(AlertMetric::DiskWritten),
+            _ => key
+                .strip_prefix(FILESYSTEM_METRIC_PREFIX)
+                .filter(|path| path.starts_with('/'))
+                .map(|path| AlertMetric::FilesystemUsed(path.to_string())),
+        }
+    }
+
+    pub fn unit(&self) -> AlertUnit {
+        match self {
+            AlertMetric::CpuUsage | AlertMetric::MemoryUsed | AlertMetric::FilesystemUsed(_) => AlertUnit::Percent,
+            AlertMetric::MemoryAvailable => AlertUnit::Bytes,
+            AlertMetric::NetworkReceived
+            | AlertMetric::NetworkTransmitted
+            | AlertMetric::DiskRead
+            | AlertMetric::DiskWritten => AlertUnit::BytesPerSecond,
+        }
+    }
+
+    /// A value of the metric for a notification, i.e. "97.2%" or "1.5GiB/s"
+    pub fn format_value(&self, value: f64) -> String {
+        let mut value = value;
+        let mut unit = "B";
+
+        match self.unit() {
+            AlertUnit::Percent => return format!("{:.1}%", value),
+            AlertUnit::Bytes | AlertUnit::BytesPerSecond => {
+                if let Some((name, size)) = BYTE_UNITS.iter().find(|(_, size)| value >= *size as f64) {
+                    value /= *size as f64;
+                    unit = name;
+                }
+            }
+        }
+
+        match self.unit() {
+            AlertUnit::BytesPerSecond => format!("{:.1}{}/s", value, unit),
+            _ => format!("{:.1}{}", value, unit),
+        }
+    }
+}
+
+/// Whether a rule is breached when the value is above or below its threshold
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AlertComparison {
+    Above,
+    Below,
+}
+
+impl AlertComparison {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            AlertComparison::Above => ">",
+            AlertComparison::Below => "<",
+        }
+    }
+}
+
+/// A condition that raises an alert, written as "<metric> <comparison> <threshold> [for <duration>]",
+/// i.e. "cpu > 95% for 30s", and the hook command it runs when raised. The condition is stored in
+/// the configuration as that text.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredAlertRule", into = "StoredAlertRule")]
+pub struct AlertRule {
+    pub metric: AlertMetric,
+    pub comparison: AlertComparison,
+    /// The threshold in the metric's unit: percent, bytes or bytes per second
+    pub threshold: u64,
+    /// How long the threshold has to be breached before the alert is raised
+    pub duration: Duration,
+    /// A shell command to run when the rule is raised, i.e. to pause a sync client
+    pub hook: Option<String>,
+}
+
+/// A rule as it is stored in the configuration, with its condition as text
+#[derive(Serialize, Deserialize)]
+struct StoredAlertRule {
+    condition: String,
+    #[serde(default)]
+    hook: Option<String>,
+}
+
+impl AlertRule {
+    pub fn is_breached(&self, value: f64) -> bool {
+        match self.comparison {
+            AlertComparison::Above => value > self.threshold as f64,
+            AlertComparison::Below => value < self.threshold as f64,
+        }
+    }
+
+    /// Whether the value is back past the threshold by more than `hysteresis_percent` of it, so a
+    /// value hovering around the threshold doesn't raise the alert over and over
+    pub fn is_cleared(&self, value: f64, hysteresis_percent: u64) -> bool {
+        let margin = self.threshold as f64 * hysteresis_percent as f64 / 100.0;
+
+        match self.comparison {
+            AlertComparison::Above => value <= self.threshold as f64 - margin,
+            AlertComparison::Below => value >= self.threshold as f64 + margin,
+        }
+    }
+}
+
+impl FromStr for AlertRule {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || fl!("settings-alert-rule-error");
+        let captures = ALERT_RULE_PATTERN.captures(input).ok_or_else(error)?;
+
+        let metric = AlertMetric::from_key(&captures["metric"]).ok_or_else(error)?;
+        let comparison = match &captures["comparison"] {
+            ">" => AlertComparison::Above,
+            _ => AlertComparison::Below,
+        };
+        let value = captures["value"].parse::<f64>().map_err(|_| error())?;
+        let unit = captures.name("unit").map(|unit| unit.as_str());
+        let is_rate = captures.name("rate").is_some();
+
+        let threshold = match (metric.unit(), unit) {
+            (AlertUnit::Percent, None | Some("%")) if !is_rate && value <= 100.0 && value.fract() == 0.0 => {
+                value as u64
+            }
+            (AlertUnit::Bytes, unit) if !is_rate => (value * byte_multiplier(unit).ok_or_else(error)?).round() as u64,
+            (AlertUnit::BytesPerSecond, unit) => (value * byte_multiplier(unit).ok_or_else(error)?).round() as u64,
+            _ => return Err(error()),
+        };
+
+        let duration = match (captures.name("duration"), captures.name("duration_unit")) {
+            (Some(duration), Some(duration_unit)) => {
+                let duration = duration.as_str().parse::<u64>().map_err(|_| error())?;
+                let seconds = match duration_unit.as_str() {
+                    "h" => duration.checked_mul(60 * 60),
+                    "m" => duration.checked_mul(60),
+                    _ => Some(duration),
+                };
+                Duration::from_secs(seconds.ok_or_else(error)?)
+            }
+            _ => Duration::ZERO,
+        };
+
+        Ok(AlertRule {
+            metric,
+            comparison,
+            threshold,
+            duration,
+            hook: None,
+        })
+    }
+}
+
+impl fmt::Display for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.metric.key(), self.comparison.symbol())?;
+
+        match self.metric.unit() {
+            AlertUnit::Percent => write!(f, "{}%", self.threshold)?,
+            AlertUnit::Bytes => write!(f, "{}", format_bytes(self.threshold))?,
+            AlertUnit::BytesPerSecond => write!(f, "{}/s", format_bytes(self.threshold))?,
+        }
+
+        let seconds = self.duration.as_secs();
+
+        match seconds {
+            0 => Ok(()),
+            _ if seconds % (60 * 60) == 0 => write!(f, " for {}h", seconds / (60 * 60)),
+            _ if seconds % 60 == 0 => write!(f, " for {}m", seconds / 60),
+            _ => write!(f, " for {}s", seconds),
+        }
+    }
+}
+
+impl TryFrom<StoredAlertRule> for AlertRule {
+    type Error = String;
+
+    fn try_from(stored: StoredAlertRule) -> Result<Self, Self::Error> {
+        let rule: AlertRule = stored.condition.parse()?;
+
+        Ok(AlertRule {
+            hook: stored.hook,
+            ..rule
+        })
+    }
+}
+
+impl From<AlertRule> for StoredAlertRule {
+    fn from(rule: AlertRule) -> Self {
+        StoredAlertRule {
+            condition: rule.to_string(),
+            hook: rule.hook,
+        }
+    }
+}
+
+/// The number of bytes in a unit, where both the IEC (KiB) and SI (kB) units are accepted
+fn byte_multiplier(unit: Option<&str>) -> Option<f64> {
+    let multiplier: u64 = match unit.map(|unit| unit.to_ascii_lowercase()).as_deref() {
+        None | Some("b") => 1,
+        Some("kib") => 1 << 10,
+        Some("mib") => 1 << 20,
+        Some("gib") => 1 << 30,
+        Some("tib") => 1 << 40,
+        Some("kb") => 1_000,
+        Some("mb") => 1_000_000,
+        Some("gb") => 1_000_000_000,
+        Some("tb") => 1_000_000_000_000,
+        _ => return None,
+    };
+
+    Some(multiplier as f64)
+}
+
+/// A byte count in the largest binary unit it is a whole multiple of, so it parses back the same
+fn format_bytes(bytes: u64) -> String {
+    BYTE_UNITS
+        .iter()
+        .find(|(_, size)| bytes >= *size && bytes % size == 0)
+        .map(|(name, size)| format!("{}{}", bytes / size, name))
+        .unwrap_or_else(|| format!("{}B", bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(input: &str) -> AlertRule {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn percent_rule_is_parsed() {
+        assert_eq!(
+            rule("cpu > 95% for 30s"),
+            AlertRule {
+                metric: AlertMetric::CpuUsage,
+                comparison: AlertComparison::Above,
+                threshold: 95,
+                duration: Duration::from_secs(30),
+                hook: None,
+            }
+        );
+    }
+
+    #[test]
+    fn byte_units_are_converted() {
+        assert_eq!(rule("memory-available < 1 GiB").threshold, 1 << 30);
+        assert_eq!(rule("memory-available < 512MB").threshold, 512_000_000);
+        assert_eq!(rule("network-rx > 1.5MiB/s for 2m").threshold, 1_572_864);
+        assert_eq!(rule("network-rx > 1.5MiB/s for 2m").duration, Duration::from_secs(120));
+        assert_eq!(rule("disk-write > 4096").threshold, 4096);
+    }
+
+    #[test]
+    fn filesystem_rule_has_the_mount_point() {
+        let rule = rule("filesystem:/home > 90%");
+
+        assert_eq!(rule.metric, AlertMetric::FilesystemUsed("/home".to_string()));
+        assert_eq!(rule.duration, Duration::ZERO);
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for input in [
+            "",
+            "cpu",
+            "cpu >> 90%",
+            "gpu > 90%",
+            "cpu > 90 GiB",
+            "cpu > 101%",
+            "cpu > 90.5%",
+            "memory-available < 1 parsec",
+            "memory-available < 1GiB/s",
+            "filesystem:home > 90%",
+            "cpu > 90% for ever",
+        ] {
+            assert!(input.parse::<AlertRule>().is_err(), "{} should be rejected", input);
+        }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for input in [
+            "cpu > 95% for 30s",
+            "memory-available < 1GiB",
+            "network-tx > 3MiB/s for 2m",
+            "disk-read > 1000B/s for 1h",
+            "filesystem:/ > 90%",
+        ] {
+            assert_eq!(rule(input).to_string(), input);
+        }
+    }
+
+    #[test]
+    fn hysteresis_delays_clearing() {
+        let above = rule("cpu > 90%");
+        let below = rule("memory-available < 1000B");
+
+        assert!(above.is_breached(91.0) && !above.is_breached(90.0));
+        assert!(!above.is_cleared(85.0, 10));
+        assert!(above.is_cleared(81.0, 10));
+        assert!(!below.is_cleared(1050.0, 10));
+        assert!(below.is_cleared(1100.0, 10));
+    }
+
+    #[test]
+    fn values_are_formatted_in_the_metric_unit() {
+        assert_eq!(AlertMetric::CpuUsage.format_value(97.25), "97.2%");
+        assert_eq!(
+            AlertMetric::MemoryAvailable.format_value(1.5 * (1 << 30) as f64),
+            "1.5GiB"
+        );
+        assert_eq!(AlertMetric::DiskWritten.format_value(512.0), "512.0B/s");
+    }
+}
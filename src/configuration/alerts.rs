@@ -0,0 +1,184 @@
+use crate::configuration::alert_rule::{AlertMetric, AlertRule};
+use crate::configuration::app_configuration::{
+    ALERTS_ENABLED_SETTING_KEY, ALERTS_SETTINGS_WINDOW_ID, ALERT_COOLDOWN_SETTING_KEY, ALERT_HOOK_COOLDOWN_SETTING_KEY,
+    ALERT_HOOK_SETTING_KEYS, ALERT_HYSTERESIS_SETTING_KEY, ALERT_RULE_SETTING_KEYS,
+};
+use crate::configuration::validation::ConfigurationValidation;
+use crate::core::settings::SettingsForm;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The rules that raise a desktop notification, and run their hook, when a monitored value
+/// crosses a threshold
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfiguration {
+    /// Whether alerts are raised, so the rules can be silenced without losing them
+    pub enabled: bool,
+    /// The rule in each slot of the settings form, or None for an empty slot, so a rule keeps its
+    /// slot when the slot before it is cleared
+    pub rules: Vec<Option<AlertRule>>,
+    /// How long after a notification the same rule stays quiet, even if it is raised again
+    pub cooldown: Duration,
+    /// How far back past its threshold a value has to go, in percent of the threshold, before the
+    /// rule can be raised again
+    pub hysteresis_percent: u64,
+    /// How long after a hook has started before the same hook can run again
+    pub hook_cooldown: Duration,
+}
+
+impl Default for AlertsConfiguration {
+    fn default() -> Self {
+        AlertsConfiguration {
+            enabled: true,
+            rules: Vec::new(),
+            cooldown: Duration::from_secs(5 * 60),
+            hysteresis_percent: 5,
+            hook_cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+impl AlertsConfiguration {
+    /// The mount points the filesystem rules watch, which the disk monitor has to read
+    pub fn filesystem_paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self
+            .rules
+            .iter()
+            .flatten()
+            .filter_map(|rule| match &rule.metric {
+                AlertMetric::FilesystemUsed(path) => Some(path.clone()),
+                _ => None,
+            })
+            .collect();
+
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    pub fn update(&self, settings_form: &SettingsForm) -> Self {
+        if settings_form.settings_window_id != ALERTS_SETTINGS_WINDOW_ID.clone() {
+            panic!("Attempted to update Alerts settings from a non-alerts settings window.")
+        }
+
+        let rules = ALERT_RULE_SETTING_KEYS
+            .iter()
+            .enumerate()
+            .zip(ALERT_HOOK_SETTING_KEYS)
+            .map(|((slot, key), hook_key)| {
+                let rule = ConfigurationValidation::sanitise_alert_rule(
+                    settings_form
+                        .values
+                        .get(key)
+                        .expect("Alert rule missing from settings form options")
+                        .value
+                        .clone(),
+                    self.rules.get(slot).cloned().flatten(),
+                )?;
+                let hook = ConfigurationValidation::sanitise_alert_hook(
+                    settings_form
+                        .values
+                        .get(hook_key)
+                        .expect("Alert hook missing from settings form options")
+                        .value
+                        .clone(),
+                );
+
+                Some(AlertRule { hook, ..rule })
+            })
+            .collect();
+
+        AlertsConfiguration {
+            enabled: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(ALERTS_ENABLED_SETTING_KEY)
+                    .expect("Alerts enabled missing from settings form options")
+                    .value
+                    .clone(),
+                self.enabled,
+            ),
+            rules,
+            cooldown: ConfigurationValidation::sanitise_alert_cooldown(
+                settings_form
+                    .values
+                    .get(ALERT_COOLDOWN_SETTING_KEY)
+                    .expect("Alert cooldown missing from settings form options")
+                    .value
+                    .clone(),
+                self.cooldown,
+            ),
+            hysteresis_percent: ConfigurationValidation::sanitise_hysteresis(
+                settings_form
+                    .values
+                    .get(ALERT_HYSTERESIS_SETTING_KEY)
+                    .expect("Alert hysteresis missing from settings form options")
+                    .value
+                    .clone(),
+                self.hysteresis_percent,
+            ),
+            hook_cooldown: ConfigurationValidation::sanitise_alert_cooldown(
+                settings_form
+                    .values
+                    .get(ALERT_HOOK_COOLDOWN_SETTING_KEY)
+                    .expect("Alert hook cooldown missing from settings form options")
+                    .value
+                    .clone(),
+                self.hook_cooldown,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form_with_rules(configuration: &AlertsConfiguration, rules: [&str; 4]) -> SettingsForm {
+        let mut settings_form = SettingsForm::from(configuration);
+
+        for (key, rule) in ALERT_RULE_SETTING_KEYS.iter().zip(rules) {
+            settings_form.values.get_mut(key).unwrap().value = rule.to_string();
+        }
+
+        settings_form
+    }
+
+    #[test]
+    fn rules_keep_their_slot_after_an_empty_slot() {
+        let configuration = AlertsConfiguration::default();
+
+        let configuration =
+            configuration.update(&form_with_rules(&configuration, ["cpu > 90%", "", "memory > 80%", ""]));
+
+        assert_eq!(configuration.rules[1], None);
+        assert_eq!(configuration.rules[2], "memory > 80%".parse().ok());
+    }
+
+    #[test]
+    fn an_unfinished_rule_after_an_empty_slot_keeps_the_rule_of_its_own_slot() {
+        let configuration = AlertsConfiguration::default();
+        let configuration =
+            configuration.update(&form_with_rules(&configuration, ["cpu > 90%", "", "memory > 80%", ""]));
+
+        let configuration =
+            configuration.update(&form_with_rules(&configuration, ["cpu > 90%", "", "memory >", "disk-"]));
+
+        assert_eq!(configuration.rules[2], "memory > 80%".parse().ok());
+        assert_eq!(configuration.rules[3], None);
+        assert_eq!(configuration.rules.iter().flatten().count(), 2);
+    }
+
+    #[test]
+    fn a_hook_without_a_rule_is_reported_in_the_form() {
+        let mut settings_form = form_with_rules(&AlertsConfiguration::default(), ["", "cpu > 90%", "", ""]);
+        settings_form.values.get_mut(ALERT_HOOK_SETTING_KEYS[0]).unwrap().value = "notify-send".to_string();
+        settings_form.values.get_mut(ALERT_HOOK_SETTING_KEYS[1]).unwrap().value = "notify-send".to_string();
+
+        let validate_hook = |slot: usize| settings_form.validate_against_other_fields(ALERT_HOOK_SETTING_KEYS[slot]);
+
+        assert!(validate_hook(0).is_err());
+        assert!(validate_hook(1).is_ok());
+    }
+}
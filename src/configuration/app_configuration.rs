@@ -7,7 +7,14 @@ use once_cell::sync::Lazy;
 use std::collections::BTreeMap;
 use crate::configuration::disk::DiskConfiguration;
 use crate::configuration::general::GeneralConfiguration;
+use crate::configuration::file::FileConfiguration;
+use crate::configuration::power::PowerConfiguration;
+use crate::configuration::paging::PagingConfiguration;
+use crate::configuration::arc::ArcConfiguration;
+use crate::configuration::raid::RaidConfiguration;
+use crate::configuration::alerts::AlertsConfiguration;
 use crate::core::settings::SettingsForm;
+use crate::monitors::monitor_registry::MonitorRegistry;
 
 pub static MAIN_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
 pub static GENERAL_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
@@ -16,18 +23,61 @@ pub static MEMORY_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new
 pub static NETWORK_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> =
     Lazy::new(|| cosmic::iced::window::Id::unique());
 pub static DISK_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static FILE_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static POWER_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static PAGING_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static ARC_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static RAID_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static CPU_DETAIL_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static MEMORY_DETAIL_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static NETWORK_DETAIL_WINDOW_ID: Lazy<cosmic::iced::window::Id> =
+    Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static DISK_DETAIL_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+/// The dashboard is shown in the same popup as the settings, so this only identifies its content
+pub static ALERTS_SETTINGS_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
+pub static DASHBOARD_WINDOW_ID: Lazy<cosmic::iced::window::Id> = Lazy::new(|| cosmic::iced::window::Id::unique());
 
 pub const SENSOR_INTERVAL_MINIMUM_IN_MS: u64 = 250;
 pub const SENSOR_MAX_SAMPLES_MINIMUM: usize = 1;
 pub const SENSOR_MAX_LABEL_LENGTH: usize = 10;
+pub const SENSOR_MAX_UNIT_LENGTH: usize = 6;
 pub const LABEL_TEXT_SETTING_KEY: &'static str = "settings-label-text";
 pub const LABEL_COLOUR_SETTING_KEY: &'static str = "settings-label-colour";
 pub const HIDE_INDICATOR_SETTING_KEY: &'static str = "settings-hide-indicator";
 pub const HIDE_LABEL_SETTING_KEY: &'static str = "settings-hide-label";
 pub const UPDATE_INTERVAL_SETTING_KEY: &'static str = "settings-update-interval";
 pub const MAX_SAMPLES_SETTING_KEY: &'static str = "settings-max-samples";
+pub const SMOOTHING_SETTING_KEY: &'static str = "settings-smoothing";
+pub const SMOOTHING_WINDOW_SETTING_KEY: &'static str = "settings-smoothing-window";
+pub const DISPLAY_MODE_SETTING_KEY: &'static str = "settings-display-mode";
+pub const FORMAT_TEMPLATE_SETTING_KEY: &'static str = "settings-format-template";
+pub const WARNING_THRESHOLD_SETTING_KEY: &'static str = "settings-warning-threshold";
+pub const CRITICAL_THRESHOLD_SETTING_KEY: &'static str = "settings-critical-threshold";
+pub const THRESHOLD_HYSTERESIS_SETTING_KEY: &'static str = "settings-threshold-hysteresis";
+pub const THRESHOLD_COLOUR_LABEL_SETTING_KEY: &'static str = "settings-threshold-colour-label";
 pub const FIX_INDICATOR_SIZE_SETTING_KEY: &'static str = "settings-fix-indicator-size";
 pub const USE_IEC_UNITS_SETTING_KEY: &'static str = "settings-use-iec-units";
+pub const SENSOR_ROOT_SETTING_KEY: &'static str = "settings-sensor-root";
+pub const FILE_PATH_SETTING_KEY: &'static str = "settings-file-path";
+pub const FILE_PATTERN_SETTING_KEY: &'static str = "settings-file-pattern";
+pub const FILE_FIELD_INDEX_SETTING_KEY: &'static str = "settings-file-field-index";
+pub const FILE_SCALE_SETTING_KEY: &'static str = "settings-file-scale";
+pub const FILE_UNIT_SETTING_KEY: &'static str = "settings-file-unit";
+pub const FILE_RATE_MODE_SETTING_KEY: &'static str = "settings-file-rate-mode";
+pub const POWER_SHOW_ALL_DOMAINS_SETTING_KEY: &'static str = "settings-power-show-all-domains";
+pub const PAGING_SHOW_MAJOR_FAULTS_SETTING_KEY: &'static str = "settings-paging-show-major-faults";
+pub const ARC_SHOW_TARGET_SETTING_KEY: &'static str = "settings-arc-show-target";
+pub const MEMORY_COUNT_ZFS_ARC_SETTING_KEY: &'static str = "settings-memory-count-zfs-arc";
+pub const MEMORY_SHOW_COMPRESSION_SETTING_KEY: &'static str = "settings-memory-show-compression";
+pub const MEMORY_VALUE_MODE_SETTING_KEY: &'static str = "settings-memory-value-mode";
+pub const ALERTS_ENABLED_SETTING_KEY: &'static str = "settings-alerts-enabled";
+pub const ALERT_RULE_SETTING_KEYS: [&'static str; 4] =
+    ["settings-alert-rule-1", "settings-alert-rule-2", "settings-alert-rule-3", "settings-alert-rule-4"];
+pub const ALERT_COOLDOWN_SETTING_KEY: &'static str = "settings-alert-cooldown";
+pub const ALERT_HYSTERESIS_SETTING_KEY: &'static str = "settings-alert-hysteresis";
+pub const ALERT_HOOK_SETTING_KEYS: [&'static str; 4] =
+    ["settings-alert-hook-1", "settings-alert-hook-2", "settings-alert-hook-3", "settings-alert-hook-4"];
+pub const ALERT_HOOK_COOLDOWN_SETTING_KEY: &'static str = "settings-alert-hook-cooldown";
 
 
 #[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
@@ -37,17 +87,26 @@ pub struct AppConfiguration {
     pub cpu: CpuConfiguration,
     pub memory: MemoryConfiguration,
     pub network: NetworkConfiguration,
-    pub disk: DiskConfiguration
+    pub disk: DiskConfiguration,
+    pub file: FileConfiguration,
+    pub power: PowerConfiguration,
+    pub paging: PagingConfiguration,
+    pub arc: ArcConfiguration,
+    pub raid: RaidConfiguration,
+    pub alerts: AlertsConfiguration,
 }
 
 impl AppConfiguration {
-    pub fn settings_form_options(&self) -> BTreeMap<window::Id, SettingsForm> {
-        BTreeMap::from([
+    pub fn settings_form_options(&self, monitor_registry: &MonitorRegistry) -> BTreeMap<window::Id, SettingsForm> {
+        let mut settings_forms = BTreeMap::from([
             (GENERAL_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.general)),
-            (CPU_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.cpu)),
-            (MEMORY_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.memory)),
-            (NETWORK_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.network)),        
-            (DISK_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.disk))
-        ])
+            (ALERTS_SETTINGS_WINDOW_ID.clone(), SettingsForm::from(&self.alerts)),
+        ]);
+
+        for definition in monitor_registry.definitions() {
+            settings_forms.insert(definition.id(), definition.settings_form(self));
+        }
+
+        settings_forms
     }
 }
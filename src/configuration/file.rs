@@ -0,0 +1,296 @@
+use crate::configuration::app_configuration::*;
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::smoothing::{deserialize_smoothing_window, SmoothingStrategy};
+use crate::configuration::thresholds::ThresholdConfiguration;
+use crate::configuration::validation::ConfigurationValidation;
+use crate::core::app_colours::ACCENT_YELLOW;
+use crate::core::settings::SettingsForm;
+use crate::fl;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// The fields of the format template: the scaled value, and its unit, which ends in /s in rate mode
+pub const FILE_TEMPLATE_FIELDS: [TemplateField; 2] = [TemplateField::Number("value"), TemplateField::Text("unit")];
+
+/// How the value read from the file is displayed
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum FileValueMode {
+    /// Show the value as read, i.e. a temperature or voltage
+    Absolute,
+    /// Show the change in value per second, i.e. for an ever increasing counter
+    Rate,
+}
+
+/// The factor each value read from the file is multiplied by. It is always a finite non-zero
+/// number, so unlike a bare f64 it can be compared for equality.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct FileScale(f64);
+
+impl FileScale {
+    pub fn new(value: f64) -> Option<Self> {
+        (value.is_finite() && value != 0.0).then_some(FileScale(value))
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+// The value is never NaN, so every scale is equal to itself.
+impl Eq for FileScale {}
+
+impl Default for FileScale {
+    fn default() -> Self {
+        FileScale(1.0)
+    }
+}
+
+impl fmt::Display for FileScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<f64> for FileScale {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        FileScale::new(value).ok_or_else(|| fl!("settings-file-scale-error"))
+    }
+}
+
+impl From<FileScale> for f64 {
+    fn from(scale: FileScale) -> Self {
+        scale.0
+    }
+}
+
+/// The configuration for the generic sysfs / procfs file monitor
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileConfiguration {
+    /// Whether to hide the file indicator from the panel
+    pub hide_indicator: bool,
+    /// Whether to hide only the label for this indicator
+    pub hide_label: bool,
+    /// The duration between each update interval, i.e. 5 seconds
+    pub update_interval: Duration,
+    /// The number of samples to keep and average for the final result
+    pub max_samples: usize,
+    /// How the samples are combined into the final result
+    pub smoothing: SmoothingStrategy,
+    /// The span of samples to combine in time, used instead of max_samples when set
+    #[serde(deserialize_with = "deserialize_smoothing_window")]
+    pub smoothing_window: Option<Duration>,
+    /// Whether the indicator shows the value, a graph of recent values or both
+    pub display_mode: IndicatorDisplayMode,
+    /// How the value is formatted, or None for the default format
+    pub format_template: Option<FormatTemplate>,
+    /// When the value turns the warning or critical colour, in the unit of the scaled value
+    pub thresholds: ThresholdConfiguration,
+    /// The label colour
+    pub label_colour: Option<String>,
+    /// The indicator label text
+    pub label_text: Option<String>,
+    /// The path of the file to read, i.e. /sys/class/backlight/intel_backlight/brightness
+    pub file_path: String,
+    /// An optional regex to select the value. The first capture group is used if there is one.
+    pub pattern: Option<String>,
+    /// An optional whitespace separated field index to select the value
+    pub field_index: Option<usize>,
+    /// The factor each value is multiplied by, i.e. 0.001 to convert millivolts to volts
+    pub scale: FileScale,
+    /// The unit suffix shown after the value
+    pub unit: String,
+    /// Whether to show the value as read or as a rate per second
+    pub mode: FileValueMode,
+}
+
+impl Default for FileConfiguration {
+    fn default() -> Self {
+        Self {
+            hide_indicator: true,
+            hide_label: false,
+            update_interval: Duration::from_secs(1),
+            max_samples: 2,
+            smoothing: SmoothingStrategy::default(),
+            smoothing_window: None,
+            display_mode: IndicatorDisplayMode::default(),
+            format_template: None,
+            thresholds: ThresholdConfiguration::default(),
+            label_colour: Some(ACCENT_YELLOW.to_string()),
+            label_text: Some("FILE".to_string()),
+            file_path: String::new(),
+            pattern: None,
+            field_index: None,
+            scale: FileScale::default(),
+            unit: String::new(),
+            mode: FileValueMode::Absolute,
+        }
+    }
+}
+
+impl FileConfiguration {
+    pub fn update(&self, settings_form: &SettingsForm) -> Self {
+        if settings_form.settings_window_id != FILE_SETTINGS_WINDOW_ID.clone() {
+            panic!("Attempted to update file settings from a non-file settings window.")
+        }
+
+        let rate_mode = ConfigurationValidation::sanitise_boolean_input(
+            settings_form
+                .values
+                .get(FILE_RATE_MODE_SETTING_KEY)
+                .expect("Rate mode missing from settings form options")
+                .value
+                .clone(),
+            self.mode == FileValueMode::Rate,
+        );
+
+        FileConfiguration {
+            hide_indicator: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_INDICATOR_SETTING_KEY)
+                    .expect("Hide indicator missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_indicator,
+            ),
+            hide_label: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_LABEL_SETTING_KEY)
+                    .expect("Hide label missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_label,
+            ),
+            update_interval: ConfigurationValidation::sanitise_interval_input(
+                settings_form
+                    .values
+                    .get(UPDATE_INTERVAL_SETTING_KEY)
+                    .expect("Update interval missing from settings form options")
+                    .value
+                    .clone(),
+                self.update_interval,
+            ),
+            max_samples: ConfigurationValidation::sanitise_max_samples(
+                settings_form
+                    .values
+                    .get(MAX_SAMPLES_SETTING_KEY)
+                    .expect("Max samples missing from settings form options")
+                    .value
+                    .clone(),
+                self.max_samples,
+            ),
+            smoothing: ConfigurationValidation::sanitise_smoothing_strategy(
+                settings_form
+                    .values
+                    .get(SMOOTHING_SETTING_KEY)
+                    .expect("Smoothing missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing,
+            ),
+            smoothing_window: ConfigurationValidation::sanitise_smoothing_window(
+                settings_form
+                    .values
+                    .get(SMOOTHING_WINDOW_SETTING_KEY)
+                    .expect("Smoothing window missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing_window,
+            ),
+            display_mode: ConfigurationValidation::sanitise_display_mode(
+                settings_form
+                    .values
+                    .get(DISPLAY_MODE_SETTING_KEY)
+                    .expect("Display mode missing from settings form options")
+                    .value
+                    .clone(),
+                self.display_mode,
+            ),
+            format_template: ConfigurationValidation::sanitise_format_template(
+                settings_form
+                    .values
+                    .get(FORMAT_TEMPLATE_SETTING_KEY)
+                    .expect("Format template missing from settings form options")
+                    .value
+                    .clone(),
+                self.format_template.clone(),
+                &FILE_TEMPLATE_FIELDS,
+            ),
+            thresholds: self.thresholds.update(settings_form),
+            label_colour: Some(
+                settings_form
+                    .values
+                    .get(LABEL_COLOUR_SETTING_KEY)
+                    .expect("Label colour missing from settings form options")
+                    .value
+                    .clone(),
+            ),
+            label_text: Some(ConfigurationValidation::sanitise_label_text(
+                settings_form
+                    .values
+                    .get(LABEL_TEXT_SETTING_KEY)
+                    .expect("Label text missing from settings form options")
+                    .value
+                    .clone(),
+                self.label_text.clone().unwrap_or_default(),
+            )),
+            file_path: ConfigurationValidation::sanitise_file_path(
+                settings_form
+                    .values
+                    .get(FILE_PATH_SETTING_KEY)
+                    .expect("File path missing from settings form options")
+                    .value
+                    .clone(),
+                self.file_path.clone(),
+            ),
+            pattern: ConfigurationValidation::sanitise_file_pattern(
+                settings_form
+                    .values
+                    .get(FILE_PATTERN_SETTING_KEY)
+                    .expect("Pattern missing from settings form options")
+                    .value
+                    .clone(),
+                self.pattern.clone(),
+            ),
+            field_index: ConfigurationValidation::sanitise_field_index(
+                settings_form
+                    .values
+                    .get(FILE_FIELD_INDEX_SETTING_KEY)
+                    .expect("Field index missing from settings form options")
+                    .value
+                    .clone(),
+                self.field_index,
+            ),
+            scale: ConfigurationValidation::sanitise_scale(
+                settings_form
+                    .values
+                    .get(FILE_SCALE_SETTING_KEY)
+                    .expect("Scale missing from settings form options")
+                    .value
+                    .clone(),
+                self.scale,
+            ),
+            unit: ConfigurationValidation::sanitise_unit(
+                settings_form
+                    .values
+                    .get(FILE_UNIT_SETTING_KEY)
+                    .expect("Unit missing from settings form options")
+                    .value
+                    .clone(),
+                self.unit.clone(),
+            ),
+            mode: if rate_mode {
+                FileValueMode::Rate
+            } else {
+                FileValueMode::Absolute
+            },
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use crate::fl;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The most decimals a template can ask a number for
+const MAX_PRECISION: usize = 9;
+
+/// A field a monitor fills in for its format template
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TemplateField {
+    /// A number, which can be given a precision, i.e. "{used:.1}"
+    Number(&'static str),
+    /// Text, i.e. a unit
+    Text(&'static str),
+}
+
+impl TemplateField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateField::Number(name) | TemplateField::Text(name) => name,
+        }
+    }
+
+    /// The field names as they are listed in the settings, i.e. "{used}, {total}"
+    pub fn list(fields: &[TemplateField]) -> String {
+        fields
+            .iter()
+            .map(|field| format!("{{{}}}", field.name()))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// The value of a field when a template is rendered
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateValue {
+    Number {
+        value: f64,
+        /// The widest value the field shows, which fixed size indicators are measured with
+        max: f64,
+        /// The decimals shown when the template doesn't give a precision
+        precision: usize,
+        /// The unit written straight after the number, if the field carries its own
+        unit: &'static str,
+    },
+    Text(String),
+}
+
+impl TemplateValue {
+    fn render(&self, precision: Option<usize>, widest: bool) -> String {
+        match self {
+            TemplateValue::Number {
+                value,
+                max,
+                precision: default_precision,
+                unit,
+            } => {
+                let value = if widest { max } else { value };
+                format!("{:.*}{}", precision.unwrap_or(*default_precision), value, unit)
+            }
+            TemplateValue::Text(text) => text.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    Field { name: String, precision: Option<usize> },
+}
+
+/// How an indicator formats its value, i.e. "{used:.1}/{total:.0} {unit}" or "{rx}↓ {tx}↑". Each
+/// field in braces is filled in by the monitor, and "{{" or "}}" show a brace. It is stored in the
+/// configuration as that text.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FormatTemplate {
+    source: String,
+    parts: Vec<TemplatePart>,
+}
+
+impl FormatTemplate {
+    /// Parses a template that may only use the given fields of a monitor
+    pub fn parse_with_fields(input: &str, fields: &[TemplateField]) -> Result<Self, String> {
+        let template: FormatTemplate = input.parse()?;
+
+        for part in &template.parts {
+            let TemplatePart::Field { name, precision } = part else {
+                continue;
+            };
+
+            match fields.iter().find(|field| field.name() == name) {
+                None => {
+                    return Err(fl!(
+                        "settings-format-template-unknown-field",
+                        field = name.clone(),
+                        fields = TemplateField::list(fields)
+                    ))
+                }
+                Some(TemplateField::Text(_)) if precision.is_some() => {
+                    return Err(fl!("settings-format-template-text-precision", field = name.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(template)
+    }
+
+    /// Fills in the fields with their values. A field without a value is left as it was written.
+    pub fn render(&self, values: &[(&str, TemplateValue)]) -> String {
+        self.render_values(values, false)
+    }
+
+    /// Fills in the fields with the widest values they show, so a fixed size indicator is wide
+    /// enough for any value
+    pub fn render_widest(&self, values: &[(&str, TemplateValue)]) -> String {
+        self.render_values(values, true)
+    }
+
+    fn render_values(&self, values: &[(&str, TemplateValue)], widest: bool) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Field { name, precision } => match values.iter().find(|(field, _)| field == name) {
+                    Some((_, value)) => value.render(*precision, widest),
+                    None => format_field(name, *precision),
+                },
+            })
+            .collect()
+    }
+}
+
+impl FromStr for FormatTemplate {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = || fl!("settings-format-template-error");
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = input.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut spec = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => return Err(error()),
+                            Some(c) => spec.push(c),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(parse_field(&spec).ok_or_else(error)?);
+                }
+                '}' => return Err(error()),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        Ok(FormatTemplate {
+            source: input.to_string(),
+            parts,
+        })
+    }
+}
+
+/// A field written as "name" or "name:.precision"
+fn parse_field(spec: &str) -> Option<TemplatePart> {
+    let (name, precision) = match spec.split_once(':') {
+        Some((name, precision)) => (name, Some(precision)),
+        None => (spec, None),
+    };
+    let name = name.trim();
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+        return None;
+    }
+
+    let precision = match precision {
+        Some(precision) => {
+            let precision = precision.trim().strip_prefix('.')?.parse::<usize>().ok()?;
+
+            if precision > MAX_PRECISION {
+                return None;
+            }
+
+            Some(precision)
+        }
+        None => None,
+    };
+
+    Some(TemplatePart::Field {
+        name: name.to_string(),
+        precision,
+    })
+}
+
+fn format_field(name: &str, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{{{}:.{}}}", name, precision),
+        None => format!("{{{}}}", name),
+    }
+}
+
+impl fmt::Display for FormatTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl TryFrom<String> for FormatTemplate {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<FormatTemplate> for String {
+    fn from(template: FormatTemplate) -> Self {
+        template.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIELDS: [TemplateField; 3] = [
+        TemplateField::Number("used"),
+        TemplateField::Number("total"),
+        TemplateField::Text("unit"),
+    ];
+
+    fn memory_values() -> Vec<(&'static str, TemplateValue)> {
+        let number = |value: f64| TemplateValue::Number {
+            value,
+            max: 15.6,
+            precision: 1,
+            unit: "",
+        };
+
+        vec![
+            ("used", number(5.25)),
+            ("total", number(15.6)),
+            ("unit", TemplateValue::Text("GiB".to_string())),
+        ]
+    }
+
+    fn render(input: &str) -> String {
+        FormatTemplate::parse_with_fields(input, &FIELDS)
+            .unwrap()
+            .render(&memory_values())
+    }
+
+    #[test]
+    fn fields_are_filled_in_with_their_precision() {
+        assert_eq!(render("{used:.2}/{total:.0} {unit}"), "5.25/16 GiB");
+        assert_eq!(render("{used} {unit}"), "5.2 GiB");
+    }
+
+    #[test]
+    fn doubled_braces_are_shown_as_braces() {
+        assert_eq!(render("{{{used}}}"), "{5.2}");
+    }
+
+    #[test]
+    fn numbers_carry_their_own_unit() {
+        let template: FormatTemplate = "{rx}↓ {tx:.2}↑".parse().unwrap();
+        let rate = |value: f64| TemplateValue::Number {
+            value,
+            max: 999.9,
+            precision: 1,
+            unit: "MiB/s",
+        };
+
+        assert_eq!(
+            template.render(&[("rx", rate(1.5)), ("tx", rate(0.25))]),
+            "1.5MiB/s↓ 0.25MiB/s↑"
+        );
+    }
+
+    #[test]
+    fn widest_rendering_uses_the_max_values() {
+        let template = FormatTemplate::parse_with_fields("{used}/{total:.0} {unit}", &FIELDS).unwrap();
+
+        assert_eq!(template.render_widest(&memory_values()), "15.6/16 GiB");
+    }
+
+    #[test]
+    fn invalid_syntax_is_rejected() {
+        for input in [
+            "{used",
+            "used}",
+            "{}",
+            "{Used}",
+            "{used:1}",
+            "{used:.x}",
+            "{used:.10}",
+            "{{used}",
+        ] {
+            assert!(input.parse::<FormatTemplate>().is_err(), "{} should be rejected", input);
+        }
+    }
+
+    #[test]
+    fn only_the_monitor_fields_are_accepted() {
+        assert!(FormatTemplate::parse_with_fields("{free}", &FIELDS).is_err());
+        assert!(FormatTemplate::parse_with_fields("{unit:.1}", &FIELDS).is_err());
+        assert!(FormatTemplate::parse_with_fields("{used:.1} of {total}", &FIELDS).is_ok());
+    }
+
+    #[test]
+    fn display_is_the_template_as_written() {
+        let template: FormatTemplate = "{used:.1}/{total:.0} {unit}".parse().unwrap();
+
+        assert_eq!(template.to_string(), "{used:.1}/{total:.0} {unit}");
+    }
+}
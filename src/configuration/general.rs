@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::configuration::app_configuration::{GENERAL_SETTINGS_WINDOW_ID, FIX_INDICATOR_SIZE_SETTING_KEY, USE_IEC_UNITS_SETTING_KEY};
+use crate::configuration::app_configuration::{GENERAL_SETTINGS_WINDOW_ID, FIX_INDICATOR_SIZE_SETTING_KEY, USE_IEC_UNITS_SETTING_KEY, SENSOR_ROOT_SETTING_KEY};
 use crate::configuration::validation::ConfigurationValidation;
 use crate::core::settings::SettingsForm;
+use crate::sensors::sensor_root::DEFAULT_SENSOR_ROOT;
 
 const DEFAULT_INDICATOR_FONT_SIZE: u16 = 14;
 
@@ -25,6 +26,8 @@ pub struct GeneralConfiguration {
     pub fix_indicator_size: bool,
     /// When true, use IEC units (MiB, GiB); when false, use SI (MB, GB).
     pub use_iec_units: bool,
+    /// The directory /proc and /sys are read from, i.e. /run/host when running in a Flatpak
+    pub sensor_root: String,
 }
 
 impl Default for GeneralConfiguration {
@@ -42,6 +45,7 @@ impl Default for GeneralConfiguration {
             horizontal_font_size_xl: DEFAULT_INDICATOR_FONT_SIZE + 8,
             fix_indicator_size: true,
             use_iec_units: false,
+            sensor_root: DEFAULT_SENSOR_ROOT.to_string(),
         }
     }
 }
@@ -71,6 +75,15 @@ impl GeneralConfiguration {
                     .clone(),
                 self.use_iec_units,
             ),
+            sensor_root: ConfigurationValidation::sanitise_sensor_root(
+                settings_form
+                    .values
+                    .get(SENSOR_ROOT_SETTING_KEY)
+                    .expect("Sensor root missing from settings form options")
+                    .value
+                    .clone(),
+                self.sensor_root.clone(),
+            ),
             ..self.clone()
         }
     }
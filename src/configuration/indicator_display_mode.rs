@@ -0,0 +1,51 @@
+use crate::fl;
+use serde::{Deserialize, Serialize};
+
+/// What an indicator shows on the panel
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum IndicatorDisplayMode {
+    /// The current value as text
+    #[default]
+    Number,
+    /// A sparkline of the recent values
+    Graph,
+    /// The current value followed by a sparkline
+    Both,
+}
+
+impl IndicatorDisplayMode {
+    pub const ALL: [IndicatorDisplayMode; 3] = [
+        IndicatorDisplayMode::Number,
+        IndicatorDisplayMode::Graph,
+        IndicatorDisplayMode::Both,
+    ];
+
+    /// The value stored in the settings form
+    pub fn key(&self) -> &'static str {
+        match self {
+            IndicatorDisplayMode::Number => "number",
+            IndicatorDisplayMode::Graph => "graph",
+            IndicatorDisplayMode::Both => "both",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.key() == key.trim())
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            IndicatorDisplayMode::Number => fl!("settings-display-mode-number"),
+            IndicatorDisplayMode::Graph => fl!("settings-display-mode-graph"),
+            IndicatorDisplayMode::Both => fl!("settings-display-mode-both"),
+        }
+    }
+
+    pub fn shows_number(&self) -> bool {
+        *self != IndicatorDisplayMode::Graph
+    }
+
+    pub fn shows_graph(&self) -> bool {
+        *self != IndicatorDisplayMode::Number
+    }
+}
@@ -1,12 +1,74 @@
 use crate::configuration::app_configuration::*;
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::smoothing::{deserialize_smoothing_window, SmoothingStrategy};
+use crate::configuration::thresholds::ThresholdConfiguration;
 use crate::configuration::validation::ConfigurationValidation;
 use crate::core::app_colours::EXT_PURPLE;
+use crate::fl;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use crate::core::settings::SettingsForm;
 
+/// Which reading of the memory the indicator shows
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MemoryValueMode {
+    /// The memory used, i.e. 5.2GiB
+    #[default]
+    Used,
+    /// The memory used in percent of the total, i.e. 33.4%
+    PercentUsed,
+    /// The memory used and the total, i.e. 5.2GiB/15.5GiB
+    UsedOfTotal,
+    /// The memory still available, i.e. 10.3GiB
+    Available,
+}
+
+impl MemoryValueMode {
+    pub const ALL: [MemoryValueMode; 4] = [
+        MemoryValueMode::Used,
+        MemoryValueMode::PercentUsed,
+        MemoryValueMode::UsedOfTotal,
+        MemoryValueMode::Available,
+    ];
+
+    /// The value stored in the settings form
+    pub fn key(&self) -> &'static str {
+        match self {
+            MemoryValueMode::Used => "used",
+            MemoryValueMode::PercentUsed => "percent-used",
+            MemoryValueMode::UsedOfTotal => "used-of-total",
+            MemoryValueMode::Available => "available",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.key() == key.trim())
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            MemoryValueMode::Used => fl!("settings-memory-value-mode-used"),
+            MemoryValueMode::PercentUsed => fl!("settings-memory-value-mode-percent-used"),
+            MemoryValueMode::UsedOfTotal => fl!("settings-memory-value-mode-used-of-total"),
+            MemoryValueMode::Available => fl!("settings-memory-value-mode-available"),
+        }
+    }
+}
+
+/// The fields of the format template: the memory used, total and available in GiB or GB, which is
+/// the unit, and the percent used
+pub const MEMORY_TEMPLATE_FIELDS: [TemplateField; 5] = [
+    TemplateField::Number("used"),
+    TemplateField::Number("total"),
+    TemplateField::Number("available"),
+    TemplateField::Number("percent"),
+    TemplateField::Text("unit"),
+];
+
 /// The configuration for the memory monitor
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MemoryConfiguration {
     /// Whether to hide the CPU indicator from the panel
     pub hide_indicator: bool,
@@ -16,10 +78,27 @@ pub struct MemoryConfiguration {
     pub update_interval: Duration,
     /// The number of samples to keep and average for the final result
     pub max_samples: usize,
+    /// How the samples are combined into the final result
+    pub smoothing: SmoothingStrategy,
+    /// The span of samples to combine in time, used instead of max_samples when set
+    #[serde(deserialize_with = "deserialize_smoothing_window")]
+    pub smoothing_window: Option<Duration>,
+    /// Whether the indicator shows the value, a graph of recent values or both
+    pub display_mode: IndicatorDisplayMode,
+    /// Which reading of the memory the value shows
+    pub value_mode: MemoryValueMode,
+    /// How the value is formatted, or None to format it by the value mode
+    pub format_template: Option<FormatTemplate>,
+    /// When the value turns the warning or critical colour, in percent of the memory used
+    pub thresholds: ThresholdConfiguration,
     /// The indicator icon colour key
     pub label_colour: Option<String>,
     /// The indicator label text
     pub label_text: Option<String>,
+    /// Whether to treat the shrinkable part of the ZFS ARC as available memory rather than used
+    pub count_zfs_arc_as_reclaimable: bool,
+    /// Whether to show the zram and zswap sizes and compression ratios
+    pub show_compression: bool,
 }
 
 impl Default for MemoryConfiguration {
@@ -29,8 +108,16 @@ impl Default for MemoryConfiguration {
             hide_label: false,
             update_interval: Duration::from_secs(1),
             max_samples: 2,
+            smoothing: SmoothingStrategy::default(),
+            smoothing_window: None,
+            display_mode: IndicatorDisplayMode::default(),
+            value_mode: MemoryValueMode::default(),
+            format_template: None,
+            thresholds: ThresholdConfiguration::new(70, 90, 5),
             label_colour: Some(EXT_PURPLE.to_string()),
             label_text: Some("MEM".to_string()),
+            count_zfs_arc_as_reclaimable: false,
+            show_compression: false,
         }
     }
 }
@@ -78,6 +165,53 @@ impl MemoryConfiguration {
                     .clone(),
                 self.max_samples,
             ),
+            smoothing: ConfigurationValidation::sanitise_smoothing_strategy(
+                settings_form
+                    .values
+                    .get(SMOOTHING_SETTING_KEY)
+                    .expect("Smoothing missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing,
+            ),
+            smoothing_window: ConfigurationValidation::sanitise_smoothing_window(
+                settings_form
+                    .values
+                    .get(SMOOTHING_WINDOW_SETTING_KEY)
+                    .expect("Smoothing window missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing_window,
+            ),
+            display_mode: ConfigurationValidation::sanitise_display_mode(
+                settings_form
+                    .values
+                    .get(DISPLAY_MODE_SETTING_KEY)
+                    .expect("Display mode missing from settings form options")
+                    .value
+                    .clone(),
+                self.display_mode,
+            ),
+            value_mode: ConfigurationValidation::sanitise_memory_value_mode(
+                settings_form
+                    .values
+                    .get(MEMORY_VALUE_MODE_SETTING_KEY)
+                    .expect("Memory value mode missing from settings form options")
+                    .value
+                    .clone(),
+                self.value_mode,
+            ),
+            format_template: ConfigurationValidation::sanitise_format_template(
+                settings_form
+                    .values
+                    .get(FORMAT_TEMPLATE_SETTING_KEY)
+                    .expect("Format template missing from settings form options")
+                    .value
+                    .clone(),
+                self.format_template.clone(),
+                &MEMORY_TEMPLATE_FIELDS,
+            ),
+            thresholds: self.thresholds.update(settings_form),
             label_colour: Some(
                 settings_form
                     .values
@@ -95,6 +229,24 @@ impl MemoryConfiguration {
                     .clone(),
                 self.label_text.clone().unwrap_or_default(),
             )),
+            count_zfs_arc_as_reclaimable: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(MEMORY_COUNT_ZFS_ARC_SETTING_KEY)
+                    .expect("Count ZFS ARC missing from settings form options")
+                    .value
+                    .clone(),
+                self.count_zfs_arc_as_reclaimable,
+            ),
+            show_compression: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(MEMORY_SHOW_COMPRESSION_SETTING_KEY)
+                    .expect("Show compression missing from settings form options")
+                    .value
+                    .clone(),
+                self.show_compression,
+            ),
         }
     }
 }
@@ -1,7 +1,18 @@
 pub mod validation;
+pub mod smoothing;
+pub mod indicator_display_mode;
+pub mod thresholds;
+pub mod alert_rule;
+pub mod alerts;
+pub mod format_template;
 pub mod app_configuration;
 pub mod network;
 pub mod cpu;
 pub mod memory;
 pub mod disk;
-pub mod general;
\ No newline at end of file
+pub mod general;
+pub mod file;
+pub mod power;
+pub mod paging;
+pub mod arc;
+pub mod raid;
\ No newline at end of file
@@ -1,12 +1,20 @@
 use crate::configuration::app_configuration::*;
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::smoothing::{deserialize_smoothing_window, SmoothingStrategy};
+use crate::configuration::thresholds::ThresholdConfiguration;
 use crate::configuration::validation::ConfigurationValidation;
 use crate::core::app_colours::ACCENT_GREEN;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use crate::core::settings::SettingsForm;
 
+/// The fields of the format template: the received and transmitted rates, each with its own unit
+pub const NETWORK_TEMPLATE_FIELDS: [TemplateField; 2] = [TemplateField::Number("rx"), TemplateField::Number("tx")];
+
 /// The configuration for the network monitor
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NetworkConfiguration {
     /// Whether to hide the CPU indicator from the panel
     pub hide_indicator: bool,
@@ -16,6 +24,17 @@ pub struct NetworkConfiguration {
     pub update_interval: Duration,
     /// The number of samples to keep and average for the final result
     pub max_samples: usize,
+    /// How the samples are combined into the final result
+    pub smoothing: SmoothingStrategy,
+    /// The span of samples to combine in time, used instead of max_samples when set
+    #[serde(deserialize_with = "deserialize_smoothing_window")]
+    pub smoothing_window: Option<Duration>,
+    /// Whether the indicator shows the value, a graph of recent values or both
+    pub display_mode: IndicatorDisplayMode,
+    /// How the value is formatted, or None for the default format
+    pub format_template: Option<FormatTemplate>,
+    /// When the value turns the warning or critical colour, in KiB/s of the busier direction
+    pub thresholds: ThresholdConfiguration,
     /// The label colour
     pub label_colour: Option<String>,
     /// The indicator label text
@@ -29,6 +48,11 @@ impl Default for NetworkConfiguration {
             hide_label: false,
             update_interval: Duration::from_secs(1),
             max_samples: 4,
+            smoothing: SmoothingStrategy::default(),
+            smoothing_window: None,
+            display_mode: IndicatorDisplayMode::default(),
+            format_template: None,
+            thresholds: ThresholdConfiguration::default(),
             label_colour: Some(ACCENT_GREEN.to_string()),
             label_text: Some("NET".to_string()),
         }
@@ -78,6 +102,44 @@ impl NetworkConfiguration {
                     .clone(),
                 self.max_samples,
             ),
+            smoothing: ConfigurationValidation::sanitise_smoothing_strategy(
+                settings_form
+                    .values
+                    .get(SMOOTHING_SETTING_KEY)
+                    .expect("Smoothing missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing,
+            ),
+            smoothing_window: ConfigurationValidation::sanitise_smoothing_window(
+                settings_form
+                    .values
+                    .get(SMOOTHING_WINDOW_SETTING_KEY)
+                    .expect("Smoothing window missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing_window,
+            ),
+            display_mode: ConfigurationValidation::sanitise_display_mode(
+                settings_form
+                    .values
+                    .get(DISPLAY_MODE_SETTING_KEY)
+                    .expect("Display mode missing from settings form options")
+                    .value
+                    .clone(),
+                self.display_mode,
+            ),
+            format_template: ConfigurationValidation::sanitise_format_template(
+                settings_form
+                    .values
+                    .get(FORMAT_TEMPLATE_SETTING_KEY)
+                    .expect("Format template missing from settings form options")
+                    .value
+                    .clone(),
+                self.format_template.clone(),
+                &NETWORK_TEMPLATE_FIELDS,
+            ),
+            thresholds: self.thresholds.update(settings_form),
             label_colour: Some(
                 settings_form
                     .values
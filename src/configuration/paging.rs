@@ -0,0 +1,181 @@
+use crate::configuration::app_configuration::*;
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::smoothing::{deserialize_smoothing_window, SmoothingStrategy};
+use crate::configuration::thresholds::ThresholdConfiguration;
+use crate::configuration::validation::ConfigurationValidation;
+use crate::core::app_colours::ACCENT_PINK;
+use crate::core::settings::SettingsForm;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The fields of the format template: the swap in and out rates, each with its own unit, the major
+/// page faults per second and the OOM kills since the applet started
+pub const PAGING_TEMPLATE_FIELDS: [TemplateField; 4] = [
+    TemplateField::Number("in"),
+    TemplateField::Number("out"),
+    TemplateField::Number("faults"),
+    TemplateField::Number("oom"),
+];
+
+/// The configuration for the swap and paging activity monitor
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PagingConfiguration {
+    /// Whether to hide the paging indicator from the panel
+    pub hide_indicator: bool,
+    /// Whether to hide only the label for this indicator
+    pub hide_label: bool,
+    /// The duration between each update interval, i.e. 5 seconds
+    pub update_interval: Duration,
+    /// The number of samples to keep and average for the final result
+    pub max_samples: usize,
+    /// How the samples are combined into the final result
+    pub smoothing: SmoothingStrategy,
+    /// The span of samples to combine in time, used instead of max_samples when set
+    #[serde(deserialize_with = "deserialize_smoothing_window")]
+    pub smoothing_window: Option<Duration>,
+    /// Whether the indicator shows the value, a graph of recent values or both
+    pub display_mode: IndicatorDisplayMode,
+    /// How the value is formatted, or None for the default format
+    pub format_template: Option<FormatTemplate>,
+    /// When the value turns the warning or critical colour, in KiB/s of the busier swap direction
+    pub thresholds: ThresholdConfiguration,
+    /// The label colour
+    pub label_colour: Option<String>,
+    /// The indicator label text
+    pub label_text: Option<String>,
+    /// Whether to show the major page fault rate as well as the swap rates
+    pub show_major_faults: bool,
+}
+
+impl Default for PagingConfiguration {
+    fn default() -> Self {
+        Self {
+            // Most of the time there is no swap activity to show, so this is opt-in.
+            hide_indicator: true,
+            hide_label: false,
+            update_interval: Duration::from_secs(1),
+            max_samples: 3,
+            smoothing: SmoothingStrategy::default(),
+            smoothing_window: None,
+            display_mode: IndicatorDisplayMode::default(),
+            format_template: None,
+            thresholds: ThresholdConfiguration::default(),
+            label_colour: Some(ACCENT_PINK.to_string()),
+            label_text: Some("SWAP".to_string()),
+            show_major_faults: false,
+        }
+    }
+}
+
+impl PagingConfiguration {
+    pub fn update(&self, settings_form: &SettingsForm) -> Self {
+        if settings_form.settings_window_id != PAGING_SETTINGS_WINDOW_ID.clone() {
+            panic!("Attempted to update paging settings from a non-paging settings window.")
+        }
+
+        PagingConfiguration {
+            hide_indicator: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_INDICATOR_SETTING_KEY)
+                    .expect("Hide indicator missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_indicator,
+            ),
+            hide_label: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_LABEL_SETTING_KEY)
+                    .expect("Hide label missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_label,
+            ),
+            update_interval: ConfigurationValidation::sanitise_interval_input(
+                settings_form
+                    .values
+                    .get(UPDATE_INTERVAL_SETTING_KEY)
+                    .expect("Update interval missing from settings form options")
+                    .value
+                    .clone(),
+                self.update_interval,
+            ),
+            max_samples: ConfigurationValidation::sanitise_max_samples(
+                settings_form
+                    .values
+                    .get(MAX_SAMPLES_SETTING_KEY)
+                    .expect("Max samples missing from settings form options")
+                    .value
+                    .clone(),
+                self.max_samples,
+            ),
+            smoothing: ConfigurationValidation::sanitise_smoothing_strategy(
+                settings_form
+                    .values
+                    .get(SMOOTHING_SETTING_KEY)
+                    .expect("Smoothing missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing,
+            ),
+            smoothing_window: ConfigurationValidation::sanitise_smoothing_window(
+                settings_form
+                    .values
+                    .get(SMOOTHING_WINDOW_SETTING_KEY)
+                    .expect("Smoothing window missing from settings form options")
+                    .value
+                    .clone(),
+                self.smoothing_window,
+            ),
+            display_mode: ConfigurationValidation::sanitise_display_mode(
+                settings_form
+                    .values
+                    .get(DISPLAY_MODE_SETTING_KEY)
+                    .expect("Display mode missing from settings form options")
+                    .value
+                    .clone(),
+                self.display_mode,
+            ),
+            format_template: ConfigurationValidation::sanitise_format_template(
+                settings_form
+                    .values
+                    .get(FORMAT_TEMPLATE_SETTING_KEY)
+                    .expect("Format template missing from settings form options")
+                    .value
+                    .clone(),
+                self.format_template.clone(),
+                &PAGING_TEMPLATE_FIELDS,
+            ),
+            thresholds: self.thresholds.update(settings_form),
+            label_colour: Some(
+                settings_form
+                    .values
+                    .get(LABEL_COLOUR_SETTING_KEY)
+                    .expect("Label colour missing from settings form options")
+                    .value
+                    .clone(),
+            ),
+            label_text: Some(ConfigurationValidation::sanitise_label_text(
+                settings_form
+                    .values
+                    .get(LABEL_TEXT_SETTING_KEY)
+                    .expect("Label text missing from settings form options")
+                    .value
+                    .clone(),
+                self.label_text.clone().unwrap_or_default(),
+            )),
+            show_major_faults: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(PAGING_SHOW_MAJOR_FAULTS_SETTING_KEY)
+                    .expect("Show major faults missing from settings form options")
+                    .value
+                    .clone(),
+                self.show_major_faults,
+            ),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::configuration::app_configuration::*;
+use crate::configuration::validation::ConfigurationValidation;
+use crate::core::app_colours::EXT_ORANGE;
+use crate::core::settings::SettingsForm;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The configuration for the md RAID monitor
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RaidConfiguration {
+    /// Whether to hide the RAID indicator from the panel. It is only shown when an array needs attention.
+    pub hide_indicator: bool,
+    /// Whether to hide only the label for this indicator
+    pub hide_label: bool,
+    /// The duration between each update interval, i.e. 5 seconds
+    pub update_interval: Duration,
+    /// The label colour while an array is rebuilding. Degraded arrays are always shown in red.
+    pub label_colour: Option<String>,
+    /// The indicator label text
+    pub label_text: Option<String>,
+}
+
+impl Default for RaidConfiguration {
+    fn default() -> Self {
+        Self {
+            hide_indicator: false,
+            hide_label: false,
+            update_interval: Duration::from_secs(5),
+            label_colour: Some(EXT_ORANGE.to_string()),
+            label_text: Some("RAID".to_string()),
+        }
+    }
+}
+
+impl RaidConfiguration {
+    pub fn update(&self, settings_form: &SettingsForm) -> Self {
+        if settings_form.settings_window_id != RAID_SETTINGS_WINDOW_ID.clone() {
+            panic!("Attempted to update RAID settings from a non-RAID settings window.")
+        }
+
+        RaidConfiguration {
+            hide_indicator: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_INDICATOR_SETTING_KEY)
+                    .expect("Hide indicator missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_indicator,
+            ),
+            hide_label: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(HIDE_LABEL_SETTING_KEY)
+                    .expect("Hide label missing from settings form options")
+                    .value
+                    .clone(),
+                self.hide_label,
+            ),
+            update_interval: ConfigurationValidation::sanitise_interval_input(
+                settings_form
+                    .values
+                    .get(UPDATE_INTERVAL_SETTING_KEY)
+                    .expect("Update interval missing from settings form options")
+                    .value
+                    .clone(),
+                self.update_interval,
+            ),
+            label_colour: Some(
+                settings_form
+                    .values
+                    .get(LABEL_COLOUR_SETTING_KEY)
+                    .expect("Label colour missing from settings form options")
+                    .value
+                    .clone(),
+            ),
+            label_text: Some(ConfigurationValidation::sanitise_label_text(
+                settings_form
+                    .values
+                    .get(LABEL_TEXT_SETTING_KEY)
+                    .expect("Label text missing from settings form options")
+                    .value
+                    .clone(),
+                self.label_text.clone().unwrap_or_default(),
+            )),
+        }
+    }
+}
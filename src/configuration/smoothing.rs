@@ -0,0 +1,57 @@
+use crate::fl;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::time::Duration;
+
+/// How a monitor combines its recent samples into the value shown on the panel
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SmoothingStrategy {
+    /// The mean of the samples in the window
+    #[default]
+    MovingAverage,
+    /// A running average that weights recent samples more, so it follows spikes sooner
+    ExponentialMovingAverage,
+    /// The middle sample in the window, which ignores one-off outliers
+    Median,
+    /// Jumps straight to a new peak, then decays back towards the current value
+    PeakHold,
+}
+
+impl SmoothingStrategy {
+    pub const ALL: [SmoothingStrategy; 4] = [
+        SmoothingStrategy::MovingAverage,
+        SmoothingStrategy::ExponentialMovingAverage,
+        SmoothingStrategy::Median,
+        SmoothingStrategy::PeakHold,
+    ];
+
+    /// The value stored in the settings form
+    pub fn key(&self) -> &'static str {
+        match self {
+            SmoothingStrategy::MovingAverage => "moving-average",
+            SmoothingStrategy::ExponentialMovingAverage => "exponential-moving-average",
+            SmoothingStrategy::Median => "median",
+            SmoothingStrategy::PeakHold => "peak-hold",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|strategy| strategy.key() == key.trim())
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            SmoothingStrategy::MovingAverage => fl!("settings-smoothing-moving-average"),
+            SmoothingStrategy::ExponentialMovingAverage => fl!("settings-smoothing-exponential-moving-average"),
+            SmoothingStrategy::Median => fl!("settings-smoothing-median"),
+            SmoothingStrategy::PeakHold => fl!("settings-smoothing-peak-hold"),
+        }
+    }
+}
+
+/// Reads a monitor's smoothing window from the configuration. A zero window would drop every
+/// sample, so it falls back to the max samples like an unset window does.
+pub fn deserialize_smoothing_window<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    let window = Option::<Duration>::deserialize(deserializer)?;
+
+    Ok(window.filter(|window| !window.is_zero()))
+}
@@ -0,0 +1,82 @@
+use crate::configuration::app_configuration::{
+    CRITICAL_THRESHOLD_SETTING_KEY, THRESHOLD_COLOUR_LABEL_SETTING_KEY, THRESHOLD_HYSTERESIS_SETTING_KEY,
+    WARNING_THRESHOLD_SETTING_KEY,
+};
+use crate::configuration::validation::ConfigurationValidation;
+use crate::core::settings::SettingsForm;
+use serde::{Deserialize, Serialize};
+
+/// When an indicator changes colour to draw attention to its value. The thresholds are in the
+/// monitor's own unit, i.e. percent for the CPU usage.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThresholdConfiguration {
+    /// The value at which the indicator turns the warning colour, or None to never warn
+    pub warning: Option<u64>,
+    /// The value at which the indicator turns the critical colour, or None to never turn critical.
+    /// It is never below the warning threshold.
+    pub critical: Option<u64>,
+    /// How far below a threshold the value has to fall before the colour is cleared, so a value
+    /// hovering around the threshold doesn't make the indicator flicker
+    pub hysteresis: u64,
+    /// Whether the label changes colour along with the value
+    pub colour_label: bool,
+}
+
+impl ThresholdConfiguration {
+    pub fn new(warning: u64, critical: u64, hysteresis: u64) -> Self {
+        Self {
+            warning: Some(warning),
+            critical: Some(critical),
+            hysteresis,
+            colour_label: false,
+        }
+    }
+
+    /// Reads the thresholds from the settings form of the monitor they belong to
+    pub fn update(&self, settings_form: &SettingsForm) -> Self {
+        let warning = ConfigurationValidation::sanitise_threshold(
+            settings_form
+                .values
+                .get(WARNING_THRESHOLD_SETTING_KEY)
+                .expect("Warning threshold missing from settings form options")
+                .value
+                .clone(),
+            self.warning,
+        );
+        let critical = ConfigurationValidation::sanitise_threshold(
+            settings_form
+                .values
+                .get(CRITICAL_THRESHOLD_SETTING_KEY)
+                .expect("Critical threshold missing from settings form options")
+                .value
+                .clone(),
+            self.critical,
+        );
+        let (warning, critical) =
+            ConfigurationValidation::sanitise_threshold_order((warning, critical), (self.warning, self.critical));
+
+        ThresholdConfiguration {
+            warning,
+            critical,
+            hysteresis: ConfigurationValidation::sanitise_hysteresis(
+                settings_form
+                    .values
+                    .get(THRESHOLD_HYSTERESIS_SETTING_KEY)
+                    .expect("Threshold hysteresis missing from settings form options")
+                    .value
+                    .clone(),
+                self.hysteresis,
+            ),
+            colour_label: ConfigurationValidation::sanitise_boolean_input(
+                settings_form
+                    .values
+                    .get(THRESHOLD_COLOUR_LABEL_SETTING_KEY)
+                    .expect("Threshold label colouring missing from settings form options")
+                    .value
+                    .clone(),
+                self.colour_label,
+            ),
+        }
+    }
+}
@@ -1,7 +1,20 @@
 use crate::configuration::app_configuration::{
-    SENSOR_INTERVAL_MINIMUM_IN_MS, SENSOR_MAX_LABEL_LENGTH, SENSOR_MAX_SAMPLES_MINIMUM,
+    SENSOR_INTERVAL_MINIMUM_IN_MS, SENSOR_MAX_LABEL_LENGTH, SENSOR_MAX_SAMPLES_MINIMUM, SENSOR_MAX_UNIT_LENGTH,
 };
+use crate::configuration::alert_rule::AlertRule;
+use crate::configuration::arc::ARC_TEMPLATE_FIELDS;
+use crate::configuration::cpu::CPU_TEMPLATE_FIELDS;
+use crate::configuration::disk::DISK_TEMPLATE_FIELDS;
+use crate::configuration::file::{FileScale, FILE_TEMPLATE_FIELDS};
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::memory::{MemoryValueMode, MEMORY_TEMPLATE_FIELDS};
+use crate::configuration::network::NETWORK_TEMPLATE_FIELDS;
+use crate::configuration::paging::PAGING_TEMPLATE_FIELDS;
+use crate::configuration::power::POWER_TEMPLATE_FIELDS;
+use crate::configuration::smoothing::SmoothingStrategy;
 use crate::fl;
+use regex::Regex;
 use std::cmp;
 use std::time::Duration;
 
@@ -91,6 +104,232 @@ impl ConfigurationValidation {
         cmp::max(parsed_value, SENSOR_MAX_SAMPLES_MINIMUM)
     }
 
+    pub fn is_valid_smoothing_strategy(input: &str) -> Result<(), String> {
+        SmoothingStrategy::from_key(input)
+            .map(|_| ())
+            .ok_or_else(|| fl!("settings-smoothing-error"))
+    }
+
+    pub fn sanitise_smoothing_strategy(new_input: String, previous_value: SmoothingStrategy) -> SmoothingStrategy {
+        SmoothingStrategy::from_key(new_input.as_str()).unwrap_or(previous_value)
+    }
+
+    /// The smoothing window is in seconds, or empty to use the max samples instead
+    pub fn is_valid_smoothing_window(input: &str) -> Result<(), String> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        let error_message = fl!("settings-smoothing-window-error");
+
+        let value = input.trim().parse::<f64>().map_err(|_| error_message.clone())?;
+
+        // A window too short to be a whole nanosecond would drop every sample
+        match Duration::try_from_secs_f64(value) {
+            Ok(window) if !window.is_zero() => Ok(()),
+            _ => Err(error_message),
+        }
+    }
+
+    pub fn sanitise_smoothing_window(new_input: String, previous_value: Option<Duration>) -> Option<Duration> {
+        if Self::is_valid_smoothing_window(new_input.as_str()).is_err() {
+            return previous_value;
+        }
+
+        new_input
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+    }
+
+    pub fn is_valid_display_mode(input: &str) -> Result<(), String> {
+        IndicatorDisplayMode::from_key(input)
+            .map(|_| ())
+            .ok_or_else(|| fl!("settings-display-mode-error"))
+    }
+
+    pub fn sanitise_display_mode(new_input: String, previous_value: IndicatorDisplayMode) -> IndicatorDisplayMode {
+        IndicatorDisplayMode::from_key(new_input.as_str()).unwrap_or(previous_value)
+    }
+
+    pub fn is_valid_memory_value_mode(input: &str) -> Result<(), String> {
+        MemoryValueMode::from_key(input)
+            .map(|_| ())
+            .ok_or_else(|| fl!("settings-memory-value-mode-error"))
+    }
+
+    pub fn sanitise_memory_value_mode(new_input: String, previous_value: MemoryValueMode) -> MemoryValueMode {
+        MemoryValueMode::from_key(new_input.as_str()).unwrap_or(previous_value)
+    }
+
+    pub fn is_valid_format_template(input: &str, fields: &[TemplateField]) -> Result<(), String> {
+        // An empty template leaves the default format
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        FormatTemplate::parse_with_fields(input.trim(), fields).map(|_| ())
+    }
+
+    pub fn is_valid_cpu_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &CPU_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_memory_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &MEMORY_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_network_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &NETWORK_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_disk_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &DISK_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_file_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &FILE_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_power_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &POWER_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_paging_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &PAGING_TEMPLATE_FIELDS)
+    }
+
+    pub fn is_valid_arc_format_template(input: &str) -> Result<(), String> {
+        Self::is_valid_format_template(input, &ARC_TEMPLATE_FIELDS)
+    }
+
+    pub fn sanitise_format_template(
+        new_input: String,
+        previous_value: Option<FormatTemplate>,
+        fields: &[TemplateField],
+    ) -> Option<FormatTemplate> {
+        if new_input.trim().is_empty() {
+            return None;
+        }
+
+        FormatTemplate::parse_with_fields(new_input.trim(), fields)
+            .ok()
+            .or(previous_value)
+    }
+
+    pub fn is_valid_threshold(input: &str) -> Result<(), String> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        input
+            .trim()
+            .parse::<u64>()
+            .map(|_| ())
+            .map_err(|_| fl!("settings-threshold-error"))
+    }
+
+    pub fn sanitise_threshold(new_input: String, previous_value: Option<u64>) -> Option<u64> {
+        if Self::is_valid_threshold(new_input.as_str()).is_err() {
+            return previous_value;
+        }
+
+        new_input.trim().parse::<u64>().ok()
+    }
+
+    /// A warning threshold above the critical one would never show, as the critical colour wins
+    pub fn is_valid_threshold_order(warning: Option<u64>, critical: Option<u64>) -> Result<(), String> {
+        match (warning, critical) {
+            (Some(warning), Some(critical)) if warning > critical => Err(fl!("settings-threshold-order-error")),
+            _ => Ok(()),
+        }
+    }
+
+    /// The new warning and critical thresholds, or the previous ones if the warning is above the critical
+    pub fn sanitise_threshold_order(
+        new_thresholds: (Option<u64>, Option<u64>),
+        previous_thresholds: (Option<u64>, Option<u64>),
+    ) -> (Option<u64>, Option<u64>) {
+        let (warning, critical) = new_thresholds;
+
+        if Self::is_valid_threshold_order(warning, critical).is_err() {
+            return previous_thresholds;
+        }
+
+        new_thresholds
+    }
+
+    pub fn is_valid_hysteresis(input: &str) -> Result<(), String> {
+        input
+            .trim()
+            .parse::<u64>()
+            .map(|_| ())
+            .map_err(|_| fl!("settings-threshold-hysteresis-error"))
+    }
+
+    pub fn sanitise_hysteresis(new_input: String, previous_value: u64) -> u64 {
+        if Self::is_valid_hysteresis(new_input.as_str()).is_err() {
+            return previous_value;
+        }
+
+        new_input
+            .trim()
+            .parse::<u64>()
+            .expect("Failed to parse. Should always be valid here.")
+    }
+
+    pub fn is_valid_alert_rule(input: &str) -> Result<(), String> {
+        // An empty rule leaves its slot unused
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        input.parse::<AlertRule>().map(|_| ())
+    }
+
+    pub fn sanitise_alert_rule(new_input: String, previous_value: Option<AlertRule>) -> Option<AlertRule> {
+        if new_input.trim().is_empty() {
+            return None;
+        }
+
+        new_input.parse::<AlertRule>().ok().or(previous_value)
+    }
+
+    /// A hook is only run by the rule in its slot, so one without a rule would be lost
+    pub fn is_valid_alert_hook_for_rule(rule_input: &str, hook_input: &str) -> Result<(), String> {
+        match rule_input.trim().is_empty() && !hook_input.trim().is_empty() {
+            true => Err(fl!("settings-alert-hook-rule-error")),
+            false => Ok(()),
+        }
+    }
+
+    /// Any command is accepted as it is run by the shell, so only an empty one is left out
+    pub fn sanitise_alert_hook(new_input: String) -> Option<String> {
+        let hook = new_input.trim();
+
+        match hook.is_empty() {
+            true => None,
+            false => Some(hook.to_string()),
+        }
+    }
+
+    pub fn is_valid_alert_cooldown(input: &str) -> Result<(), String> {
+        input
+            .trim()
+            .parse::<u64>()
+            .map(|_| ())
+            .map_err(|_| fl!("settings-alert-cooldown-error"))
+    }
+
+    pub fn sanitise_alert_cooldown(new_input: String, previous_value: Duration) -> Duration {
+        new_input
+            .trim()
+            .parse::<u64>()
+            .map(Duration::from_secs)
+            .unwrap_or(previous_value)
+    }
+
     pub fn is_valid_label_text(input: &str) -> Result<(), String> {
         if input.trim().is_empty() || input.len() > SENSOR_MAX_LABEL_LENGTH {
             Err(fl!("settings-label-text-error", max_length = SENSOR_MAX_LABEL_LENGTH))
@@ -112,6 +351,125 @@ impl ConfigurationValidation {
 
         new_input.trim().to_string()
     }
+
+    pub fn is_valid_file_path(input: &str) -> Result<(), String> {
+        let trimmed = input.trim();
+
+        // An empty path is allowed as the file monitor is disabled until one is set.
+        if trimmed.is_empty() || trimmed.starts_with('/') {
+            Ok(())
+        } else {
+            Err(fl!("settings-file-path-error"))
+        }
+    }
+
+    pub fn sanitise_file_path(new_input: String, previous_input: String) -> String {
+        if Self::is_valid_file_path(new_input.as_str()).is_err() {
+            return previous_input;
+        }
+
+        new_input.trim().to_string()
+    }
+
+    pub fn is_valid_file_pattern(input: &str) -> Result<(), String> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        Regex::new(input.trim())
+            .map(|_| ())
+            .map_err(|_| fl!("settings-file-pattern-error"))
+    }
+
+    pub fn sanitise_file_pattern(new_input: String, previous_input: Option<String>) -> Option<String> {
+        if Self::is_valid_file_pattern(new_input.as_str()).is_err() {
+            return previous_input;
+        }
+
+        let trimmed = new_input.trim();
+
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_string())
+        }
+    }
+
+    pub fn is_valid_field_index(input: &str) -> Result<(), String> {
+        if input.trim().is_empty() {
+            return Ok(());
+        }
+
+        input
+            .trim()
+            .parse::<usize>()
+            .map(|_| ())
+            .map_err(|_| fl!("settings-file-field-index-error"))
+    }
+
+    pub fn sanitise_field_index(new_input: String, previous_value: Option<usize>) -> Option<usize> {
+        if Self::is_valid_field_index(new_input.as_str()).is_err() {
+            return previous_value;
+        }
+
+        new_input.trim().parse::<usize>().ok()
+    }
+
+    pub fn is_valid_scale(input: &str) -> Result<(), String> {
+        let error_message = fl!("settings-file-scale-error");
+
+        let value = input.trim().parse::<f64>().map_err(|_| error_message.clone())?;
+
+        match FileScale::new(value) {
+            Some(_) => Ok(()),
+            None => Err(error_message),
+        }
+    }
+
+    pub fn sanitise_scale(new_input: String, previous_value: FileScale) -> FileScale {
+        if Self::is_valid_scale(new_input.as_str()).is_err() {
+            return previous_value;
+        }
+
+        new_input
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(FileScale::new)
+            .expect("Failed to parse. Should always be valid here.")
+    }
+
+    pub fn is_valid_unit(input: &str) -> Result<(), String> {
+        if input.trim().chars().count() > SENSOR_MAX_UNIT_LENGTH {
+            Err(fl!("settings-file-unit-error", max_length = SENSOR_MAX_UNIT_LENGTH))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn sanitise_unit(new_input: String, previous_input: String) -> String {
+        if Self::is_valid_unit(new_input.as_str()).is_err() {
+            return previous_input;
+        }
+
+        new_input.trim().to_string()
+    }
+
+    pub fn is_valid_sensor_root(input: &str) -> Result<(), String> {
+        if input.trim().starts_with('/') {
+            Ok(())
+        } else {
+            Err(fl!("settings-sensor-root-error"))
+        }
+    }
+
+    pub fn sanitise_sensor_root(new_input: String, previous_input: String) -> String {
+        if Self::is_valid_sensor_root(new_input.as_str()).is_err() {
+            return previous_input;
+        }
+
+        new_input.trim().to_string()
+    }
 }
 
 #[cfg(test)]
@@ -332,3 +690,403 @@ mod label_text_tests {
         assert_eq!(result, trimmed);   
     }
 }
+
+#[cfg(test)]
+mod file_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::app_configuration::SENSOR_MAX_UNIT_LENGTH;
+    use crate::configuration::file::FileScale;
+
+    #[test]
+    fn is_valid_file_path_accepts_empty_and_absolute_paths() {
+        assert!(ConfigurationValidation::is_valid_file_path("").is_ok());
+        assert!(ConfigurationValidation::is_valid_file_path("/sys/class/backlight/acpi_video0/brightness").is_ok());
+    }
+
+    #[test]
+    fn is_valid_file_path_rejects_relative_paths() {
+        assert!(ConfigurationValidation::is_valid_file_path("sys/class/hwmon").is_err());
+        assert!(ConfigurationValidation::is_valid_file_path("./brightness").is_err());
+    }
+
+    #[test]
+    fn sanitise_file_path_returns_previous_when_invalid() {
+        let previous = "/proc/loadavg".to_string();
+        let result = ConfigurationValidation::sanitise_file_path("loadavg".to_string(), previous.clone());
+        assert_eq!(result, previous);
+    }
+
+    #[test]
+    fn is_valid_file_pattern_accepts_empty_and_valid_regex() {
+        assert!(ConfigurationValidation::is_valid_file_pattern("").is_ok());
+        assert!(ConfigurationValidation::is_valid_file_pattern(r"temp:\s+(\d+)").is_ok());
+    }
+
+    #[test]
+    fn is_valid_file_pattern_rejects_invalid_regex() {
+        assert!(ConfigurationValidation::is_valid_file_pattern("(unclosed").is_err());
+    }
+
+    #[test]
+    fn sanitise_file_pattern_returns_none_when_empty() {
+        let result = ConfigurationValidation::sanitise_file_pattern("  ".to_string(), Some("(\\d+)".to_string()));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn sanitise_file_pattern_returns_previous_when_invalid() {
+        let previous = Some("(\\d+)".to_string());
+        let result = ConfigurationValidation::sanitise_file_pattern("[".to_string(), previous.clone());
+        assert_eq!(result, previous);
+    }
+
+    #[test]
+    fn sanitise_field_index_handles_empty_valid_and_invalid() {
+        assert_eq!(ConfigurationValidation::sanitise_field_index("".to_string(), Some(2)), None);
+        assert_eq!(ConfigurationValidation::sanitise_field_index(" 3 ".to_string(), None), Some(3));
+        assert_eq!(ConfigurationValidation::sanitise_field_index("-1".to_string(), Some(2)), Some(2));
+    }
+
+    #[test]
+    fn is_valid_scale_rejects_zero_and_non_numeric() {
+        assert!(ConfigurationValidation::is_valid_scale("0").is_err());
+        assert!(ConfigurationValidation::is_valid_scale("abc").is_err());
+        assert!(ConfigurationValidation::is_valid_scale("inf").is_err());
+        assert!(ConfigurationValidation::is_valid_scale("").is_err());
+    }
+
+    #[test]
+    fn sanitise_scale_returns_value_when_valid() {
+        let previous = FileScale::default();
+
+        assert_eq!(
+            ConfigurationValidation::sanitise_scale("0.001".to_string(), previous).value(),
+            0.001
+        );
+        assert_eq!(ConfigurationValidation::sanitise_scale("nope".to_string(), previous), previous);
+    }
+
+    #[test]
+    fn sanitise_unit_returns_previous_when_too_long() {
+        let too_long = "V".repeat(SENSOR_MAX_UNIT_LENGTH + 1);
+        let result = ConfigurationValidation::sanitise_unit(too_long, "V".to_string());
+        assert_eq!(result, "V");
+    }
+}
+
+#[cfg(test)]
+mod smoothing_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::smoothing::SmoothingStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn is_valid_smoothing_strategy_accepts_only_known_keys() {
+        for strategy in SmoothingStrategy::ALL {
+            assert!(ConfigurationValidation::is_valid_smoothing_strategy(strategy.key()).is_ok());
+        }
+
+        assert!(ConfigurationValidation::is_valid_smoothing_strategy("").is_err());
+        assert!(ConfigurationValidation::is_valid_smoothing_strategy("mean").is_err());
+    }
+
+    #[test]
+    fn sanitise_smoothing_strategy_returns_previous_when_invalid() {
+        let result = ConfigurationValidation::sanitise_smoothing_strategy(
+            "mean".to_string(),
+            SmoothingStrategy::Median,
+        );
+        assert_eq!(result, SmoothingStrategy::Median);
+
+        let result = ConfigurationValidation::sanitise_smoothing_strategy(
+            " peak-hold ".to_string(),
+            SmoothingStrategy::Median,
+        );
+        assert_eq!(result, SmoothingStrategy::PeakHold);
+    }
+
+    #[test]
+    fn is_valid_smoothing_window_accepts_empty_and_positive_seconds() {
+        assert!(ConfigurationValidation::is_valid_smoothing_window("").is_ok());
+        assert!(ConfigurationValidation::is_valid_smoothing_window("2.5").is_ok());
+        assert!(ConfigurationValidation::is_valid_smoothing_window(" 30 ").is_ok());
+    }
+
+    #[test]
+    fn is_valid_smoothing_window_rejects_zero_negative_and_non_numeric() {
+        assert!(ConfigurationValidation::is_valid_smoothing_window("0").is_err());
+        assert!(ConfigurationValidation::is_valid_smoothing_window("0.0000000001").is_err());
+        assert!(ConfigurationValidation::is_valid_smoothing_window("-1").is_err());
+        assert!(ConfigurationValidation::is_valid_smoothing_window("inf").is_err());
+        assert!(ConfigurationValidation::is_valid_smoothing_window("ten").is_err());
+    }
+
+    #[test]
+    fn sanitise_smoothing_window_handles_empty_valid_and_invalid() {
+        let previous = Some(Duration::from_secs(10));
+
+        assert_eq!(ConfigurationValidation::sanitise_smoothing_window("".to_string(), previous), None);
+        assert_eq!(
+            ConfigurationValidation::sanitise_smoothing_window("2.5".to_string(), None),
+            Some(Duration::from_millis(2500))
+        );
+        assert_eq!(ConfigurationValidation::sanitise_smoothing_window("0".to_string(), previous), previous);
+    }
+}
+
+#[cfg(test)]
+mod display_mode_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+
+    #[test]
+    fn is_valid_display_mode_accepts_only_known_keys() {
+        for mode in IndicatorDisplayMode::ALL {
+            assert!(ConfigurationValidation::is_valid_display_mode(mode.key()).is_ok());
+        }
+
+        assert!(ConfigurationValidation::is_valid_display_mode("").is_err());
+        assert!(ConfigurationValidation::is_valid_display_mode("chart").is_err());
+    }
+
+    #[test]
+    fn sanitise_display_mode_returns_previous_when_invalid() {
+        let result =
+            ConfigurationValidation::sanitise_display_mode("chart".to_string(), IndicatorDisplayMode::Both);
+        assert_eq!(result, IndicatorDisplayMode::Both);
+
+        let result =
+            ConfigurationValidation::sanitise_display_mode(" graph ".to_string(), IndicatorDisplayMode::Number);
+        assert_eq!(result, IndicatorDisplayMode::Graph);
+    }
+}
+
+#[cfg(test)]
+mod memory_value_mode_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::memory::MemoryValueMode;
+
+    #[test]
+    fn is_valid_memory_value_mode_accepts_only_known_keys() {
+        for mode in MemoryValueMode::ALL {
+            assert!(ConfigurationValidation::is_valid_memory_value_mode(mode.key()).is_ok());
+        }
+
+        assert!(ConfigurationValidation::is_valid_memory_value_mode("").is_err());
+        assert!(ConfigurationValidation::is_valid_memory_value_mode("free").is_err());
+    }
+
+    #[test]
+    fn sanitise_memory_value_mode_returns_previous_when_invalid() {
+        let result =
+            ConfigurationValidation::sanitise_memory_value_mode("free".to_string(), MemoryValueMode::Available);
+        assert_eq!(result, MemoryValueMode::Available);
+
+        let result = ConfigurationValidation::sanitise_memory_value_mode(
+            " percent-used ".to_string(),
+            MemoryValueMode::Used,
+        );
+        assert_eq!(result, MemoryValueMode::PercentUsed);
+    }
+}
+
+#[cfg(test)]
+mod format_template_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::format_template::FormatTemplate;
+    use crate::configuration::memory::MEMORY_TEMPLATE_FIELDS;
+    use crate::configuration::network::NETWORK_TEMPLATE_FIELDS;
+
+    #[test]
+    fn format_templates_may_only_use_the_monitor_fields() {
+        assert!(ConfigurationValidation::is_valid_memory_format_template("").is_ok());
+        assert!(ConfigurationValidation::is_valid_memory_format_template("{used:.1}/{total:.0} {unit}").is_ok());
+        assert!(ConfigurationValidation::is_valid_network_format_template("{rx}↓ {tx}↑").is_ok());
+        assert!(ConfigurationValidation::is_valid_network_format_template("{used}").is_err());
+        assert!(ConfigurationValidation::is_valid_cpu_format_template("{usage").is_err());
+        assert!(ConfigurationValidation::is_valid_disk_format_template("{read} {write:.2}").is_ok());
+        assert!(ConfigurationValidation::is_valid_file_format_template("{value:.0}{unit}").is_ok());
+        assert!(ConfigurationValidation::is_valid_file_format_template("{unit:.1}").is_err());
+        assert!(ConfigurationValidation::is_valid_power_format_template("{package:.0}W {dram}W").is_ok());
+        assert!(ConfigurationValidation::is_valid_paging_format_template("{in} {out} {faults:.0}").is_ok());
+        assert!(ConfigurationValidation::is_valid_paging_format_template("{rx}").is_err());
+        assert!(ConfigurationValidation::is_valid_arc_format_template("{size}/{target} {unit} {ratio:.0}%").is_ok());
+    }
+
+    #[test]
+    fn sanitise_format_template_clears_on_empty_and_keeps_previous_when_invalid() {
+        let previous = "{rx}↓ {tx}↑".parse::<FormatTemplate>().ok();
+
+        assert_eq!(
+            ConfigurationValidation::sanitise_format_template(
+                " ".to_string(),
+                previous.clone(),
+                &NETWORK_TEMPLATE_FIELDS
+            ),
+            None
+        );
+        assert_eq!(
+            ConfigurationValidation::sanitise_format_template(
+                "{used}".to_string(),
+                previous.clone(),
+                &NETWORK_TEMPLATE_FIELDS
+            ),
+            previous
+        );
+        assert_eq!(
+            ConfigurationValidation::sanitise_format_template(
+                " {percent:.0}% ".to_string(),
+                None,
+                &MEMORY_TEMPLATE_FIELDS
+            ),
+            "{percent:.0}%".parse().ok()
+        );
+    }
+}
+
+#[cfg(test)]
+mod threshold_tests {
+    use super::ConfigurationValidation;
+    use crate::configuration::app_configuration::{CRITICAL_THRESHOLD_SETTING_KEY, WARNING_THRESHOLD_SETTING_KEY};
+    use crate::configuration::cpu::CpuConfiguration;
+    use crate::core::settings::SettingsForm;
+
+    #[test]
+    fn is_valid_threshold_accepts_empty_and_whole_numbers() {
+        assert!(ConfigurationValidation::is_valid_threshold("").is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold(" 90 ").is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold("-1").is_err());
+        assert!(ConfigurationValidation::is_valid_threshold("9.5").is_err());
+    }
+
+    #[test]
+    fn sanitise_threshold_clears_on_empty_and_keeps_previous_when_invalid() {
+        assert_eq!(ConfigurationValidation::sanitise_threshold("".to_string(), Some(70)), None);
+        assert_eq!(ConfigurationValidation::sanitise_threshold("80".to_string(), Some(70)), Some(80));
+        assert_eq!(ConfigurationValidation::sanitise_threshold("high".to_string(), Some(70)), Some(70));
+    }
+
+    #[test]
+    fn is_valid_threshold_order_rejects_a_warning_above_the_critical() {
+        assert!(ConfigurationValidation::is_valid_threshold_order(Some(70), Some(90)).is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold_order(Some(90), Some(90)).is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold_order(Some(95), None).is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold_order(None, Some(50)).is_ok());
+        assert!(ConfigurationValidation::is_valid_threshold_order(Some(95), Some(90)).is_err());
+    }
+
+    #[test]
+    fn sanitise_threshold_order_keeps_previous_when_invalid() {
+        let previous = (Some(70), Some(90));
+
+        assert_eq!(
+            ConfigurationValidation::sanitise_threshold_order((Some(60), Some(80)), previous),
+            (Some(60), Some(80))
+        );
+        assert_eq!(
+            ConfigurationValidation::sanitise_threshold_order((Some(95), Some(80)), previous),
+            previous
+        );
+    }
+
+    #[test]
+    fn the_settings_form_reports_a_warning_threshold_above_the_critical() {
+        let mut form = SettingsForm::from(&CpuConfiguration::default());
+
+        form.values.get_mut(WARNING_THRESHOLD_SETTING_KEY).unwrap().value = "95".to_string();
+
+        assert!(form.validate_against_other_fields(WARNING_THRESHOLD_SETTING_KEY).is_ok());
+        assert!(form.validate_against_other_fields(CRITICAL_THRESHOLD_SETTING_KEY).is_err());
+
+        form.values.get_mut(CRITICAL_THRESHOLD_SETTING_KEY).unwrap().value = "".to_string();
+
+        assert!(form.validate_against_other_fields(CRITICAL_THRESHOLD_SETTING_KEY).is_ok());
+    }
+
+    #[test]
+    fn sanitise_hysteresis_keeps_previous_when_invalid() {
+        assert_eq!(ConfigurationValidation::sanitise_hysteresis("".to_string(), 5), 5);
+        assert_eq!(ConfigurationValidation::sanitise_hysteresis(" 0 ".to_string(), 5), 0);
+    }
+}
+
+#[cfg(test)]
+mod alert_tests {
+    use super::ConfigurationValidation;
+    use std::time::Duration;
+
+    #[test]
+    fn is_valid_alert_rule_accepts_empty_and_valid_rules() {
+        assert!(ConfigurationValidation::is_valid_alert_rule(" ").is_ok());
+        assert!(ConfigurationValidation::is_valid_alert_rule("cpu > 95% for 30s").is_ok());
+        assert!(ConfigurationValidation::is_valid_alert_rule("cpu is busy").is_err());
+    }
+
+    #[test]
+    fn sanitise_alert_rule_clears_on_empty_and_keeps_previous_when_invalid() {
+        let previous = "cpu > 95%".parse().ok();
+
+        assert_eq!(ConfigurationValidation::sanitise_alert_rule("".to_string(), previous.clone()), None);
+        assert_eq!(
+            ConfigurationValidation::sanitise_alert_rule("cpu is busy".to_string(), previous.clone()),
+            previous
+        );
+        assert_eq!(
+            ConfigurationValidation::sanitise_alert_rule("memory > 80%".to_string(), previous),
+            "memory > 80%".parse().ok()
+        );
+    }
+
+    #[test]
+    fn sanitise_alert_hook_trims_and_clears_on_empty() {
+        assert_eq!(ConfigurationValidation::sanitise_alert_hook("  ".to_string()), None);
+        assert_eq!(
+            ConfigurationValidation::sanitise_alert_hook(" pkill -STOP syncthing ".to_string()),
+            Some("pkill -STOP syncthing".to_string())
+        );
+    }
+
+    #[test]
+    fn sanitise_alert_cooldown_is_in_seconds() {
+        let previous = Duration::from_secs(300);
+
+        assert_eq!(
+            ConfigurationValidation::sanitise_alert_cooldown(" 60 ".to_string(), previous),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            ConfigurationValidation::sanitise_alert_cooldown("soon".to_string(), previous),
+            previous
+        );
+    }
+}
+
+#[cfg(test)]
+mod sensor_root_tests {
+    use super::ConfigurationValidation;
+
+    #[test]
+    fn is_valid_sensor_root_accepts_absolute_paths() {
+        assert!(ConfigurationValidation::is_valid_sensor_root("/").is_ok());
+        assert!(ConfigurationValidation::is_valid_sensor_root("/run/host").is_ok());
+    }
+
+    #[test]
+    fn is_valid_sensor_root_rejects_empty_and_relative_paths() {
+        assert!(ConfigurationValidation::is_valid_sensor_root("").is_err());
+        assert!(ConfigurationValidation::is_valid_sensor_root("run/host").is_err());
+    }
+
+    #[test]
+    fn sanitise_sensor_root_returns_previous_when_invalid() {
+        let previous = "/".to_string();
+        let result = ConfigurationValidation::sanitise_sensor_root("host".to_string(), previous.clone());
+        assert_eq!(result, previous);
+    }
+
+    #[test]
+    fn sanitise_sensor_root_trims_valid_input() {
+        let result = ConfigurationValidation::sanitise_sensor_root(" /run/host ".to_string(), "/".to_string());
+        assert_eq!(result, "/run/host");
+    }
+}
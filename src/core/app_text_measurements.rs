@@ -8,7 +8,7 @@ pub struct AppTextMeasurements {
     /// The cosmic text measurer that is used to measure text as rendered
     cosmic_text_measurer: CosmicTextMeasurer,
     /// The text measurements for the labels, keyed by the label text.
-    text_measurements: std::cell::RefCell<HashMap<(&'static str, u16), f32>>,
+    text_measurements: std::cell::RefCell<HashMap<(String, u16), f32>>,
 
 }
 
@@ -17,9 +17,9 @@ impl AppTextMeasurements {
         Self::default()
     }
 
-    pub fn measure(&self, text: &'static str, font_size: u16) -> Option<f32> {
+    pub fn measure(&self, text: &str, font_size: u16) -> Option<f32> {
 
-        if let Some(value) = self.text_measurements.borrow().get(&(text, font_size)).copied() {
+        if let Some(value) = self.text_measurements.borrow().get(&(text.to_string(), font_size)).copied() {
             return Some(value);
         }
 
@@ -34,7 +34,7 @@ impl AppTextMeasurements {
 
         self.text_measurements
             .borrow_mut()
-            .insert((text, font_size), new_measurement);
+            .insert((text.to_string(), font_size), new_measurement);
 
         Some(new_measurement)
 
@@ -0,0 +1,317 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// How long samples are kept at the resolution they were polled at
+pub const RECENT_HISTORY_SPAN: Duration = Duration::from_secs(10 * 60);
+/// How long the downsampled history is kept for
+pub const DOWNSAMPLED_HISTORY_SPAN: Duration = Duration::from_secs(6 * 60 * 60);
+/// The span of time each downsampled bucket covers
+pub const DOWNSAMPLED_BUCKET_SPAN: Duration = Duration::from_secs(10);
+/// The most samples kept at full resolution, in case a monitor is polled very often
+const MAX_RECENT_SAMPLES: usize = 4096;
+
+/// A named value reported by a monitor, i.e. the CPU usage percentage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricValue {
+    pub name: &'static str,
+    pub value: f64,
+}
+
+impl MetricValue {
+    pub fn new(name: &'static str, value: f64) -> Self {
+        Self { name, value }
+    }
+}
+
+/// A value of a metric over a span of time. A sample polled at full resolution covers a single
+/// instant, so its minimum, maximum and average are all the same value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricSample {
+    /// When the sample, or the first sample in a downsampled bucket, was recorded
+    pub timestamp: Instant,
+    pub average: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl MetricSample {
+    fn single(timestamp: Instant, value: f64) -> Self {
+        Self {
+            timestamp,
+            average: value,
+            min: value,
+            max: value,
+        }
+    }
+}
+
+/// The readouts of a metric over a span of time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricSummary {
+    pub latest: f64,
+    pub min: f64,
+    pub max: f64,
+    pub average: f64,
+}
+
+/// A downsampled bucket that is still being filled
+#[derive(Debug, Clone)]
+struct MetricBucket {
+    sample: MetricSample,
+    total: f64,
+    count: usize,
+}
+
+impl MetricBucket {
+    fn new(timestamp: Instant, value: f64) -> Self {
+        Self {
+            sample: MetricSample::single(timestamp, value),
+            total: value,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.total += value;
+        self.count += 1;
+        self.sample.min = self.sample.min.min(value);
+        self.sample.max = self.sample.max.max(value);
+        self.sample.average = self.total / self.count as f64;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct MetricSeries {
+    recent: VecDeque<MetricSample>,
+    downsampled: VecDeque<MetricSample>,
+    bucket: Option<MetricBucket>,
+}
+
+impl MetricSeries {
+    fn record(&mut self, timestamp: Instant, value: f64) {
+        self.recent.push_back(MetricSample::single(timestamp, value));
+
+        while self.recent.len() > MAX_RECENT_SAMPLES
+            || self
+                .recent
+                .front()
+                .is_some_and(|sample| timestamp.saturating_duration_since(sample.timestamp) > RECENT_HISTORY_SPAN)
+        {
+            self.recent.pop_front();
+        }
+
+        match &mut self.bucket {
+            Some(bucket) if timestamp.saturating_duration_since(bucket.sample.timestamp) < DOWNSAMPLED_BUCKET_SPAN => {
+                bucket.add(value)
+            }
+            bucket => {
+                if let Some(full) = bucket.replace(MetricBucket::new(timestamp, value)) {
+                    self.downsampled.push_back(full.sample);
+                }
+            }
+        }
+
+        while self
+            .downsampled
+            .front()
+            .is_some_and(|sample| timestamp.saturating_duration_since(sample.timestamp) > DOWNSAMPLED_HISTORY_SPAN)
+        {
+            self.downsampled.pop_front();
+        }
+    }
+
+    fn latest(&self) -> Option<MetricSample> {
+        self.recent.back().copied()
+    }
+
+    /// The samples recorded at or after `since`, or all of them if it is None
+    fn since(&self, since: Option<Instant>, full_resolution: bool) -> Vec<MetricSample> {
+        let is_included = |sample: &&MetricSample| match since {
+            Some(since) => sample.timestamp >= since,
+            None => true,
+        };
+
+        if full_resolution {
+            return self.recent.iter().filter(is_included).copied().collect();
+        }
+
+        self.downsampled
+            .iter()
+            .chain(self.bucket.as_ref().map(|bucket| &bucket.sample))
+            .filter(is_included)
+            .copied()
+            .collect()
+    }
+}
+
+/// The recent values of every metric reported by the monitors, so trends can be drawn or exported.
+/// The last few minutes are kept as polled, and the last few hours downsampled into buckets.
+#[derive(Debug, Clone, Default)]
+pub struct MetricHistory {
+    series: BTreeMap<&'static str, MetricSeries>,
+}
+
+impl MetricHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, timestamp: Instant, values: impl IntoIterator<Item = MetricValue>) {
+        for metric in values {
+            self.series
+                .entry(metric.name)
+                .or_default()
+                .record(timestamp, metric.value);
+        }
+    }
+
+    /// The names of every metric that has been recorded
+    pub fn metric_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.series.keys().copied()
+    }
+
+    /// The most recently recorded sample of a metric
+    pub fn latest(&self, metric: &str) -> Option<MetricSample> {
+        self.series.get(metric).and_then(MetricSeries::latest)
+    }
+
+    /// The samples of a metric from the last `span` before `now`, oldest first. Spans longer than
+    /// the full resolution history are answered from the downsampled buckets.
+    pub fn query(&self, metric: &str, span: Duration, now: Instant) -> Vec<MetricSample> {
+        // An instant before the clock started can't be represented, but then every sample is in the span
+        let since = now.checked_sub(span);
+        let full_resolution = span <= RECENT_HISTORY_SPAN;
+
+        self.series
+            .get(metric)
+            .map(|series| series.since(since, full_resolution))
+            .unwrap_or_default()
+    }
+
+    /// The latest, minimum, maximum and average of a metric over the last `span` before `now`
+    pub fn summary(&self, metric: &str, span: Duration, now: Instant) -> Option<MetricSummary> {
+        let samples = self.query(metric, span, now);
+        let latest = samples.last()?.average;
+
+        Some(MetricSummary {
+            latest,
+            min: samples.iter().map(|sample| sample.min).fold(f64::INFINITY, f64::min),
+            max: samples
+                .iter()
+                .map(|sample| sample.max)
+                .fold(f64::NEG_INFINITY, f64::max),
+            average: samples.iter().map(|sample| sample.average).sum::<f64>() / samples.len() as f64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CPU: &str = "cpu";
+    const MEMORY: &str = "memory";
+
+    fn record_every_second(history: &mut MetricHistory, start: Instant, values: &[f64]) {
+        for (second, value) in values.iter().enumerate() {
+            history.record(
+                start + Duration::from_secs(second as u64),
+                [MetricValue::new(CPU, *value)],
+            );
+        }
+    }
+
+    #[test]
+    fn metrics_are_recorded_separately() {
+        let mut history = MetricHistory::new();
+        let now = Instant::now();
+
+        history.record(now, [MetricValue::new(CPU, 10.0), MetricValue::new(MEMORY, 2048.0)]);
+
+        assert_eq!(history.metric_names().collect::<Vec<_>>(), vec![CPU, MEMORY]);
+        assert_eq!(history.latest(CPU).unwrap().average, 10.0);
+        assert_eq!(history.latest(MEMORY).unwrap().average, 2048.0);
+        assert_eq!(history.latest("disk"), None);
+    }
+
+    #[test]
+    fn short_query_returns_samples_at_full_resolution() {
+        let mut history = MetricHistory::new();
+        let start = Instant::now();
+        record_every_second(&mut history, start, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+
+        let samples = history.query(CPU, Duration::from_secs(2), start + Duration::from_secs(4));
+
+        assert_eq!(
+            samples.iter().map(|sample| sample.average).collect::<Vec<_>>(),
+            vec![3.0, 4.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn full_resolution_history_is_bounded_in_time() {
+        let mut history = MetricHistory::new();
+        let start = Instant::now();
+        let values = vec![1.0; RECENT_HISTORY_SPAN.as_secs() as usize + 60];
+        record_every_second(&mut history, start, &values);
+
+        let series = &history.series[CPU];
+
+        assert_eq!(series.recent.len(), RECENT_HISTORY_SPAN.as_secs() as usize + 1);
+    }
+
+    #[test]
+    fn long_query_returns_downsampled_buckets() {
+        let mut history = MetricHistory::new();
+        let start = Instant::now();
+        let values: Vec<f64> = (0..(RECENT_HISTORY_SPAN.as_secs() + 60))
+            .map(|second| (second % 10) as f64)
+            .collect();
+        record_every_second(&mut history, start, &values);
+        let now = start + Duration::from_secs(values.len() as u64 - 1);
+
+        let samples = history.query(CPU, Duration::from_secs(60 * 60), now);
+
+        assert_eq!(samples.len(), values.len() / 10);
+        assert_eq!(samples[0].timestamp, start);
+        assert_eq!(samples[0].min, 0.0);
+        assert_eq!(samples[0].max, 9.0);
+        assert_eq!(samples[0].average, 4.5);
+    }
+
+    #[test]
+    fn downsampled_history_is_bounded_in_time() {
+        let mut series = MetricSeries::default();
+        let start = Instant::now();
+        let end = DOWNSAMPLED_HISTORY_SPAN.as_secs() + 600;
+
+        for second in (0..=end).step_by(5) {
+            series.record(start + Duration::from_secs(second), 1.0);
+        }
+
+        let oldest = series.downsampled.front().unwrap().timestamp;
+        assert!(start + Duration::from_secs(end) - oldest <= DOWNSAMPLED_HISTORY_SPAN);
+    }
+
+    #[test]
+    fn summary_gives_latest_min_max_and_average() {
+        let mut history = MetricHistory::new();
+        let start = Instant::now();
+        record_every_second(&mut history, start, &[40.0, 10.0, 30.0, 20.0]);
+
+        let summary = history
+            .summary(CPU, Duration::from_secs(60), start + Duration::from_secs(3))
+            .unwrap();
+
+        assert_eq!(
+            summary,
+            MetricSummary {
+                latest: 20.0,
+                min: 10.0,
+                max: 40.0,
+                average: 25.0,
+            }
+        );
+        assert_eq!(history.summary(MEMORY, Duration::from_secs(60), start), None);
+    }
+}
@@ -6,5 +6,7 @@ pub mod app_text_measurements;
 pub mod cosmic_text_measurer;
 pub mod app_icons;
 pub mod settings;
+pub mod monitor_health;
+pub mod metric_history;
 
 
@@ -0,0 +1,98 @@
+use crate::sensors::sensor_error::SensorError;
+use std::time::Instant;
+
+/// How much the indicator for a monitor can be trusted
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MonitorStatus {
+    /// The last poll succeeded
+    Healthy,
+    /// The last poll failed, but an earlier value is still available to show
+    Stale,
+    /// The sensor is permanently unavailable or has never been read successfully
+    Unavailable,
+}
+
+/// The poll history of a single monitor
+#[derive(Clone, Debug, Default)]
+pub struct MonitorHealth {
+    /// When the monitor was last polled successfully
+    pub last_success: Option<Instant>,
+    /// The most recent error, kept after the monitor recovers
+    pub last_error: Option<SensorError>,
+    /// The number of polls that have failed since the last success
+    pub consecutive_failures: u32,
+}
+
+impl MonitorHealth {
+    pub fn record_success(&mut self, now: Instant) {
+        self.last_success = Some(now);
+        self.consecutive_failures = 0;
+    }
+
+    pub fn record_failure(&mut self, error: SensorError) {
+        self.last_error = Some(error);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+
+    pub fn status(&self) -> MonitorStatus {
+        match &self.last_error {
+            Some(_) if self.consecutive_failures == 0 => MonitorStatus::Healthy,
+            Some(error) if error.is_permanent() || self.last_success.is_none() => MonitorStatus::Unavailable,
+            Some(_) => MonitorStatus::Stale,
+            None => MonitorStatus::Healthy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transient() -> SensorError {
+        SensorError::Transient("interrupted".to_string())
+    }
+
+    #[test]
+    fn new_monitor_is_healthy() {
+        assert_eq!(MonitorHealth::default().status(), MonitorStatus::Healthy);
+    }
+
+    #[test]
+    fn transient_failure_after_success_is_stale() {
+        let mut health = MonitorHealth::default();
+        health.record_success(Instant::now());
+        health.record_failure(transient());
+        health.record_failure(transient());
+
+        assert_eq!(health.status(), MonitorStatus::Stale);
+        assert_eq!(health.consecutive_failures, 2);
+    }
+
+    #[test]
+    fn failure_without_any_success_is_unavailable() {
+        let mut health = MonitorHealth::default();
+        health.record_failure(transient());
+
+        assert_eq!(health.status(), MonitorStatus::Unavailable);
+    }
+
+    #[test]
+    fn permanent_failure_is_unavailable_even_after_success() {
+        let mut health = MonitorHealth::default();
+        health.record_success(Instant::now());
+        health.record_failure(SensorError::Unavailable("gone".to_string()));
+
+        assert_eq!(health.status(), MonitorStatus::Unavailable);
+    }
+
+    #[test]
+    fn success_clears_failures_but_keeps_last_error() {
+        let mut health = MonitorHealth::default();
+        health.record_failure(transient());
+        health.record_success(Instant::now());
+
+        assert_eq!(health.status(), MonitorStatus::Healthy);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.last_error, Some(transient()));
+    }
+}
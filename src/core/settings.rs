@@ -1,12 +1,22 @@
 use std::time::Duration;
 use cosmic::iced::window;
 use indexmap::IndexMap;
-use crate::configuration::app_configuration::{CPU_SETTINGS_WINDOW_ID, DISK_SETTINGS_WINDOW_ID, GENERAL_SETTINGS_WINDOW_ID, HIDE_INDICATOR_SETTING_KEY, HIDE_LABEL_SETTING_KEY, LABEL_COLOUR_SETTING_KEY, LABEL_TEXT_SETTING_KEY, MAX_SAMPLES_SETTING_KEY, MEMORY_SETTINGS_WINDOW_ID, NETWORK_SETTINGS_WINDOW_ID, UPDATE_INTERVAL_SETTING_KEY, FIX_INDICATOR_SIZE_SETTING_KEY};
-use crate::configuration::cpu::CpuConfiguration;
-use crate::configuration::disk::DiskConfiguration;
+use crate::configuration::app_configuration::{CPU_SETTINGS_WINDOW_ID, DISK_SETTINGS_WINDOW_ID, GENERAL_SETTINGS_WINDOW_ID, HIDE_INDICATOR_SETTING_KEY, HIDE_LABEL_SETTING_KEY, LABEL_COLOUR_SETTING_KEY, LABEL_TEXT_SETTING_KEY, MAX_SAMPLES_SETTING_KEY, MEMORY_SETTINGS_WINDOW_ID, NETWORK_SETTINGS_WINDOW_ID, UPDATE_INTERVAL_SETTING_KEY, FIX_INDICATOR_SIZE_SETTING_KEY, FILE_SETTINGS_WINDOW_ID, FILE_PATH_SETTING_KEY, FILE_PATTERN_SETTING_KEY, FILE_FIELD_INDEX_SETTING_KEY, FILE_SCALE_SETTING_KEY, FILE_UNIT_SETTING_KEY, FILE_RATE_MODE_SETTING_KEY, POWER_SETTINGS_WINDOW_ID, POWER_SHOW_ALL_DOMAINS_SETTING_KEY, PAGING_SETTINGS_WINDOW_ID, PAGING_SHOW_MAJOR_FAULTS_SETTING_KEY, ARC_SETTINGS_WINDOW_ID, ARC_SHOW_TARGET_SETTING_KEY, MEMORY_COUNT_ZFS_ARC_SETTING_KEY, MEMORY_SHOW_COMPRESSION_SETTING_KEY, RAID_SETTINGS_WINDOW_ID, SMOOTHING_SETTING_KEY, SMOOTHING_WINDOW_SETTING_KEY, DISPLAY_MODE_SETTING_KEY, WARNING_THRESHOLD_SETTING_KEY, CRITICAL_THRESHOLD_SETTING_KEY, THRESHOLD_HYSTERESIS_SETTING_KEY, THRESHOLD_COLOUR_LABEL_SETTING_KEY, ALERTS_SETTINGS_WINDOW_ID, ALERTS_ENABLED_SETTING_KEY, ALERT_RULE_SETTING_KEYS, ALERT_COOLDOWN_SETTING_KEY, ALERT_HYSTERESIS_SETTING_KEY, ALERT_HOOK_SETTING_KEYS, ALERT_HOOK_COOLDOWN_SETTING_KEY, MEMORY_VALUE_MODE_SETTING_KEY, FORMAT_TEMPLATE_SETTING_KEY};
+use crate::configuration::alerts::AlertsConfiguration;
+use crate::configuration::cpu::{CpuConfiguration, CPU_TEMPLATE_FIELDS};
+use crate::configuration::disk::{DiskConfiguration, DISK_TEMPLATE_FIELDS};
+use crate::configuration::file::{FileConfiguration, FileValueMode, FILE_TEMPLATE_FIELDS};
+use crate::configuration::format_template::{FormatTemplate, TemplateField};
 use crate::configuration::general::GeneralConfiguration;
-use crate::configuration::memory::MemoryConfiguration;
-use crate::configuration::network::NetworkConfiguration;
+use crate::configuration::memory::{MemoryConfiguration, MemoryValueMode, MEMORY_TEMPLATE_FIELDS};
+use crate::configuration::network::{NetworkConfiguration, NETWORK_TEMPLATE_FIELDS};
+use crate::configuration::paging::{PagingConfiguration, PAGING_TEMPLATE_FIELDS};
+use crate::configuration::arc::{ArcConfiguration, ARC_TEMPLATE_FIELDS};
+use crate::configuration::raid::RaidConfiguration;
+use crate::configuration::power::{PowerConfiguration, POWER_TEMPLATE_FIELDS};
+use crate::configuration::smoothing::SmoothingStrategy;
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::configuration::thresholds::ThresholdConfiguration;
 use crate::configuration::validation::ConfigurationValidation;
 use crate::fl;
 
@@ -21,6 +31,8 @@ pub enum SettingsFormInputType {
     String,
     CheckBox,
     ColourPicker,
+    /// A choice between fixed values, shown with the label at the same index
+    Dropdown { values: Vec<&'static str>, labels: Vec<String> },
 }
 
 #[derive(Debug, Clone)]
@@ -44,9 +56,37 @@ pub struct SettingsForm {
     pub values: IndexMap<&'static str, SettingsFormItem>,
 }
 
+impl SettingsForm {
+    /// Checks a field against the other fields it depends on, which its own validator can't see
+    pub fn validate_against_other_fields(&self, form_value_key: &str) -> Result<(), String> {
+        if let Some(slot) = ALERT_HOOK_SETTING_KEYS.iter().position(|key| *key == form_value_key) {
+            return ConfigurationValidation::is_valid_alert_hook_for_rule(
+                self.value(ALERT_RULE_SETTING_KEYS[slot]),
+                self.value(form_value_key),
+            );
+        }
+
+        match form_value_key {
+            CRITICAL_THRESHOLD_SETTING_KEY => ConfigurationValidation::is_valid_threshold_order(
+                self.threshold_value(WARNING_THRESHOLD_SETTING_KEY),
+                self.threshold_value(CRITICAL_THRESHOLD_SETTING_KEY),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn value(&self, form_value_key: &str) -> &str {
+        self.values.get(form_value_key).map_or("", |item| item.value.as_str())
+    }
+
+    fn threshold_value(&self, form_value_key: &str) -> Option<u64> {
+        ConfigurationValidation::sanitise_threshold(self.value(form_value_key).to_string(), None)
+    }
+}
+
 impl From<&CpuConfiguration> for SettingsForm {
     fn from(config: &CpuConfiguration) -> SettingsForm {
-        let values = build_shared_settings(
+        let mut values = build_shared_settings(
             config.hide_indicator,
             config.hide_label,
             config.update_interval,
@@ -54,6 +94,15 @@ impl From<&CpuConfiguration> for SettingsForm {
             config.label_colour.clone(),
             config.label_text.clone(),
         );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &CPU_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_cpu_format_template,
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-percent-helper"));
 
         SettingsForm {
             settings_window_id: CPU_SETTINGS_WINDOW_ID.clone(),
@@ -65,7 +114,7 @@ impl From<&CpuConfiguration> for SettingsForm {
 
 impl From<&MemoryConfiguration> for SettingsForm {
     fn from(config: &MemoryConfiguration) -> SettingsForm {
-        let values = build_shared_settings(
+        let mut values = build_shared_settings(
             config.hide_indicator,
             config.hide_label,
             config.update_interval,
@@ -73,6 +122,48 @@ impl From<&MemoryConfiguration> for SettingsForm {
             config.label_colour.clone(),
             config.label_text.clone(),
         );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        values.insert(
+            MEMORY_VALUE_MODE_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-memory-value-mode"),
+                value: config.value_mode.key().to_string(),
+                input_type: SettingsFormInputType::Dropdown {
+                    values: MemoryValueMode::ALL.iter().map(|mode| mode.key()).collect(),
+                    labels: MemoryValueMode::ALL.iter().map(|mode| mode.label()).collect(),
+                },
+                validator: Some(ConfigurationValidation::is_valid_memory_value_mode),
+                helper_text: None,
+            },
+        );
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &MEMORY_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_memory_format_template,
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-memory-helper"));
+        values.insert(
+            MEMORY_COUNT_ZFS_ARC_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-memory-count-zfs-arc"),
+                value: config.count_zfs_arc_as_reclaimable.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-memory-count-zfs-arc-helper")),
+            },
+        );
+        values.insert(
+            MEMORY_SHOW_COMPRESSION_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-memory-show-compression"),
+                value: config.show_compression.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-memory-show-compression-helper")),
+            },
+        );
         SettingsForm {
             settings_window_id: MEMORY_SETTINGS_WINDOW_ID.clone(),
             title: fl!("settings-memory-title"),
@@ -83,7 +174,7 @@ impl From<&MemoryConfiguration> for SettingsForm {
 
 impl From<&NetworkConfiguration> for SettingsForm {
     fn from(config: &NetworkConfiguration) -> SettingsForm {
-        let values = build_shared_settings(
+        let mut values = build_shared_settings(
             config.hide_indicator,
             config.hide_label,
             config.update_interval,
@@ -91,6 +182,15 @@ impl From<&NetworkConfiguration> for SettingsForm {
             config.label_colour.clone(),
             config.label_text.clone(),
         );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &NETWORK_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_network_format_template,
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-rate-helper"));
 
         SettingsForm {
             settings_window_id: NETWORK_SETTINGS_WINDOW_ID.clone(),
@@ -102,7 +202,7 @@ impl From<&NetworkConfiguration> for SettingsForm {
 
 impl From<&DiskConfiguration> for SettingsForm {
     fn from(config: &DiskConfiguration) -> SettingsForm {
-        let values = build_shared_settings(
+        let mut values = build_shared_settings(
             config.hide_indicator,
             config.hide_label,
             config.update_interval,
@@ -110,6 +210,15 @@ impl From<&DiskConfiguration> for SettingsForm {
             config.label_colour.clone(),
             config.label_text.clone(),
         );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &DISK_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_disk_format_template,
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-rate-helper"));
         SettingsForm {
             settings_window_id: DISK_SETTINGS_WINDOW_ID.clone(),
             title: fl!("settings-disk-title"),
@@ -118,6 +227,273 @@ impl From<&DiskConfiguration> for SettingsForm {
     }
 }
 
+impl From<&FileConfiguration> for SettingsForm {
+    fn from(config: &FileConfiguration) -> SettingsForm {
+        let mut values = build_shared_settings(
+            config.hide_indicator,
+            config.hide_label,
+            config.update_interval,
+            config.max_samples,
+            config.label_colour.clone(),
+            config.label_text.clone(),
+        );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &FILE_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_file_format_template,
+        );
+        values.insert(
+            FILE_PATH_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-path"),
+                value: config.file_path.clone(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_file_path),
+                helper_text: Some(fl!("settings-file-path-helper")),
+            },
+        );
+        values.insert(
+            FILE_PATTERN_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-pattern"),
+                value: config.pattern.clone().unwrap_or_default(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_file_pattern),
+                helper_text: Some(fl!("settings-file-pattern-helper")),
+            },
+        );
+        values.insert(
+            FILE_FIELD_INDEX_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-field-index"),
+                value: config.field_index.map(|index| index.to_string()).unwrap_or_default(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_field_index),
+                helper_text: Some(fl!("settings-file-field-index-helper")),
+            },
+        );
+        values.insert(
+            FILE_SCALE_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-scale"),
+                value: config.scale.to_string(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_scale),
+                helper_text: Some(fl!("settings-file-scale-helper")),
+            },
+        );
+        values.insert(
+            FILE_UNIT_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-unit"),
+                value: config.unit.clone(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_unit),
+                helper_text: None,
+            },
+        );
+        values.insert(
+            FILE_RATE_MODE_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-file-rate-mode"),
+                value: (config.mode == FileValueMode::Rate).to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-file-rate-mode-helper")),
+            },
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-file-helper"));
+
+        SettingsForm {
+            settings_window_id: FILE_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-file-title"),
+            values,
+        }
+    }
+}
+
+impl From<&PowerConfiguration> for SettingsForm {
+    fn from(config: &PowerConfiguration) -> SettingsForm {
+        let mut values = build_shared_settings(
+            config.hide_indicator,
+            config.hide_label,
+            config.update_interval,
+            config.max_samples,
+            config.label_colour.clone(),
+            config.label_text.clone(),
+        );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &POWER_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_power_format_template,
+        );
+        values.insert(
+            POWER_SHOW_ALL_DOMAINS_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-power-show-all-domains"),
+                value: config.show_all_domains.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-power-show-all-domains-helper")),
+            },
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-power-helper"));
+
+        SettingsForm {
+            settings_window_id: POWER_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-power-title"),
+            values,
+        }
+    }
+}
+
+impl From<&PagingConfiguration> for SettingsForm {
+    fn from(config: &PagingConfiguration) -> SettingsForm {
+        let mut values = build_shared_settings(
+            config.hide_indicator,
+            config.hide_label,
+            config.update_interval,
+            config.max_samples,
+            config.label_colour.clone(),
+            config.label_text.clone(),
+        );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &PAGING_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_paging_format_template,
+        );
+        values.insert(
+            PAGING_SHOW_MAJOR_FAULTS_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-paging-show-major-faults"),
+                value: config.show_major_faults.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-paging-show-major-faults-helper")),
+            },
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-rate-helper"));
+
+        SettingsForm {
+            settings_window_id: PAGING_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-paging-title"),
+            values,
+        }
+    }
+}
+
+impl From<&ArcConfiguration> for SettingsForm {
+    fn from(config: &ArcConfiguration) -> SettingsForm {
+        let mut values = build_shared_settings(
+            config.hide_indicator,
+            config.hide_label,
+            config.update_interval,
+            config.max_samples,
+            config.label_colour.clone(),
+            config.label_text.clone(),
+        );
+        insert_smoothing_settings(&mut values, config.smoothing, config.smoothing_window);
+        insert_display_mode_settings(&mut values, config.display_mode);
+        insert_format_template_settings(
+            &mut values,
+            config.format_template.as_ref(),
+            &ARC_TEMPLATE_FIELDS,
+            ConfigurationValidation::is_valid_arc_format_template,
+        );
+        values.insert(
+            ARC_SHOW_TARGET_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-arc-show-target"),
+                value: config.show_target.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-arc-show-target-helper")),
+            },
+        );
+        insert_threshold_settings(&mut values, config.thresholds, fl!("settings-threshold-arc-helper"));
+
+        SettingsForm {
+            settings_window_id: ARC_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-arc-title"),
+            values,
+        }
+    }
+}
+
+impl From<&RaidConfiguration> for SettingsForm {
+    fn from(config: &RaidConfiguration) -> SettingsForm {
+        // The RAID monitor always reports the latest state, so unlike the shared settings there are
+        // no samples to set
+        let values = IndexMap::from([
+            (
+                HIDE_INDICATOR_SETTING_KEY,
+                SettingsFormItem {
+                    label: fl!("settings-hide-indicator"),
+                    value: config.hide_indicator.to_string(),
+                    input_type: SettingsFormInputType::CheckBox,
+                    validator: Some(ConfigurationValidation::is_valid_boolean),
+                    helper_text: None,
+                },
+            ),
+            (
+                HIDE_LABEL_SETTING_KEY,
+                SettingsFormItem {
+                    label: fl!("settings-hide-label"),
+                    value: config.hide_label.to_string(),
+                    input_type: SettingsFormInputType::CheckBox,
+                    validator: Some(ConfigurationValidation::is_valid_boolean),
+                    helper_text: None,
+                },
+            ),
+            (
+                LABEL_TEXT_SETTING_KEY,
+                SettingsFormItem {
+                    label: fl!("settings-label-text"),
+                    value: config.label_text.clone().unwrap_or_default(),
+                    input_type: SettingsFormInputType::String,
+                    validator: Some(ConfigurationValidation::is_valid_label_text),
+                    helper_text: None,
+                },
+            ),
+            (
+                LABEL_COLOUR_SETTING_KEY,
+                SettingsFormItem {
+                    label: fl!("settings-label-colour"),
+                    value: config.label_colour.clone().unwrap_or_default(),
+                    input_type: SettingsFormInputType::ColourPicker,
+                    validator: None,
+                    helper_text: None,
+                },
+            ),
+            (
+                UPDATE_INTERVAL_SETTING_KEY,
+                SettingsFormItem {
+                    label: fl!("settings-update-interval"),
+                    value: config.update_interval.as_millis().to_string(),
+                    input_type: SettingsFormInputType::String,
+                    validator: Some(ConfigurationValidation::is_valid_interval),
+                    helper_text: None,
+                },
+            ),
+        ]);
+
+        SettingsForm {
+            settings_window_id: RAID_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-raid-title"),
+            values,
+        }
+    }
+}
+
 impl From<&GeneralConfiguration> for SettingsForm {
     fn from(config: &GeneralConfiguration) -> SettingsForm {
         let mut values: IndexMap<&'static str, SettingsFormItem> = IndexMap::new();
@@ -141,6 +517,16 @@ impl From<&GeneralConfiguration> for SettingsForm {
                 helper_text: Some(fl!("settings-use-iec-units-helper")),
             },
         );
+        values.insert(
+            crate::configuration::app_configuration::SENSOR_ROOT_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-sensor-root"),
+                value: config.sensor_root.clone(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_sensor_root),
+                helper_text: Some(fl!("settings-sensor-root-helper")),
+            },
+        );
 
         SettingsForm {
             settings_window_id: GENERAL_SETTINGS_WINDOW_ID.clone(),
@@ -150,6 +536,93 @@ impl From<&GeneralConfiguration> for SettingsForm {
     }
 }
 
+impl From<&AlertsConfiguration> for SettingsForm {
+    fn from(config: &AlertsConfiguration) -> SettingsForm {
+        let mut values: IndexMap<&'static str, SettingsFormItem> = IndexMap::new();
+        values.insert(
+            ALERTS_ENABLED_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-alerts-enabled"),
+                value: config.enabled.to_string(),
+                input_type: SettingsFormInputType::CheckBox,
+                validator: Some(ConfigurationValidation::is_valid_boolean),
+                helper_text: Some(fl!("settings-alerts-enabled-helper")),
+            },
+        );
+
+        for (slot, key) in ALERT_RULE_SETTING_KEYS.iter().enumerate() {
+            values.insert(
+                key,
+                SettingsFormItem {
+                    label: fl!("settings-alert-rule", number = slot + 1),
+                    value: config
+                        .rules
+                        .get(slot)
+                        .and_then(Option::as_ref)
+                        .map(|rule| rule.to_string())
+                        .unwrap_or_default(),
+                    input_type: SettingsFormInputType::String,
+                    validator: Some(ConfigurationValidation::is_valid_alert_rule),
+                    // The syntax is only explained once, under the first rule
+                    helper_text: if slot == 0 { Some(fl!("settings-alert-rule-helper")) } else { None },
+                },
+            );
+            values.insert(
+                ALERT_HOOK_SETTING_KEYS[slot],
+                SettingsFormItem {
+                    label: fl!("settings-alert-hook", number = slot + 1),
+                    value: config
+                        .rules
+                        .get(slot)
+                        .and_then(Option::as_ref)
+                        .and_then(|rule| rule.hook.clone())
+                        .unwrap_or_default(),
+                    input_type: SettingsFormInputType::String,
+                    validator: None,
+                    helper_text: if slot == 0 { Some(fl!("settings-alert-hook-helper")) } else { None },
+                },
+            );
+        }
+
+        values.insert(
+            ALERT_COOLDOWN_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-alert-cooldown"),
+                value: config.cooldown.as_secs().to_string(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_alert_cooldown),
+                helper_text: Some(fl!("settings-alert-cooldown-helper")),
+            },
+        );
+        values.insert(
+            ALERT_HYSTERESIS_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-alert-hysteresis"),
+                value: config.hysteresis_percent.to_string(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_hysteresis),
+                helper_text: Some(fl!("settings-alert-hysteresis-helper")),
+            },
+        );
+        values.insert(
+            ALERT_HOOK_COOLDOWN_SETTING_KEY,
+            SettingsFormItem {
+                label: fl!("settings-alert-hook-cooldown"),
+                value: config.hook_cooldown.as_secs().to_string(),
+                input_type: SettingsFormInputType::String,
+                validator: Some(ConfigurationValidation::is_valid_alert_cooldown),
+                helper_text: Some(fl!("settings-alert-hook-cooldown-helper")),
+            },
+        );
+
+        SettingsForm {
+            settings_window_id: ALERTS_SETTINGS_WINDOW_ID.clone(),
+            title: fl!("settings-alerts-title"),
+            values,
+        }
+    }
+}
+
 // This is used to build the shared settings between all the settings forms.
 /// Extend the BTreeMap returned by this with any sensor-specific settings if required.
 fn build_shared_settings(
@@ -223,3 +696,118 @@ fn build_shared_settings(
         ),
     ])
 }
+
+/// Adds the smoothing strategy and window to the settings of a monitor that smooths its samples
+fn insert_smoothing_settings(
+    values: &mut IndexMap<&'static str, SettingsFormItem>,
+    smoothing: SmoothingStrategy,
+    smoothing_window: Option<Duration>,
+) {
+    values.insert(
+        SMOOTHING_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-smoothing"),
+            value: smoothing.key().to_string(),
+            input_type: SettingsFormInputType::Dropdown {
+                values: SmoothingStrategy::ALL.iter().map(|strategy| strategy.key()).collect(),
+                labels: SmoothingStrategy::ALL.iter().map(|strategy| strategy.label()).collect(),
+            },
+            validator: Some(ConfigurationValidation::is_valid_smoothing_strategy),
+            helper_text: None,
+        },
+    );
+    values.insert(
+        SMOOTHING_WINDOW_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-smoothing-window"),
+            value: smoothing_window.map(|window| window.as_secs_f64().to_string()).unwrap_or_default(),
+            input_type: SettingsFormInputType::String,
+            validator: Some(ConfigurationValidation::is_valid_smoothing_window),
+            helper_text: Some(fl!("settings-smoothing-window-helper")),
+        },
+    );
+}
+
+fn insert_display_mode_settings(values: &mut IndexMap<&'static str, SettingsFormItem>, display_mode: IndicatorDisplayMode) {
+    values.insert(
+        DISPLAY_MODE_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-display-mode"),
+            value: display_mode.key().to_string(),
+            input_type: SettingsFormInputType::Dropdown {
+                values: IndicatorDisplayMode::ALL.iter().map(|mode| mode.key()).collect(),
+                labels: IndicatorDisplayMode::ALL.iter().map(|mode| mode.label()).collect(),
+            },
+            validator: Some(ConfigurationValidation::is_valid_display_mode),
+            helper_text: None,
+        },
+    );
+}
+
+/// Adds the format template to the settings of a monitor, with its fields listed in the helper text
+fn insert_format_template_settings(
+    values: &mut IndexMap<&'static str, SettingsFormItem>,
+    format_template: Option<&FormatTemplate>,
+    fields: &[TemplateField],
+    validator: fn(&str) -> Result<(), String>,
+) {
+    values.insert(
+        FORMAT_TEMPLATE_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-format-template"),
+            value: format_template.map(|template| template.to_string()).unwrap_or_default(),
+            input_type: SettingsFormInputType::String,
+            validator: Some(validator),
+            helper_text: Some(fl!("settings-format-template-helper", fields = TemplateField::list(fields))),
+        },
+    );
+}
+
+/// Adds the warning and critical thresholds to the settings of a monitor that colours its value.
+/// The helper text explains the unit the thresholds are in.
+fn insert_threshold_settings(
+    values: &mut IndexMap<&'static str, SettingsFormItem>,
+    thresholds: ThresholdConfiguration,
+    unit_helper_text: String,
+) {
+    values.insert(
+        WARNING_THRESHOLD_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-warning-threshold"),
+            value: thresholds.warning.map(|value| value.to_string()).unwrap_or_default(),
+            input_type: SettingsFormInputType::String,
+            validator: Some(ConfigurationValidation::is_valid_threshold),
+            helper_text: Some(unit_helper_text),
+        },
+    );
+    values.insert(
+        CRITICAL_THRESHOLD_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-critical-threshold"),
+            value: thresholds.critical.map(|value| value.to_string()).unwrap_or_default(),
+            input_type: SettingsFormInputType::String,
+            validator: Some(ConfigurationValidation::is_valid_threshold),
+            helper_text: None,
+        },
+    );
+    values.insert(
+        THRESHOLD_HYSTERESIS_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-threshold-hysteresis"),
+            value: thresholds.hysteresis.to_string(),
+            input_type: SettingsFormInputType::String,
+            validator: Some(ConfigurationValidation::is_valid_hysteresis),
+            helper_text: Some(fl!("settings-threshold-hysteresis-helper")),
+        },
+    );
+    values.insert(
+        THRESHOLD_COLOUR_LABEL_SETTING_KEY,
+        SettingsFormItem {
+            label: fl!("settings-threshold-colour-label"),
+            value: thresholds.colour_label.to_string(),
+            input_type: SettingsFormInputType::CheckBox,
+            validator: Some(ConfigurationValidation::is_valid_boolean),
+            helper_text: None,
+        },
+    );
+}
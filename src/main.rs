@@ -10,6 +10,7 @@ mod ui;
 mod sensors;
 mod monitors;
 mod configuration;
+mod alerts;
 
 fn main() -> cosmic::iced::Result {
     JournalLog::new().unwrap().install().unwrap();
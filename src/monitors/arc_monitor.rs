@@ -0,0 +1,286 @@
+use crate::configuration::app_configuration::{AppConfiguration, ARC_SETTINGS_WINDOW_ID};
+use crate::configuration::arc::ArcConfiguration;
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::proc_arcstats_reader::{ProcArcStatsReader, ProcArcStatsStatus};
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use cosmic::iced::window;
+use log::info;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The metric history names of the ARC size, in bytes, and hit ratio percentage
+pub const ARC_SIZE_METRIC: &str = "arc-size-bytes";
+pub const ARC_TARGET_METRIC: &str = "arc-target-bytes";
+pub const ARC_HIT_RATIO_METRIC: &str = "arc-hit-ratio-percent";
+
+#[derive(Debug, Clone, Default)]
+pub struct ArcStats {
+    pub size_bytes: u64,
+    pub target_bytes: u64,
+    /// The percentage of ARC reads that were hits over the sample window
+    pub hit_ratio: f64,
+    /// Which of the configured thresholds the ARC size has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl ArcStats {
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![
+            MetricValue::new(ARC_SIZE_METRIC, self.size_bytes as f64),
+            MetricValue::new(ARC_TARGET_METRIC, self.target_bytes as f64),
+            MetricValue::new(ARC_HIT_RATIO_METRIC, self.hit_ratio),
+        ]
+    }
+}
+
+pub struct ArcMonitor<S: SensorReader<Output = ProcArcStatsStatus>> {
+    sensor_reader: S,
+    /// Smooths the hits and misses since the previous poll
+    smoother: Smoother<2>,
+    previous: Option<ProcArcStatsStatus>,
+    thresholds: ThresholdTracker,
+}
+
+impl<S: SensorReader<Output = ProcArcStatsStatus>> ArcMonitor<S> {
+    pub fn new(sensor_reader: S, configuration: &ArcConfiguration) -> Self {
+        info!("Creating new ZFS ARC monitor {:?}", configuration);
+        Self {
+            sensor_reader,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous: None,
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+        }
+    }
+
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<ArcStats, SensorError> {
+        let current = match self.sensor_reader.read() {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        };
+
+        // If no previous reading, use the current values to prevent big deltas
+        let previous = self
+            .previous
+            .replace(current.clone())
+            .unwrap_or_else(|| current.clone());
+
+        let [window_hits, window_misses] = self.smoother.push(
+            Instant::now(),
+            [
+                current.hits.saturating_sub(previous.hits) as f64,
+                current.misses.saturating_sub(previous.misses) as f64,
+            ],
+        );
+
+        // An idle pool has no reads in the window, so fall back to the ratio since boot.
+        let hit_ratio = if window_hits + window_misses > 0.0 {
+            hit_ratio(window_hits, window_misses)
+        } else {
+            hit_ratio(current.hits as f64, current.misses as f64)
+        };
+
+        Ok(ArcStats {
+            size_bytes: current.size_bytes,
+            target_bytes: current.target_bytes,
+            hit_ratio,
+            // The thresholds are in MiB
+            threshold_level: self.thresholds.update(current.size_bytes as f64 / 1024.0 / 1024.0),
+        })
+    }
+}
+
+fn hit_ratio(hits: f64, misses: f64) -> f64 {
+    let total = hits + misses;
+
+    if total <= 0.0 {
+        0.0
+    } else {
+        hits / total * 100.0
+    }
+}
+
+/// The ZFS ARC monitor
+pub struct ArcMonitorDefinition;
+
+impl MonitorDefinition for ArcMonitorDefinition {
+    fn id(&self) -> window::Id {
+        ARC_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(ArcStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.arc)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.arc = configuration.arc.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.arc.update_interval
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.arc.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(ArcMonitor::new(
+            ProcArcStatsReader::new(sensor_root.clone()),
+            &configuration.arc,
+        )))
+    }
+}
+
+impl<S: SensorReader<Output = ProcArcStatsStatus> + Send> Monitor for ArcMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(ArcMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        ArcMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::thresholds::ThresholdConfiguration;
+    use std::cell::Cell;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    struct MockProcArcStatsReader {
+        readings: Vec<Result<ProcArcStatsStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockProcArcStatsReader {
+        fn new(readings: Vec<Result<ProcArcStatsStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockProcArcStatsReader {
+        type Output = ProcArcStatsStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn make_config(max_samples: usize) -> ArcConfiguration {
+        let mut cfg = ArcConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg
+    }
+
+    #[test]
+    fn first_poll_gives_ratio_since_boot() {
+        let reader = MockProcArcStatsReader::new(vec![Ok(ProcArcStatsStatus::new(8 * GIB, 12 * GIB, GIB, 900, 100))]);
+        let mut monitor = ArcMonitor::new(reader, &make_config(2));
+
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(result.size_bytes, 8 * GIB);
+        assert_eq!(result.target_bytes, 12 * GIB);
+        assert_eq!(result.hit_ratio, 90.0);
+    }
+
+    #[test]
+    fn hit_ratio_uses_window_deltas() {
+        let reader = MockProcArcStatsReader::new(vec![
+            Ok(ProcArcStatsStatus::new(8 * GIB, 12 * GIB, GIB, 900, 100)),
+            Ok(ProcArcStatsStatus::new(8 * GIB, 12 * GIB, GIB, 950, 150)),
+        ]);
+        let mut monitor = ArcMonitor::new(reader, &make_config(1));
+
+        _ = monitor.poll();
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(result.hit_ratio, 50.0);
+    }
+
+    #[test]
+    fn thresholds_are_in_mib_of_the_arc_size() {
+        let reader = MockProcArcStatsReader::new(vec![
+            Ok(ProcArcStatsStatus::new(2 * GIB, 12 * GIB, GIB, 0, 0)),
+            Ok(ProcArcStatsStatus::new(8 * GIB, 12 * GIB, GIB, 0, 0)),
+        ]);
+        let mut config = make_config(1);
+        config.thresholds = ThresholdConfiguration::new(1024, 4096, 0);
+        let mut monitor = ArcMonitor::new(reader, &config);
+
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Warning);
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Critical);
+    }
+
+    #[test]
+    fn hit_ratio_is_zero_without_reads() {
+        let reader = MockProcArcStatsReader::new(vec![Ok(ProcArcStatsStatus::new(0, 0, 0, 0, 0))]);
+        let mut monitor = ArcMonitor::new(reader, &make_config(1));
+
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(result.hit_ratio, 0.0);
+    }
+
+    #[test]
+    fn samples_buffer_trims_to_max_size() {
+        let reader = MockProcArcStatsReader::new(vec![
+            Ok(ProcArcStatsStatus::new(GIB, GIB, 0, 0, 0)),
+            Ok(ProcArcStatsStatus::new(GIB, GIB, 0, 10, 0)),
+            Ok(ProcArcStatsStatus::new(GIB, GIB, 0, 10, 10)),
+        ]);
+        let mut monitor = ArcMonitor::new(reader, &make_config(2));
+
+        _ = monitor.poll();
+        _ = monitor.poll();
+        let result = monitor.poll().unwrap();
+
+        assert!(monitor.smoother.len() == 2);
+        assert_eq!(result.hit_ratio, 50.0);
+    }
+
+    #[test]
+    fn error_is_propagated() {
+        let reader = MockProcArcStatsReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let mut monitor = ArcMonitor::new(reader, &make_config(1));
+
+        let err = monitor.poll().unwrap_err();
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+}
@@ -1,17 +1,62 @@
-use crate::sensors::proc_stat_reader::ProcStatStatus;
+use crate::sensors::proc_stat_reader::{ProcStatCoreStatus, ProcStatStatus};
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
-use std::collections::VecDeque;
-use log::info;
-use crate::configuration::app_configuration::AppConfiguration;
+use log::{info, warn};
+use crate::alerts::alert_evaluator::AlertValue;
+use crate::configuration::alert_rule::AlertMetric;
+use crate::configuration::cpu::CpuConfiguration;
+use crate::configuration::app_configuration::{AppConfiguration, CPU_DETAIL_WINDOW_ID, CPU_SETTINGS_WINDOW_ID};
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{DetailVisibility, Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::proc_pid_stat_reader::{ProcPidStatReader, ProcPidStatStatus, ProcPidStatus};
+use crate::sensors::proc_stat_reader::ProcStatSensorReader;
+use crate::sensors::sensor_root::SensorRoot;
+use cosmic::iced::window;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The metric history name of the CPU usage percentage
+pub const CPU_USAGE_METRIC: &str = "cpu-usage-percent";
+/// How many of the busiest processes are kept for the detail popup
+pub const TOP_PROCESS_COUNT: usize = 5;
+
+/// The share of all the CPU time a process used since the previous poll
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpuProcessStats {
+    pub pid: u32,
+    pub name: String,
+    pub usage_percent: f64,
+}
 
 #[derive(Default, Clone, Debug)]
 pub struct CpuStats {
     pub cpu_usage_percent: f64,
+    /// The unsmoothed usage of each core since the previous poll, in core order
+    pub core_usage_percent: Vec<f64>,
+    /// The busiest processes since the previous poll, busiest first
+    pub top_processes: Vec<CpuProcessStats>,
+    /// Which of the configured thresholds the smoothed usage has crossed
+    pub threshold_level: ThresholdLevel,
 }
 
 impl CpuStats {
     pub fn new(cpu_usage_percent: f64) -> Self {
-        Self { cpu_usage_percent }
+        Self {
+            cpu_usage_percent,
+            ..Default::default()
+        }
+    }
+
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![MetricValue::new(CPU_USAGE_METRIC, self.cpu_usage_percent)]
+    }
+
+    pub fn alert_values(&self) -> Vec<AlertValue> {
+        vec![AlertValue::new(AlertMetric::CpuUsage, self.cpu_usage_percent)]
     }
 }
 
@@ -19,63 +64,253 @@ pub struct CpuMonitor<S: SensorReader<Output = ProcStatStatus>> {
     sensor_reader: S,
     previous_idle: u64,
     previous_total: u64,
-    sample_buffer: VecDeque<f64>,
-    max_samples: usize,
+    previous_cores: Vec<ProcStatCoreStatus>,
+    /// Reads the CPU time of every process for the top processes, if they are shown
+    process_reader: Option<Box<dyn SensorReader<Output = ProcPidStatStatus> + Send>>,
+    /// Whether the detail popup showing the top processes is open, as scanning every process is
+    /// too costly to do on every poll while nothing shows them
+    detail_visibility: DetailVisibility,
+    /// The CPU time of each process and the total time at the previous scan, or None if the
+    /// processes weren't scanned on the previous poll
+    previous_processes: Option<(HashMap<u32, u64>, u64)>,
+    smoother: Smoother<1>,
+    thresholds: ThresholdTracker,
 }
 
 impl<S: SensorReader<Output = ProcStatStatus>> CpuMonitor<S> {
-    pub fn new(sensor_reader: S, configuration: &AppConfiguration) -> Self {
-        info!("Creating new cpu monitor {:?}", configuration.cpu);
+    pub fn new(sensor_reader: S, configuration: &CpuConfiguration) -> Self {
+        info!("Creating new cpu monitor {:?}", configuration);
         Self {
             sensor_reader,
             previous_idle: 0,
             previous_total: 0,
-            sample_buffer: VecDeque::with_capacity(configuration.cpu.max_samples),
-            max_samples: configuration.cpu.max_samples
+            previous_cores: Vec::new(),
+            process_reader: None,
+            detail_visibility: DetailVisibility::default(),
+            previous_processes: None,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
         }
     }
+
+    /// Adds the reader of the processes shown as the top processes while the details are open
+    pub fn with_process_reader(
+        mut self,
+        reader: impl SensorReader<Output = ProcPidStatStatus> + Send + 'static,
+    ) -> Self {
+        self.process_reader = Some(Box::new(reader));
+        self
+    }
     
-    pub fn poll(&mut self) -> Result<CpuStats, String> {
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous_idle = 0;
+        self.previous_total = 0;
+        self.previous_cores.clear();
+        self.previous_processes = None;
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<CpuStats, SensorError> {
         let current = match self.sensor_reader.read() {
             Ok(cpu_stats) => cpu_stats,
             Err(err) => return Err(err),
         };
 
         let total_idle = current.idle + current.iowait;
+        let core_usage_percent = self.core_usage_percent(&current.cores);
+        let top_processes = self.top_processes(current.total);
 
         let current_usage_percent: f64 = 100.0
             * (1.0 - (total_idle - self.previous_idle) as f64 / (current.total - self.previous_total)
             as f64);
 
-        self.sample_buffer.push_back(current_usage_percent);
-
         self.previous_idle = total_idle;
         self.previous_total = current.total;
 
-        if self.sample_buffer.len() > self.max_samples {
-            self.sample_buffer.pop_front();
+        let [smoothed_cpu_usage] = self.smoother.push(Instant::now(), [current_usage_percent]);
+
+        Ok(CpuStats {
+            cpu_usage_percent: smoothed_cpu_usage,
+            core_usage_percent,
+            top_processes,
+            threshold_level: self.thresholds.update(smoothed_cpu_usage),
+        })
+    }
+
+    /// The usage of each core since the previous poll. A core without a previous reading, i.e. on
+    /// the first poll, is measured since boot like the total usage is.
+    fn core_usage_percent(&mut self, cores: &[ProcStatCoreStatus]) -> Vec<f64> {
+        let usage = cores
+            .iter()
+            .enumerate()
+            .map(|(index, core)| {
+                let (previous_idle, previous_total) = match self.previous_cores.get(index) {
+                    Some(previous) => (previous.idle + previous.iowait, previous.total),
+                    None => (0, 0),
+                };
+                let elapsed_total = core.total.saturating_sub(previous_total);
+                let elapsed_idle = (core.idle + core.iowait).saturating_sub(previous_idle);
+
+                if elapsed_total == 0 {
+                    return 0.0;
+                }
+
+                100.0 * (1.0 - elapsed_idle as f64 / elapsed_total as f64)
+            })
+            .collect();
+
+        self.previous_cores = cores.to_vec();
+
+        usage
+    }
+
+    /// The CPU time of every process, or None while the details are closed or there is no
+    /// process reader. A process list that can't be read is logged and treated as empty, so the
+    /// usage of the cores is still shown.
+    fn read_processes(&self) -> Option<Vec<ProcPidStatus>> {
+        if !self.detail_visibility.is_visible() {
+            return None;
+        }
+
+        let reader = self.process_reader.as_ref()?;
+
+        match reader.read() {
+            Ok(status) => Some(status.processes),
+            Err(err) => {
+                warn!("Unable to scan the processes for the CPU details: {}", err);
+                Some(Vec::new())
+            }
+        }
+    }
+
+    /// The processes that used the most CPU time since the previous scan, as a share of the time
+    /// of all the cores. The first scan after the details open only records the baseline, as the
+    /// time a process used since it started says little about what is busy now.
+    fn top_processes(&mut self, current_total: u64) -> Vec<CpuProcessStats> {
+        let Some(current) = self.read_processes() else {
+            self.previous_processes = None;
+            return Vec::new();
+        };
+
+        let mut processes: Vec<CpuProcessStats> = Vec::new();
+
+        if let Some((previous_ticks, previous_total)) = &self.previous_processes {
+            let elapsed_total = current_total.saturating_sub(*previous_total);
+
+            if elapsed_total > 0 {
+                processes = current
+                    .iter()
+                    .filter_map(|process| {
+                        // A process that started since the previous scan used all of its time since then
+                        let previous_ticks = previous_ticks.get(&process.pid).copied().unwrap_or_default();
+                        let elapsed_ticks = process.cpu_ticks.saturating_sub(previous_ticks);
+
+                        (elapsed_ticks > 0).then(|| CpuProcessStats {
+                            pid: process.pid,
+                            name: process.name.clone(),
+                            usage_percent: 100.0 * elapsed_ticks as f64 / elapsed_total as f64,
+                        })
+                    })
+                    .collect();
+
+                processes.sort_by(|a, b| b.usage_percent.total_cmp(&a.usage_percent).then(a.pid.cmp(&b.pid)));
+                processes.truncate(TOP_PROCESS_COUNT);
+            }
         }
 
-        let average_cpu_usage = self.sample_buffer.iter().sum::<f64>() / self.sample_buffer.len() as f64;
+        let ticks = current.iter().map(|process| (process.pid, process.cpu_ticks)).collect();
+        self.previous_processes = Some((ticks, current_total));
+
+        processes
+    }
+}
+
+/// The cpu usage monitor
+pub struct CpuMonitorDefinition;
+
+impl MonitorDefinition for CpuMonitorDefinition {
+    fn id(&self) -> window::Id {
+        CPU_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(CpuStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.cpu)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.cpu = configuration.cpu.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.cpu.update_interval
+    }
+
+    fn detail_window_id(&self) -> Option<window::Id> {
+        Some(CPU_DETAIL_WINDOW_ID.clone())
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.cpu.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(
+            CpuMonitor::new(ProcStatSensorReader::new(sensor_root.clone()), &configuration.cpu)
+                .with_process_reader(ProcPidStatReader::new(sensor_root.clone())),
+        ))
+    }
+}
+
+impl<S: SensorReader<Output = ProcStatStatus> + Send> Monitor for CpuMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(CpuMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        CpuMonitor::reset(self);
+    }
+
+    fn set_detail_visibility(&mut self, visibility: DetailVisibility) {
+        self.detail_visibility = visibility;
+    }
 
-        Ok(CpuStats::new(average_cpu_usage))
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::configuration::app_configuration::{
+        CRITICAL_THRESHOLD_SETTING_KEY, SMOOTHING_SETTING_KEY, SMOOTHING_WINDOW_SETTING_KEY, WARNING_THRESHOLD_SETTING_KEY,
+    };
+    use crate::configuration::smoothing::SmoothingStrategy;
     use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     struct MockProcStatReader {
         // Fix set of readings to return from the reader.
-        readings: Vec<Result<ProcStatStatus, String>>,
+        readings: Vec<Result<ProcStatStatus, SensorError>>,
         // Tracks the index of the next reading to return.
         index: Cell<usize>,
     }
 
     impl MockProcStatReader {
-        fn new(readings: Vec<Result<ProcStatStatus, String>>) -> Self {
+        fn new(readings: Vec<Result<ProcStatStatus, SensorError>>) -> Self {
             Self {
                 readings,
                 index: Cell::new(0),
@@ -86,7 +321,7 @@ mod tests {
     impl SensorReader for MockProcStatReader {
         type Output = ProcStatStatus;
 
-        fn read(&self) -> Result<Self::Output, String> {
+        fn read(&self) -> Result<Self::Output, SensorError> {
             let read_index = self.index.get();
             let reading = self.readings[read_index].clone();
 
@@ -95,9 +330,9 @@ mod tests {
         }
     }
 
-    fn make_config(max_samples: usize) -> AppConfiguration {
-        let mut cfg = AppConfiguration::default();
-        cfg.cpu.max_samples = max_samples;
+    fn make_config(max_samples: usize) -> CpuConfiguration {
+        let mut cfg = CpuConfiguration::default();
+        cfg.max_samples = max_samples;
         cfg
     }
 
@@ -139,19 +374,205 @@ mod tests {
         _ = monitor.poll();
         _ = monitor.poll();
         
-        assert!(monitor.sample_buffer.len() == 2);
+        assert!(monitor.smoother.len() == 2);
     }
 
     #[test]
     fn error_is_propagated() {
-        let reader = MockProcStatReader::new(vec![Err("boom".to_string())]);
+        let reader = MockProcStatReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
         let mut monitor = CpuMonitor::new(reader, &make_config(1));
 
         let err = monitor.poll().unwrap_err();
-        assert_eq!(err, "boom");
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+
+    struct MockProcessReader {
+        // Fix set of process lists to return from the reader.
+        readings: Vec<Vec<(u32, &'static str, u64)>>,
+        // Tracks the index of the next reading to return, shared so tests can count the scans.
+        index: Arc<AtomicUsize>,
+    }
+
+    impl SensorReader for MockProcessReader {
+        type Output = ProcPidStatStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let read_index = self.index.fetch_add(1, Ordering::Relaxed);
+            let processes = self.readings[read_index]
+                .iter()
+                .map(|&(pid, name, cpu_ticks)| ProcPidStatus {
+                    pid,
+                    name: name.to_string(),
+                    cpu_ticks,
+                })
+                .collect();
+
+            Ok(ProcPidStatStatus { processes })
+        }
+    }
+
+    fn with_cores(total: u64, cores: Vec<ProcStatCoreStatus>) -> ProcStatStatus {
+        let mut status = ProcStatStatus::new(0, 0, total);
+        status.cores = cores;
+        status
+    }
+
+    /// A monitor of the given totals and process lists, with its details open
+    fn process_monitor(
+        totals: Vec<u64>,
+        processes: Vec<Vec<(u32, &'static str, u64)>>,
+    ) -> (CpuMonitor<MockProcStatReader>, DetailVisibility, Arc<AtomicUsize>) {
+        let readings = totals.into_iter().map(|total| Ok(with_cores(total, vec![]))).collect();
+        let scans = Arc::new(AtomicUsize::new(0));
+        let visibility = DetailVisibility::default();
+        visibility.set_visible(true);
+
+        let mut monitor = CpuMonitor::new(MockProcStatReader::new(readings), &make_config(4)).with_process_reader(
+            MockProcessReader {
+                readings: processes,
+                index: scans.clone(),
+            },
+        );
+        Monitor::set_detail_visibility(&mut monitor, visibility.clone());
+
+        (monitor, visibility, scans)
+    }
+
+    #[test]
+    fn core_usage_is_measured_since_the_previous_poll() {
+        let core = |idle, iowait, total| ProcStatCoreStatus { idle, iowait, total };
+        let reader = MockProcStatReader::new(vec![
+            Ok(with_cores(400, vec![core(150, 50, 200), core(100, 0, 200)])),
+            Ok(with_cores(600, vec![core(250, 50, 300), core(125, 0, 300)])),
+        ]);
+        let mut monitor = CpuMonitor::new(reader, &make_config(4));
+
+        let first = monitor.poll().unwrap();
+        assert_eq!(first.core_usage_percent, vec![0.0, 50.0]);
+
+        let second = monitor.poll().unwrap();
+        assert_eq!(second.core_usage_percent, vec![0.0, 75.0]);
+    }
+
+    #[test]
+    fn top_processes_are_the_busiest_since_the_previous_poll() {
+        let (mut monitor, _, _) = process_monitor(
+            vec![1000, 1200],
+            vec![
+                vec![(1, "systemd", 500), (2, "firefox", 100)],
+                vec![
+                    (1, "systemd", 510),
+                    (2, "firefox", 150),
+                    (3, "cargo", 20),
+                    (4, "idle", 0),
+                ],
+            ],
+        );
+
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
+
+        let stats = monitor.poll().unwrap();
+        let top: Vec<(u32, f64)> = stats
+            .top_processes
+            .iter()
+            .map(|process| (process.pid, process.usage_percent))
+            .collect();
+
+        assert_eq!(top, vec![(2, 25.0), (3, 10.0), (1, 5.0)]);
+    }
+
+    #[test]
+    fn top_processes_are_limited() {
+        let processes = |ticks: u64| (1..=8).map(|pid| (pid, "worker", ticks * pid as u64)).collect();
+        let (mut monitor, _, _) = process_monitor(vec![100, 1000], vec![processes(1), processes(2)]);
+
+        _ = monitor.poll();
+        let stats = monitor.poll().unwrap();
+
+        assert_eq!(stats.top_processes.len(), TOP_PROCESS_COUNT);
+        assert_eq!(stats.top_processes[0].pid, 8);
+    }
+
+    #[test]
+    fn processes_are_only_scanned_while_the_details_are_open() {
+        let (mut monitor, visibility, scans) = process_monitor(
+            vec![100, 200, 300, 400, 500],
+            vec![
+                vec![(1, "systemd", 10)],
+                vec![(1, "systemd", 20)],
+                vec![(1, "systemd", 90)],
+            ],
+        );
+        visibility.set_visible(false);
+
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
+        assert_eq!(scans.load(Ordering::Relaxed), 0);
+
+        // Opening the details starts a new baseline rather than measuring since the last scan
+        visibility.set_visible(true);
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
+
+        let stats = monitor.poll().unwrap();
+        assert_eq!(stats.top_processes[0].usage_percent, 10.0);
+        assert_eq!(scans.load(Ordering::Relaxed), 2);
+
+        visibility.set_visible(false);
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
+        assert_eq!(scans.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn reset_drops_the_process_baseline() {
+        let (mut monitor, _, _) =
+            process_monitor(vec![100, 200], vec![vec![(1, "systemd", 10)], vec![(1, "systemd", 20)]]);
+
+        _ = monitor.poll();
+        monitor.reset();
+
+        assert!(monitor.poll().unwrap().top_processes.is_empty());
     }
 
     fn eq_to_three_decimal_places(a: f64, b: f64) -> bool {
         (a - b).abs() < 0.0005
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn smoothing_settings_reach_the_monitor() {
+        let mut configuration = AppConfiguration::default();
+        let mut settings_form = CpuMonitorDefinition.settings_form(&configuration);
+        settings_form.values.get_mut(SMOOTHING_SETTING_KEY).unwrap().value = "median".to_string();
+        settings_form.values.get_mut(SMOOTHING_WINDOW_SETTING_KEY).unwrap().value = "10".to_string();
+        CpuMonitorDefinition.update_configuration(&mut configuration, &settings_form);
+
+        let monitor = CpuMonitor::new(MockProcStatReader::new(vec![]), &configuration.cpu);
+
+        assert_eq!(monitor.smoother.strategy(), SmoothingStrategy::Median);
+        assert_eq!(
+            monitor.smoother.window(),
+            SmoothingWindow {
+                max_samples: None,
+                duration: Duration::from_secs(10),
+            }
+        );
+    }
+
+    #[test]
+    fn threshold_settings_colour_the_usage() {
+        let mut configuration = AppConfiguration::default();
+        let mut settings_form = CpuMonitorDefinition.settings_form(&configuration);
+        settings_form.values.get_mut(WARNING_THRESHOLD_SETTING_KEY).unwrap().value = "50".to_string();
+        settings_form.values.get_mut(CRITICAL_THRESHOLD_SETTING_KEY).unwrap().value = "".to_string();
+        CpuMonitorDefinition.update_configuration(&mut configuration, &settings_form);
+        configuration.cpu.max_samples = 1;
+
+        let reader = MockProcStatReader::new(vec![
+            Ok(ProcStatStatus::new(100, 0, 1000)),
+            Ok(ProcStatStatus::new(1000, 0, 2000)),
+        ]);
+        let mut monitor = CpuMonitor::new(reader, &configuration.cpu);
+
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Warning);
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Normal);
+    }
+}
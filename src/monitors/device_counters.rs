@@ -0,0 +1,163 @@
+use log::info;
+use std::collections::HashMap;
+
+/// Tracks ever increasing counters, i.e. bytes received, for each device between polls. Devices
+/// that appear start from their current value and devices that disappear are forgotten, so hot
+/// plugging a device never shows up as a burst of traffic.
+#[derive(Debug, Default)]
+pub struct DeviceCounters<const N: usize> {
+    previous: HashMap<String, [u64; N]>,
+    /// How much each device's counters increased in the last update, by device name
+    device_deltas: Vec<(String, [u64; N])>,
+}
+
+impl<const N: usize> DeviceCounters<N> {
+    /// Records the current counters and returns how much they increased, summed across devices,
+    /// since the last update.
+    pub fn update(&mut self, devices: impl IntoIterator<Item = (String, [u64; N])>) -> [u64; N] {
+        let mut current = HashMap::with_capacity(self.previous.len());
+        let mut deltas = [0u64; N];
+
+        self.device_deltas.clear();
+
+        for (device_name, counters) in devices {
+            match self.previous.get(&device_name) {
+                Some(previous) => {
+                    let device_delta: [u64; N] =
+                        std::array::from_fn(|index| counter_delta(previous[index], counters[index]));
+
+                    for (delta, device_delta) in deltas.iter_mut().zip(device_delta) {
+                        *delta = delta.saturating_add(device_delta);
+                    }
+
+                    self.device_deltas.push((device_name.clone(), device_delta));
+                }
+                None if !self.previous.is_empty() => info!("Device {} appeared", device_name),
+                None => (),
+            }
+
+            current.insert(device_name, counters);
+        }
+
+        self.device_deltas.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for device_name in self.previous.keys().filter(|name| !current.contains_key(*name)) {
+            info!("Device {} disappeared", device_name);
+        }
+
+        self.previous = current;
+
+        deltas
+    }
+
+    /// How much each device's counters increased in the last update, sorted by device name. A
+    /// device that only just appeared has no delta yet, so it isn't included.
+    pub fn device_deltas(&self) -> &[(String, [u64; N])] {
+        &self.device_deltas
+    }
+
+    pub fn clear(&mut self) {
+        self.previous.clear();
+        self.device_deltas.clear();
+    }
+}
+
+/// The increase from previous to current. A counter that went backwards either wrapped around,
+/// which only happens to 32 bit counters and leaves it far below where it was, or was reset to zero,
+/// i.e. the driver was reloaded, in which case everything since the reset is counted.
+fn counter_delta(previous: u64, current: u64) -> u64 {
+    if current >= previous {
+        return current - previous;
+    }
+
+    let max_32_bit = u32::MAX as u64;
+
+    if previous <= max_32_bit && previous - current > max_32_bit / 2 {
+        max_32_bit - previous + current + 1
+    } else {
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str, counter: u64) -> (String, [u64; 1]) {
+        (name.to_string(), [counter])
+    }
+
+    #[test]
+    fn first_update_gives_zero_delta() {
+        let mut counters = DeviceCounters::default();
+
+        assert_eq!(counters.update(vec![device("eth0", 5000)]), [0]);
+    }
+
+    #[test]
+    fn deltas_are_summed_across_devices() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", 1000), device("wlan0", 100)]);
+
+        assert_eq!(counters.update(vec![device("eth0", 1500), device("wlan0", 300)]), [700]);
+    }
+
+    #[test]
+    fn device_deltas_are_kept_per_device() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("wlan0", 100), device("eth0", 1000)]);
+        counters.update(vec![device("wlan0", 300), device("eth0", 1500), device("usb0", 10)]);
+
+        assert_eq!(
+            counters.device_deltas(),
+            &[("eth0".to_string(), [500]), ("wlan0".to_string(), [200])]
+        );
+    }
+
+    #[test]
+    fn removed_device_does_not_reduce_the_total() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", 1000), device("usb0", 900000)]);
+
+        assert_eq!(counters.update(vec![device("eth0", 1200)]), [200]);
+    }
+
+    #[test]
+    fn added_device_starts_from_its_current_value() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", 1000)]);
+
+        assert_eq!(
+            counters.update(vec![device("eth0", 1100), device("usb0", 900000)]),
+            [100]
+        );
+        assert_eq!(
+            counters.update(vec![device("eth0", 1100), device("usb0", 900050)]),
+            [50]
+        );
+    }
+
+    #[test]
+    fn reset_counter_counts_from_zero() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", 5_000_000_000)]);
+
+        assert_eq!(counters.update(vec![device("eth0", 300)]), [300]);
+    }
+
+    #[test]
+    fn wrapped_32_bit_counter_counts_across_the_wrap() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", u32::MAX as u64 - 99)]);
+
+        assert_eq!(counters.update(vec![device("eth0", 50)]), [150]);
+    }
+
+    #[test]
+    fn replugged_device_with_the_same_name_is_a_reset() {
+        let mut counters = DeviceCounters::default();
+        counters.update(vec![device("eth0", 1000), device("usb0", 40000)]);
+
+        assert_eq!(counters.update(vec![device("eth0", 1000), device("usb0", 20)]), [20]);
+    }
+}
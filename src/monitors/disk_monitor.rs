@@ -1,101 +1,215 @@
-use crate::configuration::app_configuration::AppConfiguration;
+use crate::alerts::alert_evaluator::AlertValue;
+use crate::configuration::alert_rule::AlertMetric;
+use crate::configuration::app_configuration::{AppConfiguration, DISK_DETAIL_WINDOW_ID, DISK_SETTINGS_WINDOW_ID};
+use crate::configuration::disk::DiskConfiguration;
+use crate::monitors::device_counters::DeviceCounters;
 use crate::sensors::proc_disk_stats_reader::ProcDiskStats;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
 use log::info;
-use std::cmp;
-use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::proc_disk_stats_reader::ProcDiskStatsReader;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::statvfs_reader::{StatvfsReader, StatvfsStatus};
+use cosmic::iced::window;
+use std::sync::Arc;
 
 const SECTOR_SIZE_BYTES: u64 = 512;
 
-#[derive(Debug, Clone, Default)]
-struct DiskSample {
-    pub bytes_read: u64,
-    pub bytes_written: u64,
+/// The metric history names of the transfer rates, in bytes per second
+pub const DISK_READ_METRIC: &str = "disk-read-bytes-per-second";
+pub const DISK_WRITE_METRIC: &str = "disk-write-bytes-per-second";
+
+/// The transfer rates of one logical disk since the previous poll, in bytes per second
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskDeviceStats {
+    pub name: String,
+    pub read_bytes: u64,
+    pub written_bytes: u64,
 }
 
-impl DiskSample {
-    pub fn new(bytes_read: u64, bytes_written: u64) -> Self {
-        Self {
-            bytes_read,
-            bytes_written,
-        }
-    }
+/// How full the filesystem mounted at a path is
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiskFilesystemStats {
+    pub path: String,
+    pub used_percent: f64,
 }
 
+/// The average transfer rates, in bytes per second
 #[derive(Debug, Clone, Default)]
 pub struct DiskStats {
     pub avg_bytes_written: u64,
     pub avg_bytes_read: u64,
+    /// The rates of each logical disk, which aren't smoothed, sorted by name
+    pub devices: Vec<DiskDeviceStats>,
+    /// The filesystems the alert rules watch
+    pub filesystems: Vec<DiskFilesystemStats>,
+    /// Which of the configured thresholds the busier of the smoothed rates has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl DiskStats {
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![
+            MetricValue::new(DISK_READ_METRIC, self.avg_bytes_read as f64),
+            MetricValue::new(DISK_WRITE_METRIC, self.avg_bytes_written as f64),
+        ]
+    }
+
+    pub fn alert_values(&self) -> Vec<AlertValue> {
+        let mut values = vec![
+            AlertValue::new(AlertMetric::DiskRead, self.avg_bytes_read as f64),
+            AlertValue::new(AlertMetric::DiskWritten, self.avg_bytes_written as f64),
+        ];
+
+        values.extend(self.filesystems.iter().map(|filesystem| {
+            AlertValue::new(AlertMetric::FilesystemUsed(filesystem.path.clone()), filesystem.used_percent)
+        }));
+
+        values
+    }
 }
 
 pub struct DiskMonitor<S: SensorReader<Output = ProcDiskStats>> {
     sensor_reader: S,
-    sample_buffer: VecDeque<DiskSample>,
-    previous_sectors_read: u64,
-    previous_sectors_written: u64,
-    max_samples: usize,
+    /// Smooths the read and write rates, each weighted by the time since the previous poll
+    smoother: Smoother<2>,
+    previous_poll: Option<Instant>,
+    /// The sectors read and written counters of each logical disk
+    device_counters: DeviceCounters<2>,
+    thresholds: ThresholdTracker,
+    /// Reads how full the filesystems are, when an alert rule watches any
+    filesystem_reader: Option<Box<dyn SensorReader<Output = StatvfsStatus> + Send>>,
 }
 
 impl<S: SensorReader<Output = ProcDiskStats>> DiskMonitor<S> {
-    pub fn new(sensor_reader: S, configuration: &AppConfiguration) -> Self {
+    pub fn new(sensor_reader: S, configuration: &DiskConfiguration) -> Self {
         info!("Creating new disk monitor {:?}", configuration);
         Self {
             sensor_reader,
-            sample_buffer: VecDeque::with_capacity(configuration.memory.max_samples),
-            previous_sectors_read: 0,
-            previous_sectors_written: 0,
-            max_samples: configuration.memory.max_samples,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous_poll: None,
+            device_counters: DeviceCounters::default(),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+            filesystem_reader: None,
         }
     }
 
-    pub fn poll(&mut self) -> Result<DiskStats, String> {
+    pub fn with_filesystem_reader(
+        mut self,
+        reader: impl SensorReader<Output = StatvfsStatus> + Send + 'static,
+    ) -> Self {
+        self.filesystem_reader = Some(Box::new(reader));
+        self
+    }
+
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous_poll = None;
+        self.device_counters.clear();
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<DiskStats, SensorError> {
+        self.poll_at(Instant::now())
+    }
+
+    fn poll_at(&mut self, now: Instant) -> Result<DiskStats, SensorError> {
         let current = match self.sensor_reader.read() {
             Ok(value) => value,
             Err(err) => return Err(err),
         };
 
-        let mut current_sectors_read_total = 0u64;
-        let mut current_sectors_written_total = 0u64;
-
-        for device_status in current.device_statuses {
-            if !is_logical_disk(&device_status.device_name) {
-                continue;
-            }
-
-            current_sectors_read_total += device_status.sectors_read;
-            current_sectors_written_total += device_status.sectors_written;
-        }
-
-        if (self.previous_sectors_read == 0) && (self.previous_sectors_written == 0) {
-            self.previous_sectors_read = current_sectors_read_total;
-            self.previous_sectors_written = current_sectors_written_total;
-        }
+        let [delta_sectors_read, delta_sectors_written] = self.device_counters.update(
+            current
+                .device_statuses
+                .into_iter()
+                .filter(|device_status| is_logical_disk(&device_status.device_name))
+                .map(|device_status| {
+                    (device_status.device_name, [device_status.sectors_read, device_status.sectors_written])
+                }),
+        );
 
-        let delta_sectors_read = cmp::max(current_sectors_read_total - self.previous_sectors_read, 0);
-        let delta_sectors_written = cmp::max(current_sectors_written_total - self.previous_sectors_written, 0);
+        // How full a filesystem is needs no baseline, so it is reported from the first poll
+        let filesystems = self.read_filesystems();
 
-        self.previous_sectors_read = current_sectors_read_total;
-        self.previous_sectors_written = current_sectors_written_total;
+        // The first poll only sets the baseline, as there is nothing to compare it to
+        let Some(previous_poll) = self.previous_poll.replace(now) else {
+            return Ok(DiskStats {
+                filesystems,
+                ..Default::default()
+            });
+        };
 
         let delta_bytes_read = delta_sectors_read.saturating_mul(SECTOR_SIZE_BYTES);
         let delta_bytes_written = delta_sectors_written.saturating_mul(SECTOR_SIZE_BYTES);
 
-        self.sample_buffer
-            .push_back(DiskSample::new(delta_bytes_read, delta_bytes_written));
-        if self.sample_buffer.len() > self.max_samples {
-            self.sample_buffer.pop_front();
+        let elapsed_seconds = now.saturating_duration_since(previous_poll).as_secs_f64();
+
+        // Two polls at the same instant have no rate to report
+        if elapsed_seconds == 0.0 {
+            return Ok(DiskStats {
+                filesystems,
+                ..Default::default()
+            });
         }
 
-        let avg_bytes_read =
-            self.sample_buffer.iter().map(|s| s.bytes_read).sum::<u64>() / self.sample_buffer.len() as u64;
-        let avg_bytes_written =
-            self.sample_buffer.iter().map(|s| s.bytes_written).sum::<u64>() / self.sample_buffer.len() as u64;
+        let [avg_bytes_read, avg_bytes_written] = self.smoother.push_weighted(
+            now,
+            [delta_bytes_read as f64 / elapsed_seconds, delta_bytes_written as f64 / elapsed_seconds],
+            elapsed_seconds,
+        );
+
+        let devices = self
+            .device_counters
+            .device_deltas()
+            .iter()
+            .map(|(name, [delta_read, delta_written])| DiskDeviceStats {
+                name: name.clone(),
+                read_bytes: (delta_read.saturating_mul(SECTOR_SIZE_BYTES) as f64 / elapsed_seconds) as u64,
+                written_bytes: (delta_written.saturating_mul(SECTOR_SIZE_BYTES) as f64 / elapsed_seconds) as u64,
+            })
+            .collect();
 
         Ok(DiskStats {
-            avg_bytes_read,
-            avg_bytes_written,
+            avg_bytes_read: avg_bytes_read as u64,
+            avg_bytes_written: avg_bytes_written as u64,
+            devices,
+            filesystems,
+            // The thresholds are in KiB/s
+            threshold_level: self.thresholds.update(avg_bytes_read.max(avg_bytes_written) / 1024.0),
         })
     }
+
+    /// The filesystems are only read for the alert rules, so failing to read them doesn't fail the
+    /// disk monitor
+    fn read_filesystems(&self) -> Vec<DiskFilesystemStats> {
+        let Some(Ok(status)) = self.filesystem_reader.as_ref().map(|reader| reader.read()) else {
+            return Vec::new();
+        };
+
+        status
+            .filesystems
+            .iter()
+            .map(|filesystem| DiskFilesystemStats {
+                path: filesystem.path.clone(),
+                used_percent: filesystem.used_percent(),
+            })
+            .collect()
+    }
 }
 
 fn is_logical_disk(name: &str) -> bool {
@@ -112,19 +226,81 @@ fn is_logical_disk(name: &str) -> bool {
     false
 }
 
+/// The disk throughput monitor
+pub struct DiskMonitorDefinition;
+
+impl MonitorDefinition for DiskMonitorDefinition {
+    fn id(&self) -> window::Id {
+        DISK_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(DiskStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.disk)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.disk = configuration.disk.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.disk.update_interval
+    }
+
+    fn detail_window_id(&self) -> Option<window::Id> {
+        Some(DISK_DETAIL_WINDOW_ID.clone())
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.disk.hide_indicator {
+            return None;
+        }
+
+        let monitor = DiskMonitor::new(ProcDiskStatsReader::new(sensor_root.clone()), &configuration.disk);
+        let filesystem_paths = configuration.alerts.filesystem_paths();
+
+        if filesystem_paths.is_empty() {
+            return Some(Box::new(monitor));
+        }
+
+        Some(Box::new(
+            monitor.with_filesystem_reader(StatvfsReader::new(sensor_root.clone(), filesystem_paths)),
+        ))
+    }
+}
+
+impl<S: SensorReader<Output = ProcDiskStats> + Send> Monitor for DiskMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(DiskMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        DiskMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::sensors::proc_disk_stats_reader::ProcDiskStatsStatus;
+    use crate::sensors::statvfs_reader::FilesystemStatus;
     use std::cell::Cell;
+    use std::collections::VecDeque;
 
     struct MockProcDiskStatsReader {
-        pub samples: VecDeque<Result<ProcDiskStats, String>>,
+        pub samples: VecDeque<Result<ProcDiskStats, SensorError>>,
         pub index: Cell<usize>,
     }
 
     impl MockProcDiskStatsReader {
-        pub fn new(samples: Vec<Result<ProcDiskStats, String>>) -> Self {
+        pub fn new(samples: Vec<Result<ProcDiskStats, SensorError>>) -> Self {
             Self {
                 samples: samples.into(),
                 index: Cell::new(0),
@@ -135,7 +311,7 @@ mod test {
     impl SensorReader for MockProcDiskStatsReader {
         type Output = ProcDiskStats;
 
-        fn read(&self) -> Result<Self::Output, String> {
+        fn read(&self) -> Result<Self::Output, SensorError> {
             let i = self.index.get();
             let result = self.samples[i].clone();
             self.index.set(i + 1);
@@ -143,9 +319,10 @@ mod test {
         }
     }
 
-    fn make_config(max_samples: usize) -> AppConfiguration {
-        let mut cfg = AppConfiguration::default();
-        cfg.memory.max_samples = max_samples;
+    fn make_config(max_samples: usize) -> DiskConfiguration {
+        let mut cfg = DiskConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg.update_interval = Duration::from_secs(1);
         cfg
     }
 
@@ -183,17 +360,18 @@ mod test {
         let reader = MockProcDiskStatsReader::new(vec![Ok(sample1), Ok(sample2)]);
 
         let mut monitor = DiskMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
 
         // Throw away first result, as will always be 0
-        _ = monitor.poll();
-        let result = monitor.poll();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1));
 
         assert!(result.is_ok());
 
         let result = result.unwrap();
 
-        assert_eq!(result.avg_bytes_read, ((2000 - 1000) * 512) / 2);
-        assert_eq!(result.avg_bytes_written, ((2000 - 1000) * 512) / 2);
+        assert_eq!(result.avg_bytes_read, (2000 - 1000) * 512);
+        assert_eq!(result.avg_bytes_written, (2000 - 1000) * 512);
     }
 
     #[test]
@@ -205,26 +383,98 @@ mod test {
         let reader = MockProcDiskStatsReader::new(vec![Ok(sample1), Ok(sample2), Ok(sample3)]);
 
         let mut monitor = DiskMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
 
         // Throw away first result, as will always be 0
-        _ = monitor.poll();
+        _ = monitor.poll_at(start);
 
-        let result1 = monitor.poll();
-        let result2 = monitor.poll();
+        let result1 = monitor.poll_at(start + Duration::from_secs(1));
+        let result2 = monitor.poll_at(start + Duration::from_secs(2));
 
         assert!(result1.is_ok());
 
         let result1 = result1.unwrap();
 
-        assert_eq!(result1.avg_bytes_read, ((2000 - 1000) * 512) / 2);
-        assert_eq!(result1.avg_bytes_written, ((2000 - 1000) * 512) / 2);
+        assert_eq!(result1.avg_bytes_read, (2000 - 1000) * 512);
+        assert_eq!(result1.avg_bytes_written, (2000 - 1000) * 512);
 
         assert!(result2.is_ok());
 
         let result2 = result2.unwrap();
 
-        assert_eq!(result2.avg_bytes_read, ((3000 - 1000) * 512) / 3);
-        assert_eq!(result2.avg_bytes_written, ((3000 - 1000) * 512) / 3);
+        assert_eq!(result2.avg_bytes_read, ((3000 - 1000) * 512) / 2);
+        assert_eq!(result2.avg_bytes_written, ((3000 - 1000) * 512) / 2);
+    }
+
+    #[test]
+    fn device_rates_are_reported_per_disk() {
+        let sample = |sda: u64, nvme: u64| {
+            ProcDiskStats::new(vec![
+                ProcDiskStatsStatus::new("sda".to_string(), sda, sda / 2),
+                ProcDiskStatsStatus::new("sda1".to_string(), sda, sda / 2),
+                ProcDiskStatsStatus::new("nvme0n1".to_string(), nvme, 0),
+            ])
+        };
+
+        let reader = MockProcDiskStatsReader::new(vec![Ok(sample(0, 0)), Ok(sample(40, 8))]);
+        let mut monitor = DiskMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        let first = monitor.poll_at(start).unwrap();
+        let result = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+
+        assert!(first.devices.is_empty());
+        assert_eq!(
+            result.devices,
+            vec![
+                DiskDeviceStats {
+                    name: "nvme0n1".to_string(),
+                    read_bytes: 4 * 512,
+                    written_bytes: 0,
+                },
+                DiskDeviceStats {
+                    name: "sda".to_string(),
+                    read_bytes: 20 * 512,
+                    written_bytes: 10 * 512,
+                },
+            ]
+        );
+    }
+
+    struct MockStatvfsReader;
+
+    impl SensorReader for MockStatvfsReader {
+        type Output = StatvfsStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            Ok(StatvfsStatus {
+                filesystems: vec![FilesystemStatus {
+                    path: "/home".to_string(),
+                    total_bytes: 1000,
+                    free_bytes: 100,
+                    available_bytes: 100,
+                }],
+            })
+        }
+    }
+
+    #[test]
+    fn filesystems_are_reported_from_the_first_poll() {
+        let reader = MockProcDiskStatsReader::new(vec![Ok(make_sensor_sample("sda".to_string(), 0, 0))]);
+        let mut monitor = DiskMonitor::new(reader, &make_config(3)).with_filesystem_reader(MockStatvfsReader);
+
+        let result = monitor.poll_at(Instant::now()).unwrap();
+
+        assert_eq!(
+            result.filesystems,
+            vec![DiskFilesystemStats {
+                path: "/home".to_string(),
+                used_percent: 90.0,
+            }]
+        );
+        assert!(result
+            .alert_values()
+            .contains(&AlertValue::new(AlertMetric::FilesystemUsed("/home".to_string()), 90.0)));
     }
 
     #[test]
@@ -232,17 +482,55 @@ mod test {
         let sample1 = make_sensor_sample("nvme0n1".to_string(), 1000, 1000);
         let sample2 = make_sensor_sample("nvme0n1".to_string(), 2000, 2000);
         let sample3 = make_sensor_sample("nvme0n1".to_string(), 3000, 3000);
+        let sample4 = make_sensor_sample("nvme0n1".to_string(), 4000, 4000);
 
-        let reader = MockProcDiskStatsReader::new(vec![Ok(sample1), Ok(sample2), Ok(sample3)]);
+        let reader = MockProcDiskStatsReader::new(vec![Ok(sample1), Ok(sample2), Ok(sample3), Ok(sample4)]);
 
         let mut monitor = DiskMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
 
-        // Throw away first result, as will always be 0
-        _ = monitor.poll();
-        _ = monitor.poll();
-        _ = monitor.poll();
+        for second in 0..4 {
+            _ = monitor.poll_at(start + Duration::from_secs(second));
+        }
+
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn rates_are_per_second_for_any_interval() {
+        let sample1 = make_sensor_sample("sda".to_string(), 0, 0);
+        let sample2 = make_sensor_sample("sda".to_string(), 100, 50);
+
+        let reader = MockProcDiskStatsReader::new(vec![Ok(sample1), Ok(sample2)]);
+
+        let mut monitor = DiskMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_millis(250)).unwrap();
 
-        assert!(monitor.sample_buffer.len() == 2);
+        assert_eq!(result.avg_bytes_read, 100 * 512 * 4);
+        assert_eq!(result.avg_bytes_written, 50 * 512 * 4);
+    }
+
+    #[test]
+    fn samples_older_than_the_window_are_dropped() {
+        let reader = MockProcDiskStatsReader::new(vec![
+            Ok(make_sensor_sample("sda".to_string(), 0, 0)),
+            Ok(make_sensor_sample("sda".to_string(), 1000, 1000)),
+            Ok(make_sensor_sample("sda".to_string(), 1500, 1250)),
+        ]);
+
+        let mut monitor = DiskMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        _ = monitor.poll_at(start + Duration::from_secs(1));
+        let result = monitor.poll_at(start + Duration::from_secs(6)).unwrap();
+
+        assert_eq!(monitor.smoother.len(), 1);
+        assert_eq!(result.avg_bytes_read, 500 * 512 / 5);
+        assert_eq!(result.avg_bytes_written, 250 * 512 / 5);
     }
 
     #[test]
@@ -275,32 +563,78 @@ mod test {
         let reader = MockProcDiskStatsReader::new(vec![Ok(samples1), Ok(samples2)]);
 
         let mut monitor = DiskMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
 
         // Throw away first result, as will always be 0
-        _ = monitor.poll();
+        _ = monitor.poll_at(start);
 
-        let result = monitor.poll();
+        let result = monitor.poll_at(start + Duration::from_secs(1));
 
         assert!(result.is_ok());
 
         let result = result.unwrap();
 
-        assert_eq!(
-            result.avg_bytes_read,
-            (((2000 + 2000) - (1000 + 1000)) * 512) / 2
-        );
-        assert_eq!(
-            result.avg_bytes_written,
-            (((2000 + 2000) - (1000 + 1000)) * 512) / 2
-        );
+        assert_eq!(result.avg_bytes_read, ((2000 + 2000) - (1000 + 1000)) * 512);
+        assert_eq!(result.avg_bytes_written, ((2000 + 2000) - (1000 + 1000)) * 512);
+    }
+
+    #[test]
+    fn unplugged_disk_mid_stream_does_not_underflow() {
+        let reader = MockProcDiskStatsReader::new(vec![
+            Ok(ProcDiskStats::new(vec![
+                ProcDiskStatsStatus::new("nvme0n1".to_string(), 1000, 1000),
+                ProcDiskStatsStatus::new("sdb".to_string(), 80000, 60000),
+            ])),
+            Ok(ProcDiskStats::new(vec![
+                ProcDiskStatsStatus::new("nvme0n1".to_string(), 1100, 1200),
+                ProcDiskStatsStatus::new("sdb".to_string(), 81000, 60000),
+            ])),
+            Ok(ProcDiskStats::new(vec![ProcDiskStatsStatus::new("nvme0n1".to_string(), 1200, 1400)])),
+        ]);
+
+        let mut monitor = DiskMonitor::new(reader, &make_config(1));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let before_unplug = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(before_unplug.avg_bytes_read, (100 + 1000) * 512);
+        assert_eq!(before_unplug.avg_bytes_written, 200 * 512);
+
+        let after_unplug = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(after_unplug.avg_bytes_read, 100 * 512);
+        assert_eq!(after_unplug.avg_bytes_written, 200 * 512);
+    }
+
+    #[test]
+    fn replugged_disk_counts_from_zero() {
+        let reader = MockProcDiskStatsReader::new(vec![
+            Ok(ProcDiskStats::new(vec![ProcDiskStatsStatus::new("sdb".to_string(), 80000, 60000)])),
+            Ok(ProcDiskStats::new(vec![])),
+            Ok(ProcDiskStats::new(vec![ProcDiskStatsStatus::new("sdb".to_string(), 10, 0)])),
+            Ok(ProcDiskStats::new(vec![ProcDiskStatsStatus::new("sdb".to_string(), 30, 4)])),
+        ]);
+
+        let mut monitor = DiskMonitor::new(reader, &make_config(1));
+        let start = Instant::now();
+
+        for second in 0..3 {
+            let result = monitor.poll_at(start + Duration::from_secs(second)).unwrap();
+            assert_eq!(result.avg_bytes_read, 0);
+            assert_eq!(result.avg_bytes_written, 0);
+        }
+
+        let result = monitor.poll_at(start + Duration::from_secs(3)).unwrap();
+        assert_eq!(result.avg_bytes_read, 20 * 512);
+        assert_eq!(result.avg_bytes_written, 4 * 512);
     }
 
     #[test]
     fn error_is_propagated() {
-        let reader = MockProcDiskStatsReader::new(vec![Err("boom".to_string())]);
+        let reader = MockProcDiskStatsReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
         let mut monitor = DiskMonitor::new(reader, &make_config(1));
 
         let err = monitor.poll().unwrap_err();
-        assert_eq!(err, "boom");
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
     }
 }
@@ -0,0 +1,349 @@
+use crate::configuration::app_configuration::{AppConfiguration, FILE_SETTINGS_WINDOW_ID};
+use crate::configuration::file::{FileConfiguration, FileValueMode};
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use crate::sensors::sys_file_reader::{SysFileReader, SysFileStatus};
+use cosmic::iced::window;
+use log::info;
+use regex::Regex;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The metric history name of the scaled file value
+pub const FILE_VALUE_METRIC: &str = "file-value";
+
+#[derive(Default, Clone, Debug)]
+pub struct FileStats {
+    pub value: f64,
+    /// Set on the first read in rate mode, which only reads the counter and so has no rate yet
+    pub is_baseline: bool,
+    /// Which of the configured thresholds the smoothed value has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl FileStats {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            is_baseline: false,
+            threshold_level: ThresholdLevel::Normal,
+        }
+    }
+
+    /// The value, or none on the first read in rate mode as it has nothing to measure against
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        if self.is_baseline {
+            return Vec::new();
+        }
+
+        vec![MetricValue::new(FILE_VALUE_METRIC, self.value)]
+    }
+}
+
+pub struct FileMonitor<S: SensorReader<Output = SysFileStatus>> {
+    sensor_reader: S,
+    smoother: Smoother<1>,
+    /// The time and value of the previous read, which rates are measured from
+    previous: Option<(Instant, f64)>,
+    mode: FileValueMode,
+    scale: f64,
+    thresholds: ThresholdTracker,
+}
+
+impl<S: SensorReader<Output = SysFileStatus>> FileMonitor<S> {
+    pub fn new(sensor_reader: S, configuration: &FileConfiguration) -> Self {
+        info!("Creating new file monitor {:?}", configuration);
+        Self {
+            sensor_reader,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous: None,
+            mode: configuration.mode,
+            scale: configuration.scale.value(),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+        }
+    }
+
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<FileStats, SensorError> {
+        self.poll_at(Instant::now())
+    }
+
+    fn poll_at(&mut self, now: Instant) -> Result<FileStats, SensorError> {
+        let current = match self.sensor_reader.read() {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        };
+
+        if self.mode == FileValueMode::Absolute {
+            let [smoothed] = self.smoother.push(now, [current.value * self.scale]);
+
+            return Ok(self.stats(smoothed));
+        }
+
+        // The first read only sets the baseline, as there is nothing to compare it to
+        let Some((previous_poll, previous_value)) = self.previous.replace((now, current.value)) else {
+            return Ok(FileStats {
+                is_baseline: true,
+                ..Default::default()
+            });
+        };
+
+        let elapsed_seconds = now.saturating_duration_since(previous_poll).as_secs_f64();
+
+        // Two reads at the same instant have no rate to report
+        if elapsed_seconds == 0.0 {
+            return Ok(FileStats {
+                is_baseline: true,
+                ..Default::default()
+            });
+        }
+
+        // A counter that goes backwards has been reset, so treat it as no change.
+        let delta = if current.value >= previous_value {
+            current.value - previous_value
+        } else {
+            0.0
+        };
+
+        // Weighting each rate by its elapsed time makes the average the change over the total time
+        let [smoothed] = self
+            .smoother
+            .push_weighted(now, [delta * self.scale / elapsed_seconds], elapsed_seconds);
+
+        Ok(self.stats(smoothed))
+    }
+
+    fn stats(&mut self, value: f64) -> FileStats {
+        FileStats {
+            threshold_level: self.thresholds.update(value),
+            ..FileStats::new(value)
+        }
+    }
+}
+
+/// The monitor for a value read from a user configured file
+pub struct FileMonitorDefinition;
+
+impl MonitorDefinition for FileMonitorDefinition {
+    fn id(&self) -> window::Id {
+        FILE_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(FileStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.file)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.file = configuration.file.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.file.update_interval
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.file.hide_indicator || configuration.file.file_path.is_empty() {
+            return None;
+        }
+
+        let pattern = configuration
+            .file
+            .pattern
+            .as_deref()
+            .and_then(|pattern| Regex::new(pattern).ok());
+        let reader = SysFileReader::new(
+            sensor_root,
+            PathBuf::from(&configuration.file.file_path),
+            pattern,
+            configuration.file.field_index,
+        );
+
+        Some(Box::new(FileMonitor::new(reader, &configuration.file)))
+    }
+}
+
+impl<S: SensorReader<Output = SysFileStatus> + Send> Monitor for FileMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(FileMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        FileMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::file::FileScale;
+    use crate::configuration::thresholds::ThresholdConfiguration;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    struct MockSysFileReader {
+        readings: Vec<Result<SysFileStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockSysFileReader {
+        fn new(readings: Vec<Result<SysFileStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockSysFileReader {
+        type Output = SysFileStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn make_config(max_samples: usize, mode: FileValueMode, scale: f64) -> FileConfiguration {
+        let mut cfg = FileConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg.mode = mode;
+        cfg.scale = FileScale::new(scale).unwrap();
+        cfg.update_interval = Duration::from_secs(2);
+        cfg
+    }
+
+    #[test]
+    fn absolute_mode_averages_scaled_values() {
+        let reader = MockSysFileReader::new(vec![Ok(SysFileStatus::new(12000.0)), Ok(SysFileStatus::new(12500.0))]);
+        let mut monitor = FileMonitor::new(reader, &make_config(2, FileValueMode::Absolute, 0.001));
+
+        let result1 = monitor.poll().unwrap();
+        assert!((result1.value - 12.0).abs() < 0.0005);
+
+        let result2 = monitor.poll().unwrap();
+        assert!((result2.value - 12.25).abs() < 0.0005);
+    }
+
+    #[test]
+    fn thresholds_are_in_the_scaled_unit() {
+        let reader = MockSysFileReader::new(vec![Ok(SysFileStatus::new(45000.0)), Ok(SysFileStatus::new(85000.0))]);
+        let mut config = make_config(1, FileValueMode::Absolute, 0.001);
+        config.thresholds = ThresholdConfiguration::new(40, 80, 0);
+        let mut monitor = FileMonitor::new(reader, &config);
+
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Warning);
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Critical);
+    }
+
+    #[test]
+    fn rate_mode_first_poll_only_sets_the_baseline() {
+        let reader = MockSysFileReader::new(vec![Ok(SysFileStatus::new(5000.0))]);
+        let mut monitor = FileMonitor::new(reader, &make_config(2, FileValueMode::Rate, 1.0));
+
+        let result = monitor.poll().unwrap();
+
+        assert!(result.is_baseline);
+        assert!(result.metrics().is_empty());
+        assert!(monitor.smoother.is_empty());
+    }
+
+    #[test]
+    fn rate_mode_gives_change_per_second() {
+        let reader = MockSysFileReader::new(vec![Ok(SysFileStatus::new(5000.0)), Ok(SysFileStatus::new(7000.0))]);
+        let mut monitor = FileMonitor::new(reader, &make_config(1, FileValueMode::Rate, 1.0));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+
+        // 2000 over a 2 second interval
+        assert!((result.value - 1000.0).abs() < 0.0005);
+    }
+
+    #[test]
+    fn rate_mode_is_measured_over_the_elapsed_time() {
+        let reader = MockSysFileReader::new(vec![Ok(SysFileStatus::new(5000.0)), Ok(SysFileStatus::new(7000.0))]);
+        let mut monitor = FileMonitor::new(reader, &make_config(1, FileValueMode::Rate, 1.0));
+        let start = Instant::now();
+
+        // A read that runs late measures the change over the time that really passed
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(5)).unwrap();
+
+        assert!((result.value - 400.0).abs() < 0.0005);
+    }
+
+    #[test]
+    fn rate_mode_ignores_counter_reset() {
+        let reader = MockSysFileReader::new(vec![
+            Ok(SysFileStatus::new(5000.0)),
+            Ok(SysFileStatus::new(10.0)),
+            Ok(SysFileStatus::new(410.0)),
+        ]);
+        let mut monitor = FileMonitor::new(reader, &make_config(1, FileValueMode::Rate, 1.0));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let reset = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(reset.value, 0.0);
+
+        let after_reset = monitor.poll_at(start + Duration::from_secs(4)).unwrap();
+        assert!((after_reset.value - 200.0).abs() < 0.0005);
+    }
+
+    #[test]
+    fn samples_buffer_trims_to_max_size() {
+        let reader = MockSysFileReader::new(vec![
+            Ok(SysFileStatus::new(1.0)),
+            Ok(SysFileStatus::new(2.0)),
+            Ok(SysFileStatus::new(3.0)),
+        ]);
+        let mut monitor = FileMonitor::new(reader, &make_config(2, FileValueMode::Absolute, 1.0));
+
+        _ = monitor.poll();
+        _ = monitor.poll();
+        _ = monitor.poll();
+
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn error_is_propagated() {
+        let reader = MockSysFileReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let mut monitor = FileMonitor::new(reader, &make_config(1, FileValueMode::Absolute, 1.0));
+
+        let err = monitor.poll().unwrap_err();
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+}
@@ -1,13 +1,89 @@
-use crate::sensors::proc_meminfo_reader::ProcMemInfoStatus;
+use crate::sensors::proc_meminfo_reader::{MemoryCompressionStatus, ProcMemInfoStatus};
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
-use std::collections::VecDeque;
 use log::info;
-use crate::configuration::app_configuration::AppConfiguration;
+use crate::alerts::alert_evaluator::AlertValue;
+use crate::configuration::alert_rule::AlertMetric;
+use crate::configuration::memory::MemoryConfiguration;
+use crate::configuration::app_configuration::{AppConfiguration, MEMORY_DETAIL_WINDOW_ID, MEMORY_SETTINGS_WINDOW_ID};
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::proc_meminfo_reader::ProcMemInfoSensorReader;
+use crate::sensors::sensor_root::SensorRoot;
+use cosmic::iced::window;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The metric history names of the memory totals, in KiB
+pub const MEMORY_USED_METRIC: &str = "memory-used-kib";
+pub const MEMORY_TOTAL_METRIC: &str = "memory-total-kib";
+
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct MemoryCompressionStats {
+    pub original_kib: u64,
+    pub compressed_kib: u64,
+}
+
+impl MemoryCompressionStats {
+    /// How many times smaller the data is once compressed, i.e. 3.0 for 3:1
+    pub fn ratio(&self) -> f64 {
+        if self.compressed_kib == 0 {
+            0.0
+        } else {
+            self.original_kib as f64 / self.compressed_kib as f64
+        }
+    }
+}
+
+impl From<MemoryCompressionStatus> for MemoryCompressionStats {
+    fn from(status: MemoryCompressionStatus) -> Self {
+        Self {
+            original_kib: status.original_kib,
+            compressed_kib: status.compressed_kib,
+        }
+    }
+}
+
+/// Where the memory is going, as in the output of `free`. None of it is smoothed.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct MemoryBreakdown {
+    pub available_kib: u64,
+    pub free_kib: u64,
+    pub buffers_kib: u64,
+    /// The page cache and reclaimable kernel caches, without shared memory
+    pub cache_kib: u64,
+    pub shmem_kib: u64,
+}
+
+impl From<&ProcMemInfoStatus> for MemoryBreakdown {
+    fn from(status: &ProcMemInfoStatus) -> Self {
+        Self {
+            available_kib: status.available_kib,
+            free_kib: status.free_kib,
+            buffers_kib: status.buffers_kib,
+            cache_kib: (status.cached_kib + status.slab_reclaimable_kib).saturating_sub(status.shmem_kib),
+            shmem_kib: status.shmem_kib,
+        }
+    }
+}
 
 #[derive(Default, Clone, Debug)]
 pub struct MemoryStats {
     pub total_kib: u64,
     pub used_kib: u64,
+    /// The latest swap totals, which aren't smoothed. Both are zero without swap.
+    pub swap_total_kib: u64,
+    pub swap_used_kib: u64,
+    pub breakdown: MemoryBreakdown,
+    /// Which of the configured thresholds the smoothed percentage used has crossed
+    pub threshold_level: ThresholdLevel,
+    /// The latest zram totals, if there are any zram devices
+    pub zram: Option<MemoryCompressionStats>,
+    /// The latest zswap totals, if zswap is enabled
+    pub zswap: Option<MemoryCompressionStats>,
 }
 
 impl MemoryStats {
@@ -15,43 +91,155 @@ impl MemoryStats {
         Self {
             total_kib,
             used_kib,
+            swap_total_kib: 0,
+            swap_used_kib: 0,
+            breakdown: MemoryBreakdown::default(),
+            threshold_level: ThresholdLevel::Normal,
+            zram: None,
+            zswap: None,
+        }
+    }
+
+    pub fn used_percent(&self) -> f64 {
+        if self.total_kib == 0 {
+            0.0
+        } else {
+            self.used_kib as f64 / self.total_kib as f64 * 100.0
         }
     }
+
+    /// The memory that isn't used, which includes the reclaimable ZFS ARC when it is counted as
+    /// available
+    pub fn available_kib(&self) -> u64 {
+        self.total_kib.saturating_sub(self.used_kib)
+    }
+
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![
+            MetricValue::new(MEMORY_USED_METRIC, self.used_kib as f64),
+            MetricValue::new(MEMORY_TOTAL_METRIC, self.total_kib as f64),
+        ]
+    }
+
+    /// The available memory is what isn't counted as used, so it matches the indicator
+    pub fn alert_values(&self) -> Vec<AlertValue> {
+        vec![
+            AlertValue::new(AlertMetric::MemoryUsed, self.used_percent()),
+            AlertValue::new(AlertMetric::MemoryAvailable, self.available_kib() as f64 * 1024.0),
+        ]
+    }
 }
 
 pub struct MemoryMonitor<S: SensorReader<Output = ProcMemInfoStatus>> {
     sensor_reader: S,
-    sample_buffer: VecDeque<u64>,
-    max_samples: usize,
+    smoother: Smoother<1>,
+    thresholds: ThresholdTracker,
+    count_zfs_arc_as_reclaimable: bool,
 }
 
 impl<S: SensorReader<Output = ProcMemInfoStatus>> MemoryMonitor<S> {
-    pub fn new(sensor_reader: S, configuration: &AppConfiguration) -> Self {
-        info!("Creating new memory monitor {:?}", configuration.memory);
+    pub fn new(sensor_reader: S, configuration: &MemoryConfiguration) -> Self {
+        info!("Creating new memory monitor {:?}", configuration);
         Self {
             sensor_reader,
-            sample_buffer: VecDeque::with_capacity(configuration.memory.max_samples),
-            max_samples: configuration.memory.max_samples
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+            count_zfs_arc_as_reclaimable: configuration.count_zfs_arc_as_reclaimable,
         }
     }
     
-    pub fn poll(&mut self) -> Result<MemoryStats, String> {
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<MemoryStats, SensorError> {
         let meminfo_state = match self.sensor_reader.read() {
             Ok(state) => state,
             Err(err) => return Err(err),
         };
 
-        let current_used = meminfo_state.total_kib.saturating_sub(meminfo_state.available_kib);
-        
-        self.sample_buffer.push_back(current_used);
-        
-        if self.sample_buffer.len() > self.max_samples {
-            self.sample_buffer.pop_front();       
+        let mut current_used = meminfo_state.total_kib.saturating_sub(meminfo_state.available_kib);
+
+        if self.count_zfs_arc_as_reclaimable {
+            current_used = current_used.saturating_sub(meminfo_state.arc_reclaimable_kib);
         }
-        
-        let average_used = self.sample_buffer.iter().sum::<u64>() / self.sample_buffer.len() as u64;
 
-        Ok(MemoryStats::new(meminfo_state.total_kib, average_used))
+
+        let [smoothed_used] = self.smoother.push(Instant::now(), [current_used as f64]);
+
+        let mut stats = MemoryStats::new(meminfo_state.total_kib, smoothed_used as u64);
+        stats.swap_total_kib = meminfo_state.swap_total_kib;
+        stats.swap_used_kib = meminfo_state.swap_total_kib.saturating_sub(meminfo_state.swap_free_kib);
+        stats.breakdown = MemoryBreakdown::from(&meminfo_state);
+        stats.threshold_level = self.thresholds.update(stats.used_percent());
+        stats.zram = meminfo_state.zram.map(MemoryCompressionStats::from);
+        stats.zswap = meminfo_state.zswap.map(MemoryCompressionStats::from);
+
+        Ok(stats)
+    }
+}
+
+/// The memory usage monitor
+pub struct MemoryMonitorDefinition;
+
+impl MonitorDefinition for MemoryMonitorDefinition {
+    fn id(&self) -> window::Id {
+        MEMORY_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(MemoryStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.memory)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.memory = configuration.memory.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.memory.update_interval
+    }
+
+    fn detail_window_id(&self) -> Option<window::Id> {
+        Some(MEMORY_DETAIL_WINDOW_ID.clone())
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.memory.hide_indicator {
+            return None;
+        }
+
+        let sensor_reader = ProcMemInfoSensorReader::new(sensor_root.clone())
+            .with_zfs_arc(configuration.memory.count_zfs_arc_as_reclaimable)
+            .with_compression(configuration.memory.show_compression);
+
+        Some(Box::new(MemoryMonitor::new(sensor_reader, &configuration.memory)))
+    }
+}
+
+impl<S: SensorReader<Output = ProcMemInfoStatus> + Send> Monitor for MemoryMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(MemoryMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        MemoryMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
     }
 }
 
@@ -63,12 +251,12 @@ mod tests {
     const TOTAL_KIB: u64 = 31934904;
 
     struct MockProcMeminfoReader {
-        readings: Vec<Result<ProcMemInfoStatus, String>>,
+        readings: Vec<Result<ProcMemInfoStatus, SensorError>>,
         index: Cell<usize>
     }
 
     impl MockProcMeminfoReader {
-        fn new(readings: Vec<Result<ProcMemInfoStatus, String>>) -> MockProcMeminfoReader {
+        fn new(readings: Vec<Result<ProcMemInfoStatus, SensorError>>) -> MockProcMeminfoReader {
             Self {
                 readings,
                 index: Cell::new(0),
@@ -79,7 +267,7 @@ mod tests {
     impl SensorReader for MockProcMeminfoReader {
         type Output = ProcMemInfoStatus;
 
-        fn read(&self) -> Result<Self::Output, String> {
+        fn read(&self) -> Result<Self::Output, SensorError> {
             let index = self.index.get();
             let result = self.readings[index].clone();
             self.index.set(index + 1);
@@ -87,9 +275,9 @@ mod tests {
         }
     }
 
-    fn make_config(max_samples: usize) -> AppConfiguration {
-        let mut cfg = AppConfiguration::default();
-        cfg.memory.max_samples = max_samples;
+    fn make_config(max_samples: usize) -> MemoryConfiguration {
+        let mut cfg = MemoryConfiguration::default();
+        cfg.max_samples = max_samples;
         cfg
     }
 
@@ -102,7 +290,7 @@ mod tests {
             Ok(ProcMemInfoStatus::new(TOTAL_KIB, AVAILABLE_KIB)),
         ]);
 
-        let mut monitor = MemoryMonitor::new(mock_memory_reader, &AppConfiguration::default());
+        let mut monitor = MemoryMonitor::new(mock_memory_reader, &MemoryConfiguration::default());
         let result = monitor.poll();
 
         assert!(result.is_ok());
@@ -169,15 +357,117 @@ mod tests {
         _ = monitor.poll();
         _ = monitor.poll();
 
-        assert!(monitor.sample_buffer.len() == 2);
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn zfs_arc_is_only_reclaimable_when_configured() {
+
+        const AVAILABLE_KIB: u64 = 12048124;
+        const ARC_RECLAIMABLE_KIB: u64 = 8000000;
+
+        let mut reading = ProcMemInfoStatus::new(TOTAL_KIB, AVAILABLE_KIB);
+        reading.arc_reclaimable_kib = ARC_RECLAIMABLE_KIB;
+
+        let mut config = make_config(1);
+        let mut default_monitor = MemoryMonitor::new(MockProcMeminfoReader::new(vec![Ok(reading.clone())]), &config);
+
+        config.count_zfs_arc_as_reclaimable = true;
+        let mut arc_monitor = MemoryMonitor::new(MockProcMeminfoReader::new(vec![Ok(reading)]), &config);
+
+        assert_eq!(default_monitor.poll().unwrap().used_kib, TOTAL_KIB - AVAILABLE_KIB);
+        assert_eq!(arc_monitor.poll().unwrap().used_kib, TOTAL_KIB - AVAILABLE_KIB - ARC_RECLAIMABLE_KIB);
+    }
+
+    #[test]
+    fn compression_stats_are_reported() {
+
+        let mut reading = ProcMemInfoStatus::new(TOTAL_KIB, 22048124);
+        reading.zram = Some(MemoryCompressionStatus::new(3000000, 1000000));
+
+        let mut monitor = MemoryMonitor::new(MockProcMeminfoReader::new(vec![Ok(reading)]), &make_config(1));
+        let result = monitor.poll().unwrap();
+
+        let zram = result.zram.unwrap();
+        assert_eq!(zram.original_kib, 3000000);
+        assert_eq!(zram.compressed_kib, 1000000);
+        assert_eq!(zram.ratio(), 3.0);
+        assert_eq!(result.zswap, None);
+    }
+
+    #[test]
+    fn swap_usage_is_reported() {
+        let mut reading = ProcMemInfoStatus::new(TOTAL_KIB, 22048124);
+        reading.swap_total_kib = 8388604;
+        reading.swap_free_kib = 7340028;
+
+        let mut monitor = MemoryMonitor::new(MockProcMeminfoReader::new(vec![Ok(reading)]), &make_config(1));
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(result.swap_total_kib, 8388604);
+        assert_eq!(result.swap_used_kib, 8388604 - 7340028);
+    }
+
+    #[test]
+    fn breakdown_counts_shared_memory_apart_from_the_cache() {
+        let mut reading = ProcMemInfoStatus::new(TOTAL_KIB, 9383212);
+        reading.free_kib = 2204536;
+        reading.buffers_kib = 412992;
+        reading.cached_kib = 6619968;
+        reading.shmem_kib = 391860;
+        reading.slab_reclaimable_kib = 367428;
+
+        let mut monitor = MemoryMonitor::new(MockProcMeminfoReader::new(vec![Ok(reading)]), &make_config(1));
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(
+            result.breakdown,
+            MemoryBreakdown {
+                available_kib: 9383212,
+                free_kib: 2204536,
+                buffers_kib: 412992,
+                cache_kib: 6619968 + 367428 - 391860,
+                shmem_kib: 391860,
+            }
+        );
+    }
+
+    #[test]
+    fn memory_thresholds_are_in_percent_used() {
+        let reading = |available_kib| Ok(ProcMemInfoStatus::new(1000, available_kib));
+        let reader = MockProcMeminfoReader::new(vec![reading(250), reading(50), reading(150)]);
+        let mut monitor = MemoryMonitor::new(reader, &make_config(1));
+
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Warning);
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Critical);
+        // 85% is within the default hysteresis of the critical threshold
+        assert_eq!(monitor.poll().unwrap().threshold_level, ThresholdLevel::Critical);
+    }
+
+    #[test]
+    fn alert_values_are_percent_used_and_bytes_available() {
+        let stats = MemoryStats::new(1000, 750);
+
+        assert_eq!(
+            stats.alert_values(),
+            vec![
+                AlertValue::new(AlertMetric::MemoryUsed, 75.0),
+                AlertValue::new(AlertMetric::MemoryAvailable, 250.0 * 1024.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_compression_has_zero_ratio() {
+        assert_eq!(MemoryCompressionStats::default().ratio(), 0.0);
     }
 
     #[test]
     fn error_is_propagated() {
-        let reader =  MockProcMeminfoReader::new(vec![Err("boom".to_string())]);
+        let reader =  MockProcMeminfoReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
         let mut monitor = MemoryMonitor::new(reader, &make_config(1));
 
         let err = monitor.poll().unwrap_err();
-        assert_eq!(err, "boom");
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
     }
-}
\ No newline at end of file
+}
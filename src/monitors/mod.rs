@@ -2,3 +2,15 @@ pub mod cpu_monitor;
 pub mod memory_monitor;
 pub mod network_monitor;
 pub mod disk_monitor;
+pub mod file_monitor;
+pub mod power_monitor;
+pub mod paging_monitor;
+pub mod arc_monitor;
+pub mod raid_monitor;
+pub mod device_counters;
+pub mod suspend_detector;
+pub mod monitor;
+pub mod monitor_schedule;
+pub mod monitor_registry;
+pub mod smoothing;
+pub mod thresholds;
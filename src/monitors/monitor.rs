@@ -0,0 +1,94 @@
+use crate::alerts::alert_evaluator::AlertValue;
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::AppConfiguration;
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::smoothing::SmoothingWindow;
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use cosmic::iced::window;
+use cosmic::Element;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The latest stats produced by a monitor, which know how to draw their own indicator
+pub trait MonitorStats: Debug + Send + Sync {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>>;
+
+    /// The named values of the stats, which are recorded in the metric history
+    fn metrics(&self) -> Vec<MetricValue>;
+
+    /// The values the alert rules are checked against, or none if no rule can watch the monitor
+    fn alert_values(&self) -> Vec<AlertValue> {
+        Vec::new()
+    }
+
+    /// The content of the popup opened by clicking the indicator, or None if it has no details
+    fn draw_detail<'app>(&self, _app_state: &'app AppState) -> Option<Element<'app, Message>> {
+        None
+    }
+}
+
+/// The outcome of polling a monitor
+pub type MonitorResult = Result<Arc<dyn MonitorStats>, SensorError>;
+
+/// Whether a monitor's detail popup is open. The app sets it as the popup opens and closes, and
+/// the running monitor reads it to only gather what just the popup shows while it can be seen.
+#[derive(Clone, Debug, Default)]
+pub struct DetailVisibility(Arc<AtomicBool>);
+
+impl DetailVisibility {
+    pub fn is_visible(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.0.store(visible, Ordering::Relaxed);
+    }
+}
+
+/// A running monitor, polled on its update interval while monitoring is active
+pub trait Monitor: Send {
+    fn poll(&mut self) -> MonitorResult;
+
+    /// Drops any baseline and samples, so the next poll starts over as if the monitor was new
+    fn reset(&mut self) {}
+
+    /// Shares whether the monitor's detail popup is open, for monitors with details that are too
+    /// costly to gather on every poll
+    fn set_detail_visibility(&mut self, _visibility: DetailVisibility) {}
+
+    /// The span the monitor smooths its samples over, or None if it doesn't smooth them
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        None
+    }
+}
+
+/// Everything the app needs to know about a monitor: its section of the configuration, its
+/// settings form and how to create it. Adding a monitor means implementing this and registering
+/// it in the `MonitorRegistry`.
+pub trait MonitorDefinition: Send + Sync {
+    /// The id of the monitor's settings window, which also identifies the monitor
+    fn id(&self) -> window::Id;
+
+    /// The stats shown until the first poll completes
+    fn default_stats(&self) -> Arc<dyn MonitorStats>;
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm;
+
+    /// Applies the values from the monitor's settings form to its section of the configuration
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm);
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration;
+
+    /// The id of the popup showing the monitor's details, or None if clicking its indicator
+    /// opens the dashboard instead
+    fn detail_window_id(&self) -> Option<window::Id> {
+        None
+    }
+
+    /// Creates the monitor, or None if it is disabled in the configuration
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>>;
+}
@@ -0,0 +1,212 @@
+use crate::configuration::app_configuration::AppConfiguration;
+use crate::monitors::arc_monitor::ArcMonitorDefinition;
+use crate::monitors::cpu_monitor::CpuMonitorDefinition;
+use crate::monitors::disk_monitor::DiskMonitorDefinition;
+use crate::monitors::file_monitor::FileMonitorDefinition;
+use crate::monitors::memory_monitor::MemoryMonitorDefinition;
+use crate::monitors::monitor::{DetailVisibility, MonitorDefinition};
+use crate::monitors::monitor_schedule::MonitorSchedule;
+use crate::monitors::network_monitor::NetworkMonitorDefinition;
+use crate::monitors::paging_monitor::PagingMonitorDefinition;
+use crate::monitors::power_monitor::PowerMonitorDefinition;
+use crate::monitors::raid_monitor::RaidMonitorDefinition;
+use crate::sensors::sensor_root::SensorRoot;
+use cosmic::iced::window;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+/// The monitors available to the applet, in the order their indicators appear on the panel
+pub struct MonitorRegistry {
+    definitions: Vec<Box<dyn MonitorDefinition>>,
+    /// Whether each detail popup is open, by its detail window id. It outlives the running
+    /// monitors, so a monitor restarted while its popup is open still knows it is.
+    detail_visibility: BTreeMap<window::Id, DetailVisibility>,
+}
+
+impl MonitorRegistry {
+    pub fn new() -> Self {
+        Self {
+            definitions: Vec::new(),
+            detail_visibility: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, definition: impl MonitorDefinition + 'static) {
+        if let Some(detail_window_id) = definition.detail_window_id() {
+            self.detail_visibility
+                .insert(detail_window_id, DetailVisibility::default());
+        }
+
+        self.definitions.push(Box::new(definition));
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = &dyn MonitorDefinition> {
+        self.definitions.iter().map(|definition| definition.as_ref())
+    }
+
+    pub fn get(&self, id: &window::Id) -> Option<&dyn MonitorDefinition> {
+        self.definitions().find(|definition| definition.id() == *id)
+    }
+
+    /// Creates every monitor enabled in the configuration, scheduled to be polled from `now`
+    pub fn start_monitors(
+        &self,
+        configuration: &AppConfiguration,
+        sensor_root: &SensorRoot,
+        now: Instant,
+    ) -> MonitorSchedule {
+        let mut schedule = MonitorSchedule::new();
+
+        for definition in self.definitions() {
+            if let Some(mut monitor) = definition.create_monitor(configuration, sensor_root) {
+                if let Some(visibility) = definition
+                    .detail_window_id()
+                    .and_then(|detail_window_id| self.detail_visibility.get(&detail_window_id))
+                {
+                    monitor.set_detail_visibility(visibility.clone());
+                }
+
+                schedule.add(definition.id(), definition.update_interval(configuration), monitor, now);
+            }
+        }
+
+        schedule
+    }
+
+    /// Records whether a detail popup is open, which its running monitor sees on its next poll
+    pub fn set_detail_visible(&self, detail_window_id: &window::Id, visible: bool) {
+        if let Some(visibility) = self.detail_visibility.get(detail_window_id) {
+            visibility.set_visible(visible);
+        }
+    }
+}
+
+impl Default for MonitorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        registry.register(CpuMonitorDefinition);
+        registry.register(MemoryMonitorDefinition);
+        registry.register(NetworkMonitorDefinition);
+        registry.register(DiskMonitorDefinition);
+        registry.register(FileMonitorDefinition);
+        registry.register(PowerMonitorDefinition);
+        registry.register(PagingMonitorDefinition::default());
+        registry.register(ArcMonitorDefinition);
+        registry.register(RaidMonitorDefinition);
+
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::app_configuration::{
+        CPU_SETTINGS_WINDOW_ID, HIDE_INDICATOR_SETTING_KEY, MAX_SAMPLES_SETTING_KEY, UPDATE_INTERVAL_SETTING_KEY,
+    };
+    use crate::monitors::smoothing::SmoothingWindow;
+    use std::time::Duration;
+
+    /// Sets values in a monitor's settings form and applies it to the configuration, as saving its
+    /// settings window does
+    fn apply_settings(
+        definition: &dyn MonitorDefinition,
+        configuration: &mut AppConfiguration,
+        values: &[(&str, &str)],
+    ) {
+        let mut settings_form = definition.settings_form(configuration);
+        for (key, value) in values {
+            if let Some(item) = settings_form.values.get_mut(*key) {
+                item.value = value.to_string();
+            }
+        }
+
+        definition.update_configuration(configuration, &settings_form);
+    }
+
+    #[test]
+    fn default_registry_has_a_settings_form_for_every_monitor() {
+        let registry = MonitorRegistry::default();
+        let configuration = AppConfiguration::default();
+
+        for definition in registry.definitions() {
+            assert_eq!(
+                definition.settings_form(&configuration).settings_window_id,
+                definition.id()
+            );
+        }
+
+        assert_eq!(registry.definitions().count(), 9);
+        assert!(registry.get(&CPU_SETTINGS_WINDOW_ID).is_some());
+    }
+
+    #[test]
+    fn settings_form_round_trips_through_the_configuration() {
+        let registry = MonitorRegistry::default();
+        let mut configuration = AppConfiguration::default();
+        configuration.cpu.hide_indicator = true;
+
+        let cpu = registry.get(&CPU_SETTINGS_WINDOW_ID).unwrap();
+        let settings_form = cpu.settings_form(&configuration);
+        let mut updated = AppConfiguration::default();
+        cpu.update_configuration(&mut updated, &settings_form);
+
+        assert!(updated.cpu.hide_indicator);
+    }
+
+    #[test]
+    fn settings_form_values_reach_each_smoothed_monitor() {
+        let registry = MonitorRegistry::default();
+        let mut smoothed_monitors = 0;
+
+        for definition in registry.definitions() {
+            let mut configuration = AppConfiguration::default();
+            configuration.file.file_path = "/sys/class/hwmon/hwmon0/temp1_input".to_string();
+            // Every other monitor keeps 3 samples, so only the monitor's own section counts
+            for other in registry.definitions() {
+                let values = [(HIDE_INDICATOR_SETTING_KEY, "false"), (MAX_SAMPLES_SETTING_KEY, "3")];
+                apply_settings(other, &mut configuration, &values);
+            }
+            let values = [(MAX_SAMPLES_SETTING_KEY, "7"), (UPDATE_INTERVAL_SETTING_KEY, "500")];
+            apply_settings(definition, &mut configuration, &values);
+
+            let monitor = definition
+                .create_monitor(&configuration, &SensorRoot::new("/"))
+                .unwrap();
+            if let Some(window) = monitor.smoothing_window() {
+                assert_eq!(
+                    window,
+                    SmoothingWindow::new(7, Duration::from_millis(500), None),
+                    "{:?}",
+                    definition.id()
+                );
+                smoothed_monitors += 1;
+            }
+        }
+
+        // All but the RAID monitor, which only reports the array health
+        assert_eq!(smoothed_monitors, 8);
+    }
+
+    #[test]
+    fn hidden_monitors_are_not_started() {
+        let registry = MonitorRegistry::default();
+        let mut configuration = AppConfiguration::default();
+        configuration.cpu.hide_indicator = true;
+        configuration.memory.hide_indicator = true;
+        configuration.network.hide_indicator = true;
+        configuration.disk.hide_indicator = true;
+        // The file monitor also stays stopped while no file is configured
+        configuration.file.hide_indicator = false;
+        configuration.file.file_path = String::new();
+        configuration.power.hide_indicator = true;
+        configuration.paging.hide_indicator = true;
+        configuration.arc.hide_indicator = true;
+        configuration.raid.hide_indicator = true;
+
+        let schedule = registry.start_monitors(&configuration, &SensorRoot::new("/"), Instant::now());
+
+        assert_eq!(schedule.next_poll(), None);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::monitors::monitor::{Monitor, MonitorResult};
+use crate::sensors::sensor_error::SensorError;
+use cosmic::iced::window;
+use std::time::{Duration, Instant};
+
+struct ScheduledMonitor {
+    id: window::Id,
+    update_interval: Duration,
+    next_poll: Instant,
+    monitor: Box<dyn Monitor>,
+}
+
+/// Polls each running monitor on its own update interval
+#[derive(Default)]
+pub struct MonitorSchedule {
+    monitors: Vec<ScheduledMonitor>,
+}
+
+impl MonitorSchedule {
+    pub fn new() -> Self {
+        Self { monitors: Vec::new() }
+    }
+
+    /// Adds a monitor which is first polled at `now`
+    pub fn add(&mut self, id: window::Id, update_interval: Duration, monitor: Box<dyn Monitor>, now: Instant) {
+        self.monitors.push(ScheduledMonitor {
+            id,
+            update_interval,
+            next_poll: now,
+            monitor,
+        });
+    }
+
+    /// When the next monitor is due to be polled, or None if there are no monitors left
+    pub fn next_poll(&self) -> Option<Instant> {
+        self.monitors.iter().map(|scheduled| scheduled.next_poll).min()
+    }
+
+    /// Polls every monitor that is due and returns the results by monitor id. A monitor that
+    /// fails permanently is dropped, as polling it again would only fail again.
+    pub fn poll_due(&mut self, now: Instant) -> Vec<(window::Id, MonitorResult)> {
+        let mut results = Vec::new();
+
+        self.monitors.retain_mut(|scheduled| {
+            if scheduled.next_poll > now {
+                return true;
+            }
+
+            // A poll that ran late, i.e. after a suspend, doesn't cause a burst of catch up polls
+            scheduled.next_poll += scheduled.update_interval;
+            if scheduled.next_poll <= now {
+                scheduled.next_poll = now + scheduled.update_interval;
+            }
+
+            let result = scheduled.monitor.poll();
+            let keep = !result.as_ref().is_err_and(SensorError::is_permanent);
+
+            results.push((scheduled.id, result));
+            keep
+        });
+
+        results
+    }
+
+    pub fn reset(&mut self) {
+        for scheduled in self.monitors.iter_mut() {
+            scheduled.monitor.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{AppState, Message};
+    use crate::core::metric_history::MetricValue;
+    use crate::monitors::monitor::MonitorStats;
+    use cosmic::Element;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct MockStats;
+
+    impl MonitorStats for MockStats {
+        fn draw<'app>(&self, _app_state: &'app AppState, _horizontal: bool) -> Option<Element<'app, Message>> {
+            None
+        }
+
+        fn metrics(&self) -> Vec<MetricValue> {
+            Vec::new()
+        }
+    }
+
+    struct MockMonitor {
+        results: Vec<Result<(), SensorError>>,
+        polls: Arc<AtomicUsize>,
+        resets: Arc<AtomicUsize>,
+    }
+
+    impl MockMonitor {
+        fn create(results: Vec<Result<(), SensorError>>) -> (Box<dyn Monitor>, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+            let polls = Arc::new(AtomicUsize::new(0));
+            let resets = Arc::new(AtomicUsize::new(0));
+            let monitor = Box::new(Self {
+                results,
+                polls: polls.clone(),
+                resets: resets.clone(),
+            });
+
+            (monitor, polls, resets)
+        }
+    }
+
+    impl Monitor for MockMonitor {
+        fn poll(&mut self) -> MonitorResult {
+            let i = self.polls.fetch_add(1, Ordering::SeqCst);
+            self.results[i.min(self.results.len() - 1)]
+                .clone()
+                .map(|_| Arc::new(MockStats) as Arc<dyn MonitorStats>)
+        }
+
+        fn reset(&mut self) {
+            self.resets.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn monitors_are_polled_on_their_own_intervals() {
+        let start = Instant::now();
+        let fast_id = window::Id::unique();
+        let slow_id = window::Id::unique();
+        let (fast, fast_polls, _) = MockMonitor::create(vec![Ok(())]);
+        let (slow, slow_polls, _) = MockMonitor::create(vec![Ok(())]);
+
+        let mut schedule = MonitorSchedule::new();
+        schedule.add(fast_id, Duration::from_secs(1), fast, start);
+        schedule.add(slow_id, Duration::from_secs(3), slow, start);
+
+        assert_eq!(schedule.poll_due(start).len(), 2);
+        assert_eq!(schedule.next_poll(), Some(start + Duration::from_secs(1)));
+
+        let results = schedule.poll_due(start + Duration::from_secs(1));
+        assert_eq!(results.iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![fast_id]);
+
+        schedule.poll_due(start + Duration::from_secs(2));
+        schedule.poll_due(start + Duration::from_secs(3));
+
+        assert_eq!(fast_polls.load(Ordering::SeqCst), 4);
+        assert_eq!(slow_polls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn late_poll_does_not_catch_up() {
+        let start = Instant::now();
+        let (monitor, polls, _) = MockMonitor::create(vec![Ok(())]);
+
+        let mut schedule = MonitorSchedule::new();
+        schedule.add(window::Id::unique(), Duration::from_secs(1), monitor, start);
+        schedule.poll_due(start);
+        schedule.poll_due(start + Duration::from_secs(10));
+
+        assert_eq!(schedule.next_poll(), Some(start + Duration::from_secs(11)));
+        assert!(schedule.poll_due(start + Duration::from_millis(10500)).is_empty());
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn permanent_failure_stops_the_monitor() {
+        let start = Instant::now();
+        let (transient, _, _) = MockMonitor::create(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let (permanent, _, _) = MockMonitor::create(vec![Err(SensorError::Unavailable("gone".to_string()))]);
+
+        let mut schedule = MonitorSchedule::new();
+        schedule.add(window::Id::unique(), Duration::from_secs(1), transient, start);
+        schedule.add(window::Id::unique(), Duration::from_secs(1), permanent, start);
+
+        assert_eq!(schedule.poll_due(start).len(), 2);
+        assert_eq!(schedule.poll_due(start + Duration::from_secs(1)).len(), 1);
+    }
+
+    #[test]
+    fn reset_resets_every_monitor() {
+        let start = Instant::now();
+        let (first, _, first_resets) = MockMonitor::create(vec![Ok(())]);
+        let (second, _, second_resets) = MockMonitor::create(vec![Ok(())]);
+
+        let mut schedule = MonitorSchedule::new();
+        schedule.add(window::Id::unique(), Duration::from_secs(1), first, start);
+        schedule.add(window::Id::unique(), Duration::from_secs(5), second, start);
+        schedule.reset();
+
+        assert_eq!(first_resets.load(Ordering::SeqCst), 1);
+        assert_eq!(second_resets.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn empty_schedule_has_no_next_poll() {
+        assert_eq!(MonitorSchedule::new().next_poll(), None);
+    }
+}
@@ -1,96 +1,210 @@
-use crate::configuration::app_configuration::AppConfiguration;
+use crate::alerts::alert_evaluator::AlertValue;
+use crate::configuration::alert_rule::AlertMetric;
+use crate::configuration::app_configuration::{AppConfiguration, NETWORK_DETAIL_WINDOW_ID, NETWORK_SETTINGS_WINDOW_ID};
+use crate::configuration::network::NetworkConfiguration;
+use crate::monitors::device_counters::DeviceCounters;
 use crate::sensors::proc_net_dev_reader::ProcNetDevStatus;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
 use log::info;
-use std::cmp;
-use std::collections::VecDeque;
-
-#[derive(Debug, Clone, Default)]
-struct NetworkSample {
+use std::time::{Duration, Instant};
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::proc_net_dev_reader::ProcNetDevReader;
+use crate::sensors::sensor_root::SensorRoot;
+use cosmic::iced::window;
+use std::sync::Arc;
+
+/// The metric history names of the transfer rates, in bytes per second
+pub const NETWORK_RX_METRIC: &str = "network-rx-bytes-per-second";
+pub const NETWORK_TX_METRIC: &str = "network-tx-bytes-per-second";
+
+/// The transfer rates of one interface since the previous poll, in bytes per second
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkInterfaceStats {
+    pub name: String,
     pub rx_bytes: u64,
     pub tx_bytes: u64,
 }
 
-impl NetworkSample {
-    pub fn new(rx_bytes: u64, tx_bytes: u64) -> Self {
-        Self { rx_bytes, tx_bytes }
-    }
-}
-
+/// The average transfer rates, in bytes per second
 #[derive(Debug, Clone, Default)]
 pub struct NetworkStats {
     pub rx_bytes: u64,
     pub tx_bytes: u64,
+    /// The rates of each physical interface, which aren't smoothed, sorted by name
+    pub interfaces: Vec<NetworkInterfaceStats>,
+    /// Which of the configured thresholds the busier of the smoothed rates has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl NetworkStats {
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![
+            MetricValue::new(NETWORK_RX_METRIC, self.rx_bytes as f64),
+            MetricValue::new(NETWORK_TX_METRIC, self.tx_bytes as f64),
+        ]
+    }
+
+    pub fn alert_values(&self) -> Vec<AlertValue> {
+        vec![
+            AlertValue::new(AlertMetric::NetworkReceived, self.rx_bytes as f64),
+            AlertValue::new(AlertMetric::NetworkTransmitted, self.tx_bytes as f64),
+        ]
+    }
 }
 
 pub struct NetworkMonitor<S: SensorReader<Output = ProcNetDevStatus>> {
     sensor_reader: S,
-    sample_buffer: VecDeque<NetworkSample>,
-    previous_rx_bytes: u64,
-    previous_tx_bytes: u64,
-    max_samples: usize,
+    /// Smooths the rx and tx rates, each weighted by the time since the previous poll
+    smoother: Smoother<2>,
+    previous_poll: Option<Instant>,
+    /// The rx and tx byte counters of each physical interface
+    device_counters: DeviceCounters<2>,
+    thresholds: ThresholdTracker,
 }
 
 impl<S: SensorReader<Output = ProcNetDevStatus>> NetworkMonitor<S> {
-    pub fn new(sensor_reader: S, configuration: &AppConfiguration) -> Self {
+    pub fn new(sensor_reader: S, configuration: &NetworkConfiguration) -> Self {
         info!("Creating new network monitor {:?}", configuration);
         Self {
             sensor_reader,
-            sample_buffer: VecDeque::with_capacity(configuration.memory.max_samples),
-            previous_rx_bytes: 0,
-            previous_tx_bytes: 0,
-            max_samples: configuration.memory.max_samples,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous_poll: None,
+            device_counters: DeviceCounters::default(),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
         }
     }
 
-    pub fn poll(&mut self) -> Result<NetworkStats, String> {
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous_poll = None;
+        self.device_counters.clear();
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<NetworkStats, SensorError> {
+        self.poll_at(Instant::now())
+    }
+
+    fn poll_at(&mut self, now: Instant) -> Result<NetworkStats, SensorError> {
         let current = match self.sensor_reader.read() {
             Ok(value) => value,
             Err(err) => return Err(err),
         };
 
-        let mut current_rx_total = 0u64;
-        let mut current_tx_total = 0u64;
+        let [delta_rx, delta_tx] = self.device_counters.update(
+            current
+                .device_statuses
+                .into_iter()
+                .filter(|device_status| device_status.is_physical_device)
+                .map(|device_status| (device_status.device_name, [device_status.rx_bytes, device_status.tx_bytes])),
+        );
+
+        // The first poll only sets the baseline, as there is nothing to compare it to
+        let Some(previous_poll) = self.previous_poll.replace(now) else {
+            return Ok(NetworkStats::default());
+        };
 
-        for device_status in current.device_statuses {
-            if !device_status.is_physical_device {
-                continue;
-            }
+        let elapsed_seconds = now.saturating_duration_since(previous_poll).as_secs_f64();
 
-            current_rx_total += device_status.rx_bytes;
-            current_tx_total += device_status.tx_bytes;
+        // Two polls at the same instant have no rate to report
+        if elapsed_seconds == 0.0 {
+            return Ok(NetworkStats::default());
         }
 
-        // If no previous samples, use current values as previous to prevent big deltas
-        if (self.previous_rx_bytes == 0) && (self.previous_tx_bytes == 0) {
-            self.previous_rx_bytes = current_rx_total;
-            self.previous_tx_bytes = current_tx_total;
-        }
+        // Weighting each rate by its elapsed time makes the moving average the total bytes over
+        // the total time, so a late poll doesn't count the same as a punctual one
+        let [rx_rate, tx_rate] = self.smoother.push_weighted(
+            now,
+            [delta_rx as f64 / elapsed_seconds, delta_tx as f64 / elapsed_seconds],
+            elapsed_seconds,
+        );
+
+        let interfaces = self
+            .device_counters
+            .device_deltas()
+            .iter()
+            .map(|(name, [delta_rx, delta_tx])| NetworkInterfaceStats {
+                name: name.clone(),
+                rx_bytes: (*delta_rx as f64 / elapsed_seconds) as u64,
+                tx_bytes: (*delta_tx as f64 / elapsed_seconds) as u64,
+            })
+            .collect();
+
+        // The thresholds are in KiB/s
+        let threshold_level = self.thresholds.update(rx_rate.max(tx_rate) / 1024.0);
 
-        let delta_rx = cmp::max(current_rx_total - self.previous_rx_bytes, 0);
-        let delta_tx = cmp::max(current_tx_total - self.previous_tx_bytes, 0);
+        let result = NetworkStats {
+            tx_bytes: tx_rate as u64,
+            rx_bytes: rx_rate as u64,
+            interfaces,
+            threshold_level,
+        };
 
-        self.previous_rx_bytes = current_rx_total;
-        self.previous_tx_bytes = current_tx_total;
+        Ok(result)
+    }
+}
 
-        self.sample_buffer.push_back(NetworkSample::new(delta_rx, delta_tx));
+/// The network throughput monitor
+pub struct NetworkMonitorDefinition;
 
-        if self.sample_buffer.len() > self.max_samples {
-            self.sample_buffer.pop_front();
-        };
+impl MonitorDefinition for NetworkMonitorDefinition {
+    fn id(&self) -> window::Id {
+        NETWORK_SETTINGS_WINDOW_ID.clone()
+    }
 
-        let average_rx_bytes =
-            self.sample_buffer.iter().map(|sample| sample.rx_bytes).sum::<u64>() / self.sample_buffer.len() as u64;
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(NetworkStats::default())
+    }
 
-        let average_tx_bytes =
-            self.sample_buffer.iter().map(|sample| sample.tx_bytes).sum::<u64>() / self.sample_buffer.len() as u64;
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.network)
+    }
 
-        let result = NetworkStats {
-            tx_bytes: average_tx_bytes,
-            rx_bytes: average_rx_bytes,
-        };
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.network = configuration.network.update(settings_form);
+    }
 
-        Ok(result)
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.network.update_interval
+    }
+
+    fn detail_window_id(&self) -> Option<window::Id> {
+        Some(NETWORK_DETAIL_WINDOW_ID.clone())
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.network.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(NetworkMonitor::new(ProcNetDevReader::new(sensor_root.clone()), &configuration.network)))
+    }
+}
+
+impl<S: SensorReader<Output = ProcNetDevStatus> + Send> Monitor for NetworkMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(NetworkMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        NetworkMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
     }
 }
 
@@ -101,12 +215,12 @@ mod tests {
     use std::cell::Cell;
 
     struct MockProcNetDevReader {
-        readings: Vec<Result<ProcNetDevStatus, String>>,
+        readings: Vec<Result<ProcNetDevStatus, SensorError>>,
         index: Cell<usize>,
     }
 
     impl MockProcNetDevReader {
-        fn new(readings: Vec<Result<ProcNetDevStatus, String>>) -> Self {
+        fn new(readings: Vec<Result<ProcNetDevStatus, SensorError>>) -> Self {
             Self {
                 readings,
                 index: Cell::new(0),
@@ -116,7 +230,7 @@ mod tests {
 
     impl SensorReader for MockProcNetDevReader {
         type Output = ProcNetDevStatus;
-        fn read(&self) -> Result<Self::Output, String> {
+        fn read(&self) -> Result<Self::Output, SensorError> {
             let i = self.index.get();
             let result = self.readings[i].clone();
             self.index.set(i + 1);
@@ -124,14 +238,24 @@ mod tests {
         }
     }
 
-    fn make_config(max_samples: usize) -> AppConfiguration {
-        let mut cfg = AppConfiguration::default();
-        cfg.memory.max_samples = max_samples;
+    fn make_config(max_samples: usize) -> NetworkConfiguration {
+        let mut cfg = NetworkConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg.update_interval = Duration::from_secs(1);
         cfg
     }
 
+    fn physical_status(rx: u64, tx: u64) -> ProcNetDevStatus {
+        ProcNetDevStatus::new(vec![create_device_status(rx, tx, true)])
+    }
+
     fn create_device_status(rx: u64, tx: u64, is_physical_device: bool) -> ProcNetDevDeviceStatus {
-        ProcNetDevDeviceStatus::new(rx, tx, is_physical_device)
+        let device_name = if is_physical_device { "eth0" } else { "docker0" };
+        ProcNetDevDeviceStatus::new(device_name.to_string(), rx, tx, is_physical_device)
+    }
+
+    fn create_physical_device_status(device_name: &str, rx: u64, tx: u64) -> ProcNetDevDeviceStatus {
+        ProcNetDevDeviceStatus::new(device_name.to_string(), rx, tx, true)
     }
 
     /// This should give a zero result as we don't have any previous readings to compare against.
@@ -171,16 +295,17 @@ mod tests {
         let reader = MockProcNetDevReader::new(vec![Ok(sample1), Ok(sample2)]);
 
         let mut monitor = NetworkMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
 
         // Throw away first result as we don't have any previous readings to compare against.
-        let _ = monitor.poll();
-        let result = monitor.poll();
+        let _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1));
 
         assert!(result.is_ok());
         let result = result.unwrap();
 
-        assert_eq!(result.rx_bytes, (3000 - 2000) / 2);
-        assert_eq!(result.tx_bytes, (1500 - 1000) / 2);
+        assert_eq!(result.rx_bytes, 3000 - 2000);
+        assert_eq!(result.tx_bytes, 1500 - 1000);
     }
 
     #[test]
@@ -206,20 +331,58 @@ mod tests {
         let reader = MockProcNetDevReader::new(vec![Ok(sample1), Ok(sample2), Ok(sample3), Ok(sample4)]);
 
         let mut monitor = NetworkMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let result2 = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(result2.rx_bytes, 6000 - 2000);
+        assert_eq!(result2.tx_bytes, 3000 - 1000);
 
-        _ = monitor.poll();
+        let result3 = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(result3.rx_bytes, (9000 - 2000) / 2);
+        assert_eq!(result3.tx_bytes, (6000 - 1000) / 2);
 
-        let result2 = monitor.poll().unwrap();
-        assert_eq!(result2.rx_bytes, (6000 - 2000) / 2);
-        assert_eq!(result2.tx_bytes, (3000 - 1000) / 2);
+        let result4 = monitor.poll_at(start + Duration::from_secs(3)).unwrap();
+        assert_eq!(result4.rx_bytes, (12000 - 2000) / 3);
+        assert_eq!(result4.tx_bytes, (7000 - 1000) / 3);
+    }
+
+    #[test]
+    fn interface_rates_are_reported_per_interface() {
+        let sample1 = ProcNetDevStatus::new(vec![
+            create_physical_device_status("wlan0", 1000, 1000),
+            create_physical_device_status("eth0", 2000, 1000),
+        ]);
+        let sample2 = ProcNetDevStatus::new(vec![
+            create_physical_device_status("wlan0", 1500, 1100),
+            create_physical_device_status("eth0", 6000, 2000),
+        ]);
 
-        let result3 = monitor.poll().unwrap();
-        assert_eq!(result3.rx_bytes, (9000 - 4000 + 2000) / 3);
-        assert_eq!(result3.tx_bytes, (6000 - 2000 + 1000) / 3);
+        let reader = MockProcNetDevReader::new(vec![Ok(sample1), Ok(sample2)]);
 
-        let result4 = monitor.poll().unwrap();
-        assert_eq!(result4.rx_bytes, (12000 - 3000 + 1000) / 3);
-        assert_eq!(result4.tx_bytes, (7000 - 3000 + 2000) / 3);
+        let mut monitor = NetworkMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
+
+        let first = monitor.poll_at(start).unwrap();
+        let result = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+
+        assert!(first.interfaces.is_empty());
+        assert_eq!(
+            result.interfaces,
+            vec![
+                NetworkInterfaceStats {
+                    name: "eth0".to_string(),
+                    rx_bytes: 2000,
+                    tx_bytes: 500,
+                },
+                NetworkInterfaceStats {
+                    name: "wlan0".to_string(),
+                    rx_bytes: 250,
+                    tx_bytes: 50,
+                },
+            ]
+        );
     }
 
     #[test]
@@ -232,20 +395,175 @@ mod tests {
         let reader = MockProcNetDevReader::new(vec![Ok(sample1), Ok(sample2), Ok(sample3), Ok(sample4)]);
 
         let mut monitor = NetworkMonitor::new(reader, &make_config(2));
+        let start = Instant::now();
+
+        for second in 0..4 {
+            let _ = monitor.poll_at(start + Duration::from_secs(second));
+        }
+
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn rates_are_per_second_for_any_interval() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(physical_status(0, 0)),
+            Ok(physical_status(1000, 500)),
+            Ok(physical_status(0, 0)),
+            Ok(physical_status(1000, 500)),
+        ]);
+
+        let mut fast_monitor = NetworkMonitor::new(reader, &make_config(1));
+        let start = Instant::now();
+
+        _ = fast_monitor.poll_at(start);
+        let fast = fast_monitor.poll_at(start + Duration::from_millis(250)).unwrap();
+
+        assert_eq!(fast.rx_bytes, 4000);
+        assert_eq!(fast.tx_bytes, 2000);
+
+        let reader = MockProcNetDevReader::new(vec![Ok(physical_status(0, 0)), Ok(physical_status(1000, 500))]);
+        let mut slow_monitor = NetworkMonitor::new(reader, &make_config(1));
+
+        _ = slow_monitor.poll_at(start);
+        let slow = slow_monitor.poll_at(start + Duration::from_secs(5)).unwrap();
+
+        assert_eq!(slow.rx_bytes, 200);
+        assert_eq!(slow.tx_bytes, 100);
+    }
+
+    #[test]
+    fn late_polls_are_weighted_by_elapsed_time() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(physical_status(0, 0)),
+            Ok(physical_status(1000, 1000)),
+            Ok(physical_status(2500, 2500)),
+        ]);
+
+        let mut monitor = NetworkMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        _ = monitor.poll_at(start + Duration::from_secs(1));
+        // The timer fired half a second late, so the second sample covers 1.5s
+        let result = monitor.poll_at(start + Duration::from_millis(2500)).unwrap();
+
+        assert_eq!(result.rx_bytes, 1000);
+        assert_eq!(result.tx_bytes, 1000);
+    }
+
+    #[test]
+    fn samples_older_than_the_window_are_dropped() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(physical_status(0, 0)),
+            Ok(physical_status(10000, 10000)),
+            Ok(physical_status(20000, 20000)),
+            Ok(physical_status(20800, 20400)),
+        ]);
+
+        let mut monitor = NetworkMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        _ = monitor.poll_at(start + Duration::from_secs(1));
+        _ = monitor.poll_at(start + Duration::from_secs(2));
+        let result = monitor.poll_at(start + Duration::from_secs(10)).unwrap();
+
+        assert_eq!(monitor.smoother.len(), 1);
+        assert_eq!(result.rx_bytes, 100);
+        assert_eq!(result.tx_bytes, 50);
+    }
+
+    #[test]
+    fn unplugged_interface_mid_stream_does_not_underflow() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(ProcNetDevStatus::new(vec![
+                create_physical_device_status("eth0", 1000, 1000),
+                create_physical_device_status("usb0", 50000, 40000),
+            ])),
+            Ok(ProcNetDevStatus::new(vec![
+                create_physical_device_status("eth0", 2000, 1500),
+                create_physical_device_status("usb0", 60000, 42000),
+            ])),
+            Ok(ProcNetDevStatus::new(vec![create_physical_device_status("eth0", 3000, 2000)])),
+        ]);
+
+        let mut monitor = NetworkMonitor::new(reader, &make_config(1));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let before_unplug = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(before_unplug.rx_bytes, 1000 + 10000);
+        assert_eq!(before_unplug.tx_bytes, 500 + 2000);
+
+        let after_unplug = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(after_unplug.rx_bytes, 1000);
+        assert_eq!(after_unplug.tx_bytes, 500);
+    }
+
+    #[test]
+    fn plugged_in_interface_does_not_spike() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(ProcNetDevStatus::new(vec![create_physical_device_status("eth0", 1000, 1000)])),
+            Ok(ProcNetDevStatus::new(vec![
+                create_physical_device_status("eth0", 2000, 1500),
+                create_physical_device_status("usb0", 900000, 800000),
+            ])),
+            Ok(ProcNetDevStatus::new(vec![
+                create_physical_device_status("eth0", 3000, 2000),
+                create_physical_device_status("usb0", 901000, 800100),
+            ])),
+        ]);
+
+        let mut monitor = NetworkMonitor::new(reader, &make_config(1));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let plugged_in = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+        assert_eq!(plugged_in.rx_bytes, 1000);
+        assert_eq!(plugged_in.tx_bytes, 500);
+
+        let after = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(after.rx_bytes, 1000 + 1000);
+        assert_eq!(after.tx_bytes, 500 + 100);
+    }
+
+    #[test]
+    fn reset_starts_from_a_new_baseline() {
+        let reader = MockProcNetDevReader::new(vec![
+            Ok(physical_status(0, 0)),
+            Ok(physical_status(1000, 1000)),
+            // Traffic while suspended, which must not be counted
+            Ok(physical_status(500000, 500000)),
+            Ok(physical_status(501000, 500500)),
+        ]);
+
+        let mut monitor = NetworkMonitor::new(reader, &make_config(3));
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+        _ = monitor.poll_at(start + Duration::from_secs(1));
+
+        monitor.reset();
 
-        let _ = monitor.poll();
-        let _ = monitor.poll();
-        let _ = monitor.poll();
+        let after_resume = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(after_resume.rx_bytes, 0);
+        assert_eq!(after_resume.tx_bytes, 0);
 
-        assert!(monitor.sample_buffer.len() == 2);
+        let result = monitor.poll_at(start + Duration::from_secs(3)).unwrap();
+        assert_eq!(monitor.smoother.len(), 1);
+        assert_eq!(result.rx_bytes, 1000);
+        assert_eq!(result.tx_bytes, 500);
     }
 
     #[test]
     fn error_is_propagated() {
-        let reader = MockProcNetDevReader::new(vec![Err("boom".to_string())]);
+        let reader = MockProcNetDevReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
         let mut monitor = NetworkMonitor::new(reader, &make_config(1));
 
         let err = monitor.poll().unwrap_err();
-        assert_eq!(err, "boom");
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
     }
 }
@@ -0,0 +1,416 @@
+use crate::configuration::app_configuration::{AppConfiguration, PAGING_SETTINGS_WINDOW_ID};
+use crate::configuration::paging::PagingConfiguration;
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::page_size::page_size_bytes;
+use crate::sensors::proc_vmstat_reader::{ProcVmStatReader, ProcVmStatStatus};
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use cosmic::iced::window;
+use log::{info, warn};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// The metric history names of the paging rates
+pub const PAGING_SWAP_IN_METRIC: &str = "paging-swap-in-bytes-per-second";
+pub const PAGING_SWAP_OUT_METRIC: &str = "paging-swap-out-bytes-per-second";
+pub const PAGING_MAJOR_FAULTS_METRIC: &str = "paging-major-faults-per-second";
+pub const PAGING_OOM_KILLS_METRIC: &str = "paging-oom-kills-per-second";
+
+#[derive(Debug, Clone, Default)]
+pub struct PagingStats {
+    pub swap_in_bytes_per_second: u64,
+    pub swap_out_bytes_per_second: u64,
+    pub major_faults_per_second: f64,
+    pub oom_kills_per_second: f64,
+    /// The number of processes the OOM killer has killed since the applet started
+    pub oom_kills_since_start: u64,
+    /// Set on the first poll, which only reads the counters and so has no rates yet
+    pub is_baseline: bool,
+    /// Which of the configured thresholds the busier of the smoothed swap rates has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl PagingStats {
+    /// The rates, or none on the first poll as it has nothing to measure them against
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        if self.is_baseline {
+            return Vec::new();
+        }
+
+        vec![
+            MetricValue::new(PAGING_SWAP_IN_METRIC, self.swap_in_bytes_per_second as f64),
+            MetricValue::new(PAGING_SWAP_OUT_METRIC, self.swap_out_bytes_per_second as f64),
+            MetricValue::new(PAGING_MAJOR_FAULTS_METRIC, self.major_faults_per_second),
+            MetricValue::new(PAGING_OOM_KILLS_METRIC, self.oom_kills_per_second),
+        ]
+    }
+}
+
+pub struct PagingMonitor<S: SensorReader<Output = ProcVmStatStatus>> {
+    sensor_reader: S,
+    /// Smooths the swap in pages, swap out pages, major faults and OOM kills per second, each
+    /// weighted by the time since the previous poll
+    smoother: Smoother<4>,
+    previous: Option<(Instant, ProcVmStatStatus)>,
+    /// The OOM kill count of the first poll since the applet started
+    initial_oom_kills: Arc<OnceLock<u64>>,
+    thresholds: ThresholdTracker,
+}
+
+impl<S: SensorReader<Output = ProcVmStatStatus>> PagingMonitor<S> {
+    /// Creates a monitor that counts the OOM kills from `initial_oom_kills`, which is set by the
+    /// first poll of any monitor sharing it, so a restarted monitor keeps counting from there
+    pub fn new(sensor_reader: S, configuration: &PagingConfiguration, initial_oom_kills: Arc<OnceLock<u64>>) -> Self {
+        info!("Creating new paging monitor {:?}", configuration);
+        Self {
+            sensor_reader,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous: None,
+            initial_oom_kills,
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+        }
+    }
+
+    /// Drops the baseline and samples, so the next poll starts over. OOM kills are still counted
+    /// from when the applet started.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<PagingStats, SensorError> {
+        self.poll_at(Instant::now())
+    }
+
+    fn poll_at(&mut self, now: Instant) -> Result<PagingStats, SensorError> {
+        let current = match self.sensor_reader.read() {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        };
+
+        let oom_kills_since_start = current
+            .oom_kill
+            .saturating_sub(*self.initial_oom_kills.get_or_init(|| current.oom_kill));
+
+        // The first poll only sets the baseline, as there is nothing to compare it to
+        let Some((previous_poll, previous)) = self.previous.replace((now, current.clone())) else {
+            return Ok(PagingStats {
+                oom_kills_since_start,
+                is_baseline: true,
+                ..Default::default()
+            });
+        };
+
+        let oom_kills = current.oom_kill.saturating_sub(previous.oom_kill);
+
+        if oom_kills > 0 {
+            warn!(
+                "The OOM killer has killed {} process(es) since the last poll",
+                oom_kills
+            );
+        }
+
+        let elapsed_seconds = now.saturating_duration_since(previous_poll).as_secs_f64();
+
+        // Two polls at the same instant have no rates to report
+        if elapsed_seconds == 0.0 {
+            return Ok(PagingStats {
+                oom_kills_since_start,
+                is_baseline: true,
+                ..Default::default()
+            });
+        }
+
+        let [swap_in_pages, swap_out_pages, major_faults, oom_kills_per_second] = self.smoother.push_weighted(
+            now,
+            [
+                current.pswpin.saturating_sub(previous.pswpin) as f64 / elapsed_seconds,
+                current.pswpout.saturating_sub(previous.pswpout) as f64 / elapsed_seconds,
+                current.pgmajfault.saturating_sub(previous.pgmajfault) as f64 / elapsed_seconds,
+                oom_kills as f64 / elapsed_seconds,
+            ],
+            elapsed_seconds,
+        );
+
+        // The kernel counts swapped pages rather than bytes
+        let page_size_bytes = page_size_bytes() as f64;
+        let swap_in_bytes_per_second = swap_in_pages * page_size_bytes;
+        let swap_out_bytes_per_second = swap_out_pages * page_size_bytes;
+
+        Ok(PagingStats {
+            swap_in_bytes_per_second: swap_in_bytes_per_second as u64,
+            swap_out_bytes_per_second: swap_out_bytes_per_second as u64,
+            major_faults_per_second: major_faults,
+            oom_kills_per_second,
+            oom_kills_since_start,
+            is_baseline: false,
+            // The thresholds are in KiB/s
+            threshold_level: self
+                .thresholds
+                .update(swap_in_bytes_per_second.max(swap_out_bytes_per_second) / 1024.0),
+        })
+    }
+}
+
+/// The swap and paging activity monitor
+#[derive(Default)]
+pub struct PagingMonitorDefinition {
+    /// The OOM kill count when the applet started, which outlives the monitors it creates
+    initial_oom_kills: Arc<OnceLock<u64>>,
+}
+
+impl MonitorDefinition for PagingMonitorDefinition {
+    fn id(&self) -> window::Id {
+        PAGING_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(PagingStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.paging)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.paging = configuration.paging.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.paging.update_interval
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.paging.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(PagingMonitor::new(
+            ProcVmStatReader::new(sensor_root.clone()),
+            &configuration.paging,
+            self.initial_oom_kills.clone(),
+        )))
+    }
+}
+
+impl<S: SensorReader<Output = ProcVmStatStatus> + Send> Monitor for PagingMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(PagingMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        PagingMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::thresholds::ThresholdConfiguration;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    struct MockProcVmStatReader {
+        readings: Vec<Result<ProcVmStatStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockProcVmStatReader {
+        fn new(readings: Vec<Result<ProcVmStatStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockProcVmStatReader {
+        type Output = ProcVmStatStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn make_config(max_samples: usize) -> PagingConfiguration {
+        let mut cfg = PagingConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg.update_interval = Duration::from_secs(2);
+        cfg
+    }
+
+    fn make_monitor(readings: Vec<ProcVmStatStatus>, max_samples: usize) -> PagingMonitor<MockProcVmStatReader> {
+        let reader = MockProcVmStatReader::new(readings.into_iter().map(Ok).collect());
+
+        PagingMonitor::new(reader, &make_config(max_samples), Arc::default())
+    }
+
+    /// Polls once for each reading, 2 seconds apart, and returns the last stats
+    fn poll_all(monitor: &mut PagingMonitor<MockProcVmStatReader>, polls: u64) -> PagingStats {
+        let start = Instant::now();
+
+        (0..polls)
+            .map(|poll| monitor.poll_at(start + Duration::from_secs(2 * poll)).unwrap())
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn first_poll_only_sets_the_baseline() {
+        let mut monitor = make_monitor(vec![ProcVmStatStatus::new(100, 200, 300, 4)], 2);
+
+        let result = monitor.poll().unwrap();
+
+        assert!(result.is_baseline);
+        assert!(result.metrics().is_empty());
+        assert_eq!(result.oom_kills_since_start, 0);
+        assert!(monitor.smoother.is_empty());
+    }
+
+    #[test]
+    fn second_poll_gives_rates_per_second() {
+        let mut monitor = make_monitor(
+            vec![
+                ProcVmStatStatus::new(100, 200, 300, 0),
+                ProcVmStatStatus::new(120, 300, 340, 0),
+            ],
+            1,
+        );
+
+        let result = poll_all(&mut monitor, 2);
+
+        // All deltas are over a 2 second interval
+        assert_eq!(result.swap_in_bytes_per_second, 10 * page_size_bytes());
+        assert_eq!(result.swap_out_bytes_per_second, 50 * page_size_bytes());
+        assert_eq!(result.major_faults_per_second, 20.0);
+        assert_eq!(result.metrics().len(), 4);
+    }
+
+    #[test]
+    fn thresholds_are_in_kib_per_second_of_the_busier_direction() {
+        // The pages swapped out over the 2 second interval for the rate in KiB/s
+        let pages = |kib_per_second: u64| kib_per_second * 1024 * 2 / page_size_bytes();
+        let reader = MockProcVmStatReader::new(vec![
+            Ok(ProcVmStatStatus::new(0, 0, 0, 0)),
+            Ok(ProcVmStatStatus::new(0, pages(200), 0, 0)),
+            Ok(ProcVmStatStatus::new(0, pages(1200), 0, 0)),
+        ]);
+        let mut config = make_config(1);
+        config.thresholds = ThresholdConfiguration::new(100, 1000, 0);
+        let mut monitor = PagingMonitor::new(reader, &config, Arc::default());
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+
+        assert_eq!(
+            monitor.poll_at(start + Duration::from_secs(2)).unwrap().threshold_level,
+            ThresholdLevel::Warning
+        );
+        assert_eq!(
+            monitor.poll_at(start + Duration::from_secs(4)).unwrap().threshold_level,
+            ThresholdLevel::Critical
+        );
+    }
+
+    #[test]
+    fn rates_are_measured_over_the_elapsed_time() {
+        let mut monitor = make_monitor(
+            vec![ProcVmStatStatus::new(0, 0, 0, 0), ProcVmStatStatus::new(0, 0, 50, 0)],
+            1,
+        );
+
+        // A poll that runs late measures the faults over the time that really passed
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(5)).unwrap();
+
+        assert_eq!(result.major_faults_per_second, 10.0);
+    }
+
+    #[test]
+    fn oom_kills_are_counted_from_start() {
+        let mut monitor = make_monitor(
+            vec![
+                ProcVmStatStatus::new(0, 0, 0, 3),
+                ProcVmStatStatus::new(0, 0, 0, 3),
+                ProcVmStatStatus::new(0, 0, 0, 5),
+                ProcVmStatStatus::new(0, 0, 0, 5),
+            ],
+            1,
+        );
+        let start = Instant::now();
+
+        _ = monitor.poll_at(start);
+
+        let before_kill = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+        assert_eq!(before_kill.oom_kills_since_start, 0);
+
+        let after_kill = monitor.poll_at(start + Duration::from_secs(4)).unwrap();
+        assert_eq!(after_kill.oom_kills_since_start, 2);
+        assert_eq!(after_kill.oom_kills_per_second, 1.0);
+
+        let later = monitor.poll_at(start + Duration::from_secs(6)).unwrap();
+        assert_eq!(later.oom_kills_since_start, 2);
+        assert_eq!(later.oom_kills_per_second, 0.0);
+    }
+
+    #[test]
+    fn oom_kills_are_counted_across_monitor_restarts() {
+        let initial_oom_kills = Arc::default();
+        let reader = |oom_kill| MockProcVmStatReader::new(vec![Ok(ProcVmStatStatus::new(0, 0, 0, oom_kill))]);
+
+        let mut first = PagingMonitor::new(reader(3), &make_config(1), Arc::clone(&initial_oom_kills));
+        _ = first.poll();
+
+        let mut restarted = PagingMonitor::new(reader(5), &make_config(1), initial_oom_kills);
+        let result = restarted.poll().unwrap();
+
+        assert_eq!(result.oom_kills_since_start, 2);
+    }
+
+    #[test]
+    fn samples_buffer_trims_to_max_size() {
+        let mut monitor = make_monitor(
+            vec![
+                ProcVmStatStatus::new(0, 0, 0, 0),
+                ProcVmStatStatus::new(10, 0, 0, 0),
+                ProcVmStatStatus::new(20, 0, 0, 0),
+                ProcVmStatStatus::new(30, 0, 0, 0),
+            ],
+            2,
+        );
+
+        poll_all(&mut monitor, 4);
+
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn error_is_propagated() {
+        let reader = MockProcVmStatReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let mut monitor = PagingMonitor::new(reader, &make_config(1), Arc::default());
+
+        let err = monitor.poll().unwrap_err();
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+}
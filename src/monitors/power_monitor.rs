@@ -0,0 +1,493 @@
+use crate::configuration::app_configuration::{AppConfiguration, POWER_SETTINGS_WINDOW_ID};
+use crate::configuration::power::PowerConfiguration;
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::monitors::smoothing::{Smoother, SmoothingWindow};
+use crate::monitors::thresholds::{ThresholdLevel, ThresholdTracker};
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use crate::sensors::sys_powercap_reader::{PowercapAccess, SysPowercapReader, SysPowercapStatus};
+use cosmic::iced::window;
+use log::info;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const MICROJOULES_PER_JOULE: f64 = 1_000_000.0;
+
+/// The metric history names of the power of each RAPL domain, in watts
+pub const POWER_PACKAGE_METRIC: &str = "power-package-watts";
+pub const POWER_CORE_METRIC: &str = "power-core-watts";
+pub const POWER_UNCORE_METRIC: &str = "power-uncore-watts";
+pub const POWER_DRAM_METRIC: &str = "power-dram-watts";
+
+/// The RAPL domains reported by the power monitor
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RaplDomain {
+    Package,
+    Core,
+    Uncore,
+    Dram,
+}
+
+impl RaplDomain {
+    fn from_zone_name(name: &str) -> Option<Self> {
+        match name {
+            name if name.starts_with("package") => Some(RaplDomain::Package),
+            "core" => Some(RaplDomain::Core),
+            "uncore" => Some(RaplDomain::Uncore),
+            "dram" => Some(RaplDomain::Dram),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct PowerSample {
+    pub package_watts: Option<f64>,
+    pub core_watts: Option<f64>,
+    pub uncore_watts: Option<f64>,
+    pub dram_watts: Option<f64>,
+}
+
+impl PowerSample {
+    /// Adds the watts to the domain total, i.e. to sum the package power of multi-socket systems
+    fn add(&mut self, domain: RaplDomain, watts: f64) {
+        let total = match domain {
+            RaplDomain::Package => &mut self.package_watts,
+            RaplDomain::Core => &mut self.core_watts,
+            RaplDomain::Uncore => &mut self.uncore_watts,
+            RaplDomain::Dram => &mut self.dram_watts,
+        };
+
+        *total = Some(total.unwrap_or_default() + watts);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PowerStats {
+    pub access: PowercapAccess,
+    pub package_watts: Option<f64>,
+    pub core_watts: Option<f64>,
+    pub uncore_watts: Option<f64>,
+    pub dram_watts: Option<f64>,
+    /// Which of the configured thresholds the smoothed package power has crossed
+    pub threshold_level: ThresholdLevel,
+}
+
+impl PowerStats {
+    pub fn unavailable(access: PowercapAccess) -> Self {
+        Self {
+            access,
+            ..Default::default()
+        }
+    }
+
+    /// The power of each domain the system has
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        [
+            (POWER_PACKAGE_METRIC, self.package_watts),
+            (POWER_CORE_METRIC, self.core_watts),
+            (POWER_UNCORE_METRIC, self.uncore_watts),
+            (POWER_DRAM_METRIC, self.dram_watts),
+        ]
+        .into_iter()
+        .filter_map(|(name, watts)| watts.map(|watts| MetricValue::new(name, watts)))
+        .collect()
+    }
+}
+
+pub struct PowerMonitor<S: SensorReader<Output = SysPowercapStatus>> {
+    sensor_reader: S,
+    /// Smooths the watts of each domain, each weighted by the time since the previous poll
+    smoother: Smoother<4>,
+    previous_poll: Option<Instant>,
+    previous_energy_uj: HashMap<String, u64>,
+    thresholds: ThresholdTracker,
+}
+
+impl<S: SensorReader<Output = SysPowercapStatus>> PowerMonitor<S> {
+    pub fn new(sensor_reader: S, configuration: &PowerConfiguration) -> Self {
+        info!("Creating new power monitor {:?}", configuration);
+        Self {
+            sensor_reader,
+            smoother: Smoother::new(
+                configuration.smoothing,
+                SmoothingWindow::new(
+                    configuration.max_samples,
+                    configuration.update_interval,
+                    configuration.smoothing_window,
+                ),
+            ),
+            previous_poll: None,
+            previous_energy_uj: HashMap::new(),
+            thresholds: ThresholdTracker::new(configuration.thresholds),
+        }
+    }
+
+    /// Drops the baseline and samples, so the next poll starts over as if the monitor was new
+    pub fn reset(&mut self) {
+        self.previous_poll = None;
+        self.previous_energy_uj.clear();
+        self.smoother.clear();
+        self.thresholds.clear();
+    }
+
+    pub fn poll(&mut self) -> Result<PowerStats, SensorError> {
+        self.poll_at(Instant::now())
+    }
+
+    fn poll_at(&mut self, now: Instant) -> Result<PowerStats, SensorError> {
+        let current = match self.sensor_reader.read() {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        };
+
+        if current.access != PowercapAccess::Readable {
+            self.reset();
+            return Ok(PowerStats::unavailable(current.access));
+        }
+
+        let mut deltas_uj = Vec::new();
+
+        for zone in current.zones {
+            let Some(domain) = RaplDomain::from_zone_name(&zone.name) else {
+                continue;
+            };
+
+            // A zone without a previous reading, i.e. on the first poll, has used nothing yet
+            let previous_energy_uj = self
+                .previous_energy_uj
+                .insert(zone.zone_id, zone.energy_uj)
+                .unwrap_or(zone.energy_uj);
+
+            let delta_uj = energy_delta(previous_energy_uj, zone.energy_uj, zone.max_energy_range_uj);
+
+            deltas_uj.push((domain, delta_uj));
+        }
+
+        // The first poll only sets the baseline, as there is nothing to compare it to
+        let Some(previous_poll) = self.previous_poll.replace(now) else {
+            return Ok(PowerStats::default());
+        };
+
+        let elapsed_seconds = now.saturating_duration_since(previous_poll).as_secs_f64();
+
+        // Two polls at the same instant have no power to report
+        if elapsed_seconds == 0.0 {
+            return Ok(PowerStats::default());
+        }
+
+        let mut sample = PowerSample::default();
+
+        for (domain, delta_uj) in deltas_uj {
+            sample.add(domain, delta_uj as f64 / MICROJOULES_PER_JOULE / elapsed_seconds);
+        }
+
+        // A domain the system doesn't have is smoothed as zero but reported as missing. Weighting
+        // each sample by its elapsed time makes the average the energy over the total time.
+        let [package_watts, core_watts, uncore_watts, dram_watts] = self.smoother.push_weighted(
+            now,
+            [
+                sample.package_watts.unwrap_or_default(),
+                sample.core_watts.unwrap_or_default(),
+                sample.uncore_watts.unwrap_or_default(),
+                sample.dram_watts.unwrap_or_default(),
+            ],
+            elapsed_seconds,
+        );
+
+        let package_watts = sample.package_watts.and(Some(package_watts));
+
+        Ok(PowerStats {
+            access: PowercapAccess::Readable,
+            package_watts,
+            core_watts: sample.core_watts.and(Some(core_watts)),
+            uncore_watts: sample.uncore_watts.and(Some(uncore_watts)),
+            dram_watts: sample.dram_watts.and(Some(dram_watts)),
+            threshold_level: package_watts
+                .map(|watts| self.thresholds.update(watts))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The energy counter wraps back to zero once it passes max_energy_range_uj.
+fn energy_delta(previous_uj: u64, current_uj: u64, max_energy_range_uj: u64) -> u64 {
+    if current_uj >= previous_uj {
+        current_uj - previous_uj
+    } else {
+        max_energy_range_uj
+            .saturating_sub(previous_uj)
+            .saturating_add(current_uj)
+    }
+}
+
+/// The RAPL power usage monitor
+pub struct PowerMonitorDefinition;
+
+impl MonitorDefinition for PowerMonitorDefinition {
+    fn id(&self) -> window::Id {
+        POWER_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(PowerStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.power)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.power = configuration.power.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.power.update_interval
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.power.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(PowerMonitor::new(
+            SysPowercapReader::new(sensor_root.clone()),
+            &configuration.power,
+        )))
+    }
+}
+
+impl<S: SensorReader<Output = SysPowercapStatus> + Send> Monitor for PowerMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(PowerMonitor::poll(self)?))
+    }
+
+    fn reset(&mut self) {
+        PowerMonitor::reset(self);
+    }
+
+    fn smoothing_window(&self) -> Option<SmoothingWindow> {
+        Some(self.smoother.window())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::thresholds::ThresholdConfiguration;
+    use crate::sensors::sys_powercap_reader::RaplZoneStatus;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    const MAX_RANGE_UJ: u64 = 262_143_328_850;
+
+    struct MockSysPowercapReader {
+        readings: Vec<Result<SysPowercapStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockSysPowercapReader {
+        fn new(readings: Vec<Result<SysPowercapStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockSysPowercapReader {
+        type Output = SysPowercapStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn make_config(max_samples: usize) -> PowerConfiguration {
+        let mut cfg = PowerConfiguration::default();
+        cfg.max_samples = max_samples;
+        cfg.update_interval = Duration::from_secs(1);
+        cfg
+    }
+
+    fn make_zone(zone_id: &str, name: &str, energy_uj: u64) -> RaplZoneStatus {
+        RaplZoneStatus::new(zone_id.to_string(), name.to_string(), energy_uj, MAX_RANGE_UJ)
+    }
+
+    fn make_status(zones: Vec<RaplZoneStatus>) -> SysPowercapStatus {
+        SysPowercapStatus::new(PowercapAccess::Readable, zones)
+    }
+
+    #[test]
+    fn first_poll_only_sets_the_baseline() {
+        let reader = MockSysPowercapReader::new(vec![Ok(make_status(vec![make_zone(
+            "intel-rapl:0",
+            "package-0",
+            5_000_000,
+        )]))]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(2));
+
+        let result = monitor.poll().unwrap();
+
+        assert_eq!(result.package_watts, None);
+        assert_eq!(result.dram_watts, None);
+        assert!(result.metrics().is_empty());
+        assert!(monitor.smoother.is_empty());
+    }
+
+    #[test]
+    fn second_poll_gives_watts_per_domain() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 5_000_000),
+                make_zone("intel-rapl:0:0", "core", 1_000_000),
+                make_zone("intel-rapl:0:1", "uncore", 100_000),
+                make_zone("intel-rapl:0:2", "dram", 200_000),
+            ])),
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 17_000_000),
+                make_zone("intel-rapl:0:0", "core", 9_000_000),
+                make_zone("intel-rapl:0:1", "uncore", 600_000),
+                make_zone("intel-rapl:0:2", "dram", 1_700_000),
+            ])),
+        ]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(1));
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+
+        assert_eq!(result.access, PowercapAccess::Readable);
+        assert_eq!(result.package_watts, Some(12.0));
+        assert_eq!(result.core_watts, Some(8.0));
+        assert_eq!(result.uncore_watts, Some(0.5));
+        assert_eq!(result.dram_watts, Some(1.5));
+    }
+
+    #[test]
+    fn thresholds_are_in_watts_of_the_package() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 0),
+                make_zone("intel-rapl:0:2", "dram", 0),
+            ])),
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 30_000_000),
+                make_zone("intel-rapl:0:2", "dram", 90_000_000),
+            ])),
+        ]);
+        let mut config = make_config(1);
+        config.thresholds = ThresholdConfiguration::new(25, 50, 0);
+        let mut monitor = PowerMonitor::new(reader, &config);
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+
+        // The 90W of the dram domain is not compared to the thresholds
+        assert_eq!(result.threshold_level, ThresholdLevel::Warning);
+    }
+
+    #[test]
+    fn packages_are_summed_across_sockets() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 0),
+                make_zone("intel-rapl:1", "package-1", 0),
+            ])),
+            Ok(make_status(vec![
+                make_zone("intel-rapl:0", "package-0", 10_000_000),
+                make_zone("intel-rapl:1", "package-1", 20_000_000),
+            ])),
+        ]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(1));
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+
+        assert_eq!(result.package_watts, Some(30.0));
+    }
+
+    #[test]
+    fn counter_wraparound_is_handled() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![make_zone(
+                "intel-rapl:0",
+                "package-0",
+                MAX_RANGE_UJ - 2_000_000,
+            )])),
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 3_000_000)])),
+        ]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(1));
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(1)).unwrap();
+
+        assert_eq!(result.package_watts, Some(5.0));
+    }
+
+    #[test]
+    fn multiple_polls_are_averaged() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 0)])),
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 10_000_000)])),
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 30_000_000)])),
+        ]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(2));
+
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        _ = monitor.poll_at(start + Duration::from_secs(1));
+        let result = monitor.poll_at(start + Duration::from_secs(2)).unwrap();
+
+        assert_eq!(result.package_watts, Some(15.0));
+        assert!(monitor.smoother.len() == 2);
+    }
+
+    #[test]
+    fn watts_are_measured_over_the_elapsed_time() {
+        let reader = MockSysPowercapReader::new(vec![
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 0)])),
+            Ok(make_status(vec![make_zone("intel-rapl:0", "package-0", 10_000_000)])),
+        ]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(1));
+
+        // A poll that runs late measures the energy over the time that really passed
+        let start = Instant::now();
+        _ = monitor.poll_at(start);
+        let result = monitor.poll_at(start + Duration::from_secs(4)).unwrap();
+
+        assert_eq!(result.package_watts, Some(2.5));
+    }
+
+    #[test]
+    fn permission_denied_is_a_permanent_error() {
+        let denied = SensorError::PermissionDenied("energy_uj is only readable by root".to_string());
+        let reader = MockSysPowercapReader::new(vec![Err(denied.clone())]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(2));
+
+        let err = monitor.poll().unwrap_err();
+
+        // The schedule stops polling it, so the indicator shows the unavailable placeholder
+        assert_eq!(err, denied);
+        assert!(err.is_permanent());
+    }
+
+    #[test]
+    fn error_is_propagated() {
+        let reader = MockSysPowercapReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let mut monitor = PowerMonitor::new(reader, &make_config(1));
+
+        let err = monitor.poll().unwrap_err();
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+}
@@ -0,0 +1,318 @@
+use crate::configuration::app_configuration::{AppConfiguration, RAID_SETTINGS_WINDOW_ID};
+use crate::configuration::raid::RaidConfiguration;
+use crate::core::metric_history::MetricValue;
+use crate::core::settings::SettingsForm;
+use crate::monitors::monitor::{Monitor, MonitorDefinition, MonitorResult, MonitorStats};
+use crate::sensors::proc_mdstat_reader::{MdArrayStatus, ProcMdStatReader, ProcMdStatStatus};
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use cosmic::iced::window;
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CHECK_SYNC_ACTION: &str = "check";
+
+/// The metric history name of the number of arrays that are not healthy
+pub const RAID_UNHEALTHY_ARRAYS_METRIC: &str = "raid-unhealthy-arrays";
+
+/// The overall health of an md array, ordered from best to worst
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub enum RaidHealth {
+    /// All members are working. A routine check (scrub) still counts as healthy.
+    #[default]
+    Healthy,
+    /// The array is resyncing, recovering onto a new member or reshaping
+    Rebuilding,
+    /// One or more members are missing or failed and nothing is rebuilding them
+    Degraded,
+    /// The array is not assembled
+    Inactive,
+}
+
+impl RaidHealth {
+    fn from_array(array: &MdArrayStatus) -> Self {
+        let is_rebuilding = array
+            .sync_action
+            .as_deref()
+            .is_some_and(|action| action != CHECK_SYNC_ACTION);
+
+        if !array.active {
+            RaidHealth::Inactive
+        } else if is_rebuilding {
+            RaidHealth::Rebuilding
+        } else if array.working_members < array.expected_members || array.failed_members > 0 {
+            RaidHealth::Degraded
+        } else {
+            RaidHealth::Healthy
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RaidArrayStats {
+    pub name: String,
+    pub health: RaidHealth,
+    /// The member health map, i.e. "UU" or "U_"
+    pub member_health: String,
+    /// The rebuild progress as a percentage, while rebuilding
+    pub rebuild_progress: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RaidStats {
+    pub arrays: Vec<RaidArrayStats>,
+}
+
+impl RaidStats {
+    /// The worst health across all arrays, healthy if there are none
+    pub fn health(&self) -> RaidHealth {
+        self.arrays.iter().map(|array| array.health).max().unwrap_or_default()
+    }
+
+    /// The arrays that are not healthy
+    pub fn unhealthy_arrays(&self) -> impl Iterator<Item = &RaidArrayStats> {
+        self.arrays.iter().filter(|array| array.health != RaidHealth::Healthy)
+    }
+
+    pub fn metrics(&self) -> Vec<MetricValue> {
+        vec![MetricValue::new(
+            RAID_UNHEALTHY_ARRAYS_METRIC,
+            self.unhealthy_arrays().count() as f64,
+        )]
+    }
+}
+
+/// Unlike the other monitors there is nothing to average, so each poll reports the latest state.
+pub struct RaidMonitor<S: SensorReader<Output = ProcMdStatStatus>> {
+    sensor_reader: S,
+    previous_health: HashMap<String, RaidHealth>,
+}
+
+impl<S: SensorReader<Output = ProcMdStatStatus>> RaidMonitor<S> {
+    pub fn new(sensor_reader: S, configuration: &RaidConfiguration) -> Self {
+        info!("Creating new RAID monitor {:?}", configuration);
+        Self {
+            sensor_reader,
+            previous_health: HashMap::new(),
+        }
+    }
+
+    pub fn poll(&mut self) -> Result<RaidStats, SensorError> {
+        let current = match self.sensor_reader.read() {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        };
+
+        let arrays: Vec<RaidArrayStats> = current
+            .arrays
+            .iter()
+            .map(|array| {
+                let health = RaidHealth::from_array(array);
+
+                RaidArrayStats {
+                    name: array.name.clone(),
+                    health,
+                    member_health: array.member_health.clone(),
+                    rebuild_progress: (health == RaidHealth::Rebuilding)
+                        .then_some(array.sync_progress)
+                        .flatten(),
+                }
+            })
+            .collect();
+
+        for array in &arrays {
+            let previous = self.previous_health.insert(array.name.clone(), array.health);
+
+            if previous != Some(array.health) && array.health != RaidHealth::Healthy {
+                warn!(
+                    "RAID array {} is {:?} [{}]",
+                    array.name, array.health, array.member_health
+                );
+            }
+        }
+
+        Ok(RaidStats { arrays })
+    }
+}
+
+/// The md RAID array monitor
+pub struct RaidMonitorDefinition;
+
+impl MonitorDefinition for RaidMonitorDefinition {
+    fn id(&self) -> window::Id {
+        RAID_SETTINGS_WINDOW_ID.clone()
+    }
+
+    fn default_stats(&self) -> Arc<dyn MonitorStats> {
+        Arc::new(RaidStats::default())
+    }
+
+    fn settings_form(&self, configuration: &AppConfiguration) -> SettingsForm {
+        SettingsForm::from(&configuration.raid)
+    }
+
+    fn update_configuration(&self, configuration: &mut AppConfiguration, settings_form: &SettingsForm) {
+        configuration.raid = configuration.raid.update(settings_form);
+    }
+
+    fn update_interval(&self, configuration: &AppConfiguration) -> Duration {
+        configuration.raid.update_interval
+    }
+
+    fn create_monitor(&self, configuration: &AppConfiguration, sensor_root: &SensorRoot) -> Option<Box<dyn Monitor>> {
+        if configuration.raid.hide_indicator {
+            return None;
+        }
+
+        Some(Box::new(RaidMonitor::new(
+            ProcMdStatReader::new(sensor_root.clone()),
+            &configuration.raid,
+        )))
+    }
+}
+
+impl<S: SensorReader<Output = ProcMdStatStatus> + Send> Monitor for RaidMonitor<S> {
+    fn poll(&mut self) -> MonitorResult {
+        Ok(Arc::new(RaidMonitor::poll(self)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::app_configuration::{MAX_SAMPLES_SETTING_KEY, UPDATE_INTERVAL_SETTING_KEY};
+    use std::cell::Cell;
+
+    struct MockProcMdStatReader {
+        readings: Vec<Result<ProcMdStatStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockProcMdStatReader {
+        fn new(readings: Vec<Result<ProcMdStatStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockProcMdStatReader {
+        type Output = ProcMdStatStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn make_array(name: &str, working_members: usize, member_health: &str) -> MdArrayStatus {
+        MdArrayStatus {
+            name: name.to_string(),
+            active: true,
+            level: Some("raid1".to_string()),
+            expected_members: 2,
+            working_members,
+            member_health: member_health.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn poll_once(arrays: Vec<MdArrayStatus>) -> RaidStats {
+        let reader = MockProcMdStatReader::new(vec![Ok(ProcMdStatStatus { arrays })]);
+        let mut monitor = RaidMonitor::new(reader, &RaidConfiguration::default());
+        monitor.poll().unwrap()
+    }
+
+    #[test]
+    fn no_arrays_is_healthy() {
+        let result = poll_once(Vec::new());
+
+        assert_eq!(result.health(), RaidHealth::Healthy);
+        assert_eq!(result.unhealthy_arrays().count(), 0);
+    }
+
+    #[test]
+    fn working_mirror_is_healthy() {
+        let result = poll_once(vec![make_array("md0", 2, "UU")]);
+
+        assert_eq!(result.health(), RaidHealth::Healthy);
+    }
+
+    #[test]
+    fn missing_member_is_degraded() {
+        let result = poll_once(vec![make_array("md0", 2, "UU"), make_array("md1", 1, "U_")]);
+
+        assert_eq!(result.health(), RaidHealth::Degraded);
+
+        let unhealthy: Vec<&RaidArrayStats> = result.unhealthy_arrays().collect();
+        assert_eq!(unhealthy.len(), 1);
+        assert_eq!(unhealthy[0].name, "md1");
+        assert_eq!(unhealthy[0].member_health, "U_");
+    }
+
+    #[test]
+    fn recovery_is_rebuilding_with_progress() {
+        let mut array = make_array("md1", 1, "U_");
+        array.sync_action = Some("recovery".to_string());
+        array.sync_progress = Some(8.5);
+
+        let result = poll_once(vec![array]);
+
+        assert_eq!(result.health(), RaidHealth::Rebuilding);
+        assert_eq!(result.arrays[0].rebuild_progress, Some(8.5));
+    }
+
+    #[test]
+    fn check_is_healthy() {
+        let mut array = make_array("md0", 2, "UU");
+        array.sync_action = Some("check".to_string());
+        array.sync_progress = Some(40.0);
+
+        let result = poll_once(vec![array]);
+
+        assert_eq!(result.health(), RaidHealth::Healthy);
+        assert_eq!(result.arrays[0].rebuild_progress, None);
+    }
+
+    #[test]
+    fn inactive_is_worst() {
+        let mut inactive = make_array("md2", 0, "");
+        inactive.active = false;
+
+        let mut rebuilding = make_array("md1", 1, "U_");
+        rebuilding.sync_action = Some("resync".to_string());
+
+        let result = poll_once(vec![make_array("md0", 1, "U_"), rebuilding, inactive]);
+
+        assert_eq!(result.health(), RaidHealth::Inactive);
+        assert_eq!(result.unhealthy_arrays().count(), 3);
+    }
+
+    #[test]
+    fn error_is_propagated() {
+        let reader = MockProcMdStatReader::new(vec![Err(SensorError::Transient("boom".to_string()))]);
+        let mut monitor = RaidMonitor::new(reader, &RaidConfiguration::default());
+
+        let err = monitor.poll().unwrap_err();
+        assert_eq!(err, SensorError::Transient("boom".to_string()));
+    }
+
+    #[test]
+    fn settings_form_has_no_samples_to_set() {
+        let mut configuration = AppConfiguration::default();
+        let mut settings_form = RaidMonitorDefinition.settings_form(&configuration);
+
+        assert!(!settings_form.values.contains_key(MAX_SAMPLES_SETTING_KEY));
+
+        settings_form.values.get_mut(UPDATE_INTERVAL_SETTING_KEY).unwrap().value = "10000".to_string();
+        RaidMonitorDefinition.update_configuration(&mut configuration, &settings_form);
+
+        assert_eq!(configuration.raid.update_interval, Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,354 @@
+use crate::configuration::smoothing::SmoothingStrategy;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The span of samples a smoother combines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SmoothingWindow {
+    /// The most samples to keep, or None when the window is only limited by time
+    pub max_samples: Option<usize>,
+    /// Samples older than this are dropped. It is also how quickly the exponential moving average
+    /// and peak hold settle on a new value.
+    pub duration: Duration,
+}
+
+impl SmoothingWindow {
+    /// A window of the given length in time if one is set, otherwise of max_samples samples at the
+    /// update interval.
+    pub fn new(max_samples: usize, update_interval: Duration, duration: Option<Duration>) -> Self {
+        match duration {
+            Some(duration) => Self {
+                max_samples: None,
+                duration,
+            },
+            None => Self {
+                max_samples: Some(max_samples),
+                duration: update_interval * max_samples as u32,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SmoothingSample<const N: usize> {
+    timestamp: Instant,
+    values: [f64; N],
+    weight: f64,
+}
+
+/// Smooths N values that are sampled together, i.e. the rx and tx rates, with a configurable
+/// strategy.
+#[derive(Debug)]
+pub struct Smoother<const N: usize> {
+    strategy: SmoothingStrategy,
+    window: SmoothingWindow,
+    samples: VecDeque<SmoothingSample<N>>,
+    /// The last result and when it was produced, which the running strategies build on
+    previous: Option<(Instant, [f64; N])>,
+}
+
+impl<const N: usize> Smoother<N> {
+    pub fn new(strategy: SmoothingStrategy, window: SmoothingWindow) -> Self {
+        Self {
+            strategy,
+            window,
+            samples: VecDeque::with_capacity(window.max_samples.unwrap_or_default()),
+            previous: None,
+        }
+    }
+
+    pub fn strategy(&self) -> SmoothingStrategy {
+        self.strategy
+    }
+
+    pub fn window(&self) -> SmoothingWindow {
+        self.window
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.previous = None;
+    }
+
+    /// Adds a sample and returns the smoothed values
+    pub fn push(&mut self, timestamp: Instant, values: [f64; N]) -> [f64; N] {
+        self.push_weighted(timestamp, values, 1.0)
+    }
+
+    /// Adds a sample that counts for more or less than the others in the moving average, i.e. a
+    /// rate weighted by the time it was measured over.
+    pub fn push_weighted(&mut self, timestamp: Instant, values: [f64; N], weight: f64) -> [f64; N] {
+        self.samples.push_back(SmoothingSample {
+            timestamp,
+            values,
+            weight,
+        });
+
+        // The newest sample is always kept, so an empty window, i.e. one of zero seconds read from
+        // a hand edited configuration, leaves the values unsmoothed rather than leaving no samples
+        while self.samples.len() > 1 && self.is_oldest_outside_window(timestamp) {
+            self.samples.pop_front();
+        }
+
+        let result = match self.strategy {
+            SmoothingStrategy::MovingAverage => self.moving_average(),
+            SmoothingStrategy::ExponentialMovingAverage => self.exponential_moving_average(timestamp, values),
+            SmoothingStrategy::Median => self.median(),
+            SmoothingStrategy::PeakHold => self.peak_hold(timestamp, values),
+        };
+
+        self.previous = Some((timestamp, result));
+
+        result
+    }
+
+    /// Whether the oldest sample is one too many, or too old as of `timestamp`
+    fn is_oldest_outside_window(&self, timestamp: Instant) -> bool {
+        self.window
+            .max_samples
+            .is_some_and(|max_samples| self.samples.len() > max_samples)
+            || self
+                .samples
+                .front()
+                .is_some_and(|sample| timestamp.saturating_duration_since(sample.timestamp) >= self.window.duration)
+    }
+
+    fn moving_average(&self) -> [f64; N] {
+        let total_weight = self.samples.iter().map(|sample| sample.weight).sum::<f64>();
+
+        if total_weight <= 0.0 {
+            return [0.0; N];
+        }
+
+        std::array::from_fn(|index| {
+            self.samples
+                .iter()
+                .map(|sample| sample.values[index] * sample.weight)
+                .sum::<f64>()
+                / total_weight
+        })
+    }
+
+    fn median(&self) -> [f64; N] {
+        if self.samples.is_empty() {
+            return [0.0; N];
+        }
+
+        std::array::from_fn(|index| {
+            let mut values: Vec<f64> = self.samples.iter().map(|sample| sample.values[index]).collect();
+            values.sort_by(f64::total_cmp);
+
+            let middle = values.len() / 2;
+
+            if values.len() % 2 == 1 {
+                values[middle]
+            } else {
+                (values[middle - 1] + values[middle]) / 2.0
+            }
+        })
+    }
+
+    /// Moves the previous result towards the new values. A sample taken after a longer gap counts
+    /// for more, so the result depends on time rather than on how often the monitor is polled.
+    fn exponential_moving_average(&self, timestamp: Instant, values: [f64; N]) -> [f64; N] {
+        let Some((previous_timestamp, previous)) = self.previous else {
+            return values;
+        };
+
+        // The time constant is half the window, which gives each new sample about the same weight
+        // as a moving average over the window would.
+        let time_constant = self.window.duration.as_secs_f64() / 2.0;
+        let elapsed = timestamp.saturating_duration_since(previous_timestamp).as_secs_f64();
+        let alpha = if time_constant > 0.0 {
+            1.0 - (-elapsed / time_constant).exp()
+        } else {
+            1.0
+        };
+
+        std::array::from_fn(|index| previous[index] + alpha * (values[index] - previous[index]))
+    }
+
+    /// Follows a rise immediately, while a fall only halves the gap to the new values every window.
+    fn peak_hold(&self, timestamp: Instant, values: [f64; N]) -> [f64; N] {
+        let Some((previous_timestamp, previous)) = self.previous else {
+            return values;
+        };
+
+        let half_life = self.window.duration.as_secs_f64();
+        let elapsed = timestamp.saturating_duration_since(previous_timestamp).as_secs_f64();
+        let decay = if half_life > 0.0 {
+            0.5_f64.powf(elapsed / half_life)
+        } else {
+            0.0
+        };
+
+        std::array::from_fn(|index| values[index] + (previous[index] - values[index]).max(0.0) * decay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smoother(strategy: SmoothingStrategy, max_samples: usize) -> Smoother<1> {
+        Smoother::new(
+            strategy,
+            SmoothingWindow::new(max_samples, Duration::from_secs(1), None),
+        )
+    }
+
+    fn push_all(smoother: &mut Smoother<1>, start: Instant, values: &[f64]) -> Vec<f64> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(second, value)| smoother.push(start + Duration::from_secs(second as u64), [*value])[0])
+            .collect()
+    }
+
+    #[test]
+    fn window_from_samples_spans_the_update_intervals() {
+        let window = SmoothingWindow::new(4, Duration::from_millis(500), None);
+
+        assert_eq!(window.max_samples, Some(4));
+        assert_eq!(window.duration, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn window_in_time_has_no_sample_limit() {
+        let window = SmoothingWindow::new(4, Duration::from_millis(500), Some(Duration::from_secs(10)));
+
+        assert_eq!(window.max_samples, None);
+        assert_eq!(window.duration, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn moving_average_is_the_mean_of_the_window() {
+        let mut smoother = smoother(SmoothingStrategy::MovingAverage, 3);
+
+        let results = push_all(&mut smoother, Instant::now(), &[10.0, 20.0, 30.0, 70.0]);
+
+        assert_eq!(results, vec![10.0, 15.0, 20.0, 40.0]);
+        assert_eq!(smoother.len(), 3);
+    }
+
+    #[test]
+    fn moving_average_is_weighted() {
+        let mut smoother = smoother(SmoothingStrategy::MovingAverage, 3);
+        let start = Instant::now();
+
+        smoother.push_weighted(start, [100.0], 1.0);
+        let result = smoother.push_weighted(start + Duration::from_secs(2), [400.0], 2.0);
+
+        assert_eq!(result, [300.0]);
+    }
+
+    #[test]
+    fn median_ignores_a_single_spike() {
+        let mut smoother = smoother(SmoothingStrategy::Median, 3);
+
+        let results = push_all(&mut smoother, Instant::now(), &[10.0, 20.0, 1000.0, 30.0]);
+
+        assert_eq!(results, vec![10.0, 15.0, 20.0, 30.0]);
+    }
+
+    #[test]
+    fn empty_window_leaves_the_values_unsmoothed() {
+        let windows = [
+            SmoothingWindow::new(4, Duration::from_secs(1), Some(Duration::ZERO)),
+            SmoothingWindow::new(0, Duration::from_secs(1), None),
+        ];
+
+        for window in windows {
+            for strategy in SmoothingStrategy::ALL {
+                let mut smoother: Smoother<1> = Smoother::new(strategy, window);
+
+                let results = push_all(&mut smoother, Instant::now(), &[10.0, 20.0, 1000.0]);
+
+                assert_eq!(results, vec![10.0, 20.0, 1000.0], "{:?} over {:?}", strategy, window);
+                assert_eq!(smoother.len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn median_of_no_samples_is_zero() {
+        let smoother = smoother(SmoothingStrategy::Median, 3);
+
+        assert_eq!(smoother.median(), [0.0]);
+    }
+
+    #[test]
+    fn exponential_moving_average_follows_a_step_gradually() {
+        let mut smoother = smoother(SmoothingStrategy::ExponentialMovingAverage, 4);
+
+        let results = push_all(&mut smoother, Instant::now(), &[0.0, 100.0, 100.0, 100.0]);
+
+        assert_eq!(results[0], 0.0);
+        assert!(results[1] > 0.0 && results[1] < 100.0);
+        assert!(results[2] > results[1] && results[3] > results[2] && results[3] < 100.0);
+        // The time constant is half the 4 second window, so one second covers 1 - e^-0.5 of the step
+        assert!((results[1] - 100.0 * (1.0 - (-0.5_f64).exp())).abs() < 1e-9);
+    }
+
+    #[test]
+    fn peak_hold_rises_immediately_and_decays_by_half_each_window() {
+        let mut smoother = smoother(SmoothingStrategy::PeakHold, 2);
+        let start = Instant::now();
+
+        assert_eq!(smoother.push(start, [10.0]), [10.0]);
+        assert_eq!(smoother.push(start + Duration::from_secs(1), [90.0]), [90.0]);
+        assert_eq!(smoother.push(start + Duration::from_secs(3), [10.0]), [50.0]);
+        assert_eq!(smoother.push(start + Duration::from_secs(5), [10.0]), [30.0]);
+    }
+
+    #[test]
+    fn samples_older_than_the_window_are_dropped() {
+        let mut smoother = Smoother::new(
+            SmoothingStrategy::MovingAverage,
+            SmoothingWindow::new(1, Duration::from_secs(1), Some(Duration::from_secs(5))),
+        );
+        let start = Instant::now();
+
+        smoother.push(start, [100.0]);
+        smoother.push(start + Duration::from_secs(1), [200.0]);
+        let result = smoother.push(start + Duration::from_secs(6), [300.0]);
+
+        assert_eq!(smoother.len(), 1);
+        assert_eq!(result, [300.0]);
+    }
+
+    #[test]
+    fn values_are_smoothed_independently() {
+        let mut smoother: Smoother<2> = Smoother::new(
+            SmoothingStrategy::Median,
+            SmoothingWindow::new(3, Duration::from_secs(1), None),
+        );
+        let start = Instant::now();
+
+        smoother.push(start, [1.0, 30.0]);
+        smoother.push(start + Duration::from_secs(1), [2.0, 10.0]);
+        let result = smoother.push(start + Duration::from_secs(2), [3.0, 20.0]);
+
+        assert_eq!(result, [2.0, 20.0]);
+    }
+
+    #[test]
+    fn clear_forgets_the_previous_result() {
+        let mut smoother = smoother(SmoothingStrategy::PeakHold, 2);
+        let start = Instant::now();
+
+        smoother.push(start, [90.0]);
+        smoother.clear();
+
+        assert!(smoother.is_empty());
+        assert_eq!(smoother.push(start + Duration::from_secs(1), [10.0]), [10.0]);
+    }
+}
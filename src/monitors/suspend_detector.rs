@@ -0,0 +1,139 @@
+use crate::sensors::proc_uptime_reader::ProcUptimeStatus;
+use crate::sensors::sensor_traits::SensorReader;
+use log::{info, warn};
+use std::time::{Duration, Instant};
+
+/// How far the boot clock may run ahead of the monotonic clock between two checks before it is
+/// taken as a suspend. Large enough to cover /proc/uptime only having hundredths of a second.
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Detects a suspend by comparing the uptime, which keeps counting while suspended, with `Instant`,
+/// which doesn't. The poll intervals run on the latter, so the first poll after a resume would
+/// otherwise compare against counters from before the suspend.
+pub struct SuspendDetector<S: SensorReader<Output = ProcUptimeStatus>> {
+    sensor_reader: S,
+    previous: Option<(Instant, Duration)>,
+}
+
+impl<S: SensorReader<Output = ProcUptimeStatus>> SuspendDetector<S> {
+    pub fn new(sensor_reader: S) -> Self {
+        Self {
+            sensor_reader,
+            previous: None,
+        }
+    }
+
+    /// Whether the system has been suspended since the last check
+    pub fn has_resumed(&mut self) -> bool {
+        self.has_resumed_at(Instant::now())
+    }
+
+    fn has_resumed_at(&mut self, now: Instant) -> bool {
+        let uptime = match self.sensor_reader.read() {
+            Ok(status) => status.uptime,
+            Err(err) => {
+                warn!("Unable to check for a suspend: {}", err);
+                return false;
+            }
+        };
+
+        let Some((previous_instant, previous_uptime)) = self.previous.replace((now, uptime)) else {
+            return false;
+        };
+
+        let suspended_for = uptime
+            .saturating_sub(previous_uptime)
+            .saturating_sub(now.saturating_duration_since(previous_instant));
+
+        if suspended_for < SUSPEND_THRESHOLD {
+            return false;
+        }
+
+        info!("Resumed after being suspended for {}s", suspended_for.as_secs());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensors::sensor_error::SensorError;
+    use std::cell::Cell;
+
+    struct MockProcUptimeReader {
+        readings: Vec<Result<ProcUptimeStatus, SensorError>>,
+        index: Cell<usize>,
+    }
+
+    impl MockProcUptimeReader {
+        fn new(readings: Vec<Result<ProcUptimeStatus, SensorError>>) -> Self {
+            Self {
+                readings,
+                index: Cell::new(0),
+            }
+        }
+    }
+
+    impl SensorReader for MockProcUptimeReader {
+        type Output = ProcUptimeStatus;
+
+        fn read(&self) -> Result<Self::Output, SensorError> {
+            let i = self.index.get();
+            let result = self.readings[i].clone();
+            self.index.set(i + 1);
+            result
+        }
+    }
+
+    fn uptime(seconds: f64) -> Result<ProcUptimeStatus, SensorError> {
+        Ok(ProcUptimeStatus::new(Duration::from_secs_f64(seconds)))
+    }
+
+    #[test]
+    fn first_check_is_not_a_resume() {
+        let mut detector = SuspendDetector::new(MockProcUptimeReader::new(vec![uptime(100.0)]));
+
+        assert!(!detector.has_resumed_at(Instant::now()));
+    }
+
+    #[test]
+    fn clocks_moving_together_is_not_a_resume() {
+        let mut detector = SuspendDetector::new(MockProcUptimeReader::new(vec![
+            uptime(100.0),
+            uptime(101.01),
+            uptime(106.0),
+        ]));
+        let start = Instant::now();
+
+        assert!(!detector.has_resumed_at(start));
+        assert!(!detector.has_resumed_at(start + Duration::from_secs(1)));
+        assert!(!detector.has_resumed_at(start + Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn uptime_jumping_ahead_is_a_resume() {
+        let mut detector = SuspendDetector::new(MockProcUptimeReader::new(vec![
+            uptime(100.0),
+            uptime(3701.0),
+            uptime(3702.0),
+        ]));
+        let start = Instant::now();
+
+        assert!(!detector.has_resumed_at(start));
+        assert!(detector.has_resumed_at(start + Duration::from_secs(1)));
+        // The next check compares against the time after the resume
+        assert!(!detector.has_resumed_at(start + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn unreadable_uptime_is_not_a_resume() {
+        let mut detector = SuspendDetector::new(MockProcUptimeReader::new(vec![
+            uptime(100.0),
+            Err(SensorError::Transient("boom".to_string())),
+        ]));
+        let start = Instant::now();
+
+        assert!(!detector.has_resumed_at(start));
+        assert!(!detector.has_resumed_at(start + Duration::from_secs(1)));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::configuration::thresholds::ThresholdConfiguration;
+
+/// How close a monitor's value is to its configured limits
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub enum ThresholdLevel {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Tracks which threshold a value has crossed. A level is entered as soon as the value reaches its
+/// threshold, but is only left once the value falls the hysteresis below it.
+#[derive(Debug)]
+pub struct ThresholdTracker {
+    configuration: ThresholdConfiguration,
+    level: ThresholdLevel,
+}
+
+impl ThresholdTracker {
+    pub fn new(configuration: ThresholdConfiguration) -> Self {
+        Self {
+            configuration,
+            level: ThresholdLevel::Normal,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.level = ThresholdLevel::Normal;
+    }
+
+    /// Moves to the level of the new value and returns it
+    pub fn update(&mut self, value: f64) -> ThresholdLevel {
+        let hysteresis = self.configuration.hysteresis as f64;
+        let is_reached = |level: ThresholdLevel, threshold: Option<u64>| match threshold {
+            // The current level, and any below it, are held until the value falls the hysteresis below
+            Some(threshold) if level <= self.level => value >= threshold as f64 - hysteresis,
+            Some(threshold) => value >= threshold as f64,
+            None => false,
+        };
+
+        self.level = if is_reached(ThresholdLevel::Critical, self.configuration.critical) {
+            ThresholdLevel::Critical
+        } else if is_reached(ThresholdLevel::Warning, self.configuration.warning) {
+            ThresholdLevel::Warning
+        } else {
+            ThresholdLevel::Normal
+        };
+
+        self.level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(tracker: &mut ThresholdTracker, values: &[f64]) -> Vec<ThresholdLevel> {
+        values.iter().map(|value| tracker.update(*value)).collect()
+    }
+
+    #[test]
+    fn levels_are_entered_at_their_thresholds() {
+        let mut tracker = ThresholdTracker::new(ThresholdConfiguration::new(70, 90, 0));
+
+        assert_eq!(
+            levels(&mut tracker, &[10.0, 70.0, 89.9, 90.0, 50.0]),
+            vec![
+                ThresholdLevel::Normal,
+                ThresholdLevel::Warning,
+                ThresholdLevel::Warning,
+                ThresholdLevel::Critical,
+                ThresholdLevel::Normal,
+            ]
+        );
+    }
+
+    #[test]
+    fn levels_are_held_until_the_value_falls_the_hysteresis_below() {
+        let mut tracker = ThresholdTracker::new(ThresholdConfiguration::new(70, 90, 5));
+
+        assert_eq!(
+            levels(&mut tracker, &[92.0, 86.0, 84.9, 66.0, 69.0, 64.9]),
+            vec![
+                ThresholdLevel::Critical,
+                ThresholdLevel::Critical,
+                ThresholdLevel::Warning,
+                ThresholdLevel::Warning,
+                ThresholdLevel::Warning,
+                ThresholdLevel::Normal,
+            ]
+        );
+    }
+
+    #[test]
+    fn hysteresis_does_not_lower_the_threshold_to_enter_a_level() {
+        let mut tracker = ThresholdTracker::new(ThresholdConfiguration::new(70, 90, 5));
+
+        assert_eq!(tracker.update(67.0), ThresholdLevel::Normal);
+        assert_eq!(tracker.update(88.0), ThresholdLevel::Warning);
+    }
+
+    #[test]
+    fn unset_thresholds_are_never_reached() {
+        let mut tracker = ThresholdTracker::new(ThresholdConfiguration {
+            warning: None,
+            critical: Some(90),
+            hysteresis: 0,
+            colour_label: false,
+        });
+
+        assert_eq!(tracker.update(80.0), ThresholdLevel::Normal);
+        assert_eq!(tracker.update(95.0), ThresholdLevel::Critical);
+        assert_eq!(
+            ThresholdTracker::new(ThresholdConfiguration::default()).update(1e12),
+            ThresholdLevel::Normal
+        );
+    }
+
+    #[test]
+    fn clear_returns_to_normal() {
+        let mut tracker = ThresholdTracker::new(ThresholdConfiguration::new(70, 90, 50));
+        tracker.update(95.0);
+        tracker.clear();
+
+        assert_eq!(tracker.update(60.0), ThresholdLevel::Normal);
+    }
+}
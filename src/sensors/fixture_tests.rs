@@ -0,0 +1,442 @@
+//! Runs the readers against /proc and /sys files from different kernels, stored under
+//! tests/fixtures/<kernel>. Symlinks in sysfs, i.e. a network device's `device` link, are stored
+//! as empty files since the readers only check that they exist.
+
+use crate::configuration::raid::RaidConfiguration;
+use crate::monitors::raid_monitor::{RaidHealth, RaidMonitor};
+use crate::sensors::page_size::page_size_bytes;
+use crate::sensors::proc_arcstats_reader::ProcArcStatsReader;
+use crate::sensors::proc_disk_stats_reader::ProcDiskStatsReader;
+use crate::sensors::proc_mdstat_reader::ProcMdStatReader;
+use crate::sensors::proc_meminfo_reader::{MemoryCompressionStatus, ProcMemInfoSensorReader};
+use crate::sensors::proc_net_dev_reader::ProcNetDevReader;
+use crate::sensors::proc_pid_stat_reader::{ProcPidStatReader, ProcPidStatus};
+use crate::sensors::proc_stat_reader::{ProcStatCoreStatus, ProcStatSensorReader};
+use crate::sensors::proc_uptime_reader::ProcUptimeReader;
+use crate::sensors::proc_vmstat_reader::ProcVmStatReader;
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use crate::sensors::statvfs_reader::{FilesystemStatus, StatvfsReader};
+use crate::sensors::sys_file_reader::SysFileReader;
+use crate::sensors::sys_powercap_reader::{PowercapAccess, SysPowercapReader};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const LINUX_4_9: &str = "linux-4.9";
+const LINUX_5_4: &str = "linux-5.4";
+const LINUX_6_18: &str = "linux-6.18";
+
+fn fixture_root(kernel: &str) -> SensorRoot {
+    SensorRoot::new(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(kernel),
+    )
+}
+
+#[test]
+fn sensor_root_resolves_absolute_paths() {
+    let root = SensorRoot::new("/run/host");
+
+    assert_eq!(root.resolve("/proc/stat"), PathBuf::from("/run/host/proc/stat"));
+    assert_eq!(SensorRoot::default().resolve("/proc/stat"), PathBuf::from("/proc/stat"));
+}
+
+#[test]
+fn proc_stat_is_read_on_all_kernels() {
+    let expected = [
+        (LINUX_4_9, 13343292, 6130, 15365599, 4),
+        (LINUX_5_4, 46093823, 41592, 48930057, 8),
+        (LINUX_6_18, 118095, 3967, 130672, 1),
+    ];
+
+    for (kernel, idle, iowait, total, cores) in expected {
+        let status = ProcStatSensorReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.idle, idle, "{kernel}");
+        assert_eq!(status.iowait, iowait, "{kernel}");
+        assert_eq!(status.total, total, "{kernel}");
+        assert_eq!(status.cores.len(), cores, "{kernel}");
+    }
+}
+
+#[test]
+fn proc_stat_cores_are_read_in_order() {
+    let status = ProcStatSensorReader::new(fixture_root(LINUX_4_9)).read().unwrap();
+
+    assert_eq!(
+        status.cores[0],
+        ProcStatCoreStatus {
+            idle: 3335993,
+            iowait: 1535,
+            total: 340421 + 8230 + 142189 + 3335993 + 1535 + 11312,
+        }
+    );
+}
+
+#[test]
+fn process_cpu_time_is_read_from_each_pid() {
+    let mut processes = ProcPidStatReader::new(fixture_root(LINUX_6_18))
+        .read()
+        .unwrap()
+        .processes;
+    processes.sort_by_key(|process| process.pid);
+
+    assert_eq!(
+        processes,
+        vec![
+            ProcPidStatus {
+                pid: 1,
+                name: "systemd".to_string(),
+                cpu_ticks: 61 + 95,
+            },
+            ProcPidStatus {
+                pid: 2417,
+                name: "Web Content (1)".to_string(),
+                cpu_ticks: 5120 + 830,
+            },
+        ]
+    );
+    // Kernels without pid directories in the fixtures have no processes, rather than an error
+    assert!(ProcPidStatReader::new(fixture_root(LINUX_4_9))
+        .read()
+        .unwrap()
+        .processes
+        .is_empty());
+}
+
+#[test]
+fn proc_meminfo_is_read_on_all_kernels() {
+    let expected = [
+        (LINUX_4_9, 8167848, 5203476, 4194300, 4184828),
+        (LINUX_5_4, 32791868, 9383212, 8388604, 7340028),
+        (LINUX_6_18, 6158152, 5693948, 0, 0),
+    ];
+
+    for (kernel, total_kib, available_kib, swap_total_kib, swap_free_kib) in expected {
+        let status = ProcMemInfoSensorReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.total_kib, total_kib, "{kernel}");
+        assert_eq!(status.available_kib, available_kib, "{kernel}");
+        assert_eq!(status.swap_total_kib, swap_total_kib, "{kernel}");
+        assert_eq!(status.swap_free_kib, swap_free_kib, "{kernel}");
+    }
+}
+
+#[test]
+fn proc_meminfo_breakdown_is_read() {
+    let status = ProcMemInfoSensorReader::new(fixture_root(LINUX_5_4)).read().unwrap();
+
+    assert_eq!(status.free_kib, 2204536);
+    assert_eq!(status.buffers_kib, 412992);
+    assert_eq!(status.cached_kib, 6619968);
+    assert_eq!(status.shmem_kib, 391860);
+    assert_eq!(status.slab_reclaimable_kib, 367428);
+}
+
+#[test]
+fn zfs_arc_and_zram_are_added_to_meminfo() {
+    let status = ProcMemInfoSensorReader::new(fixture_root(LINUX_5_4))
+        .with_zfs_arc(true)
+        .with_compression(true)
+        .read()
+        .unwrap();
+
+    assert_eq!(status.arc_reclaimable_kib, (8556201984 - 1049306112) / 1024);
+    assert_eq!(status.zram, Some(MemoryCompressionStatus::new(1048576, 349525)));
+    // zswap is enabled, but 5.4 only reports its totals in debugfs, which isn't readable
+    assert_eq!(status.zswap, None);
+}
+
+#[test]
+fn zswap_is_read_from_debugfs_on_older_kernels() {
+    let status = ProcMemInfoSensorReader::new(fixture_root(LINUX_4_9))
+        .with_compression(true)
+        .read()
+        .unwrap();
+
+    // debugfs counts the stored pages, which are in the system's page size
+    let original_kib = 76800 * page_size_bytes() / 1024;
+    assert_eq!(status.zswap, Some(MemoryCompressionStatus::new(original_kib, 102400)));
+}
+
+#[test]
+fn zfs_arc_and_compression_are_only_read_when_asked_for() {
+    let status = ProcMemInfoSensorReader::new(fixture_root(LINUX_5_4)).read().unwrap();
+
+    assert_eq!(status.arc_reclaimable_kib, 0);
+    assert_eq!(status.zram, None);
+    assert_eq!(status.zswap, None);
+}
+
+#[test]
+fn unused_zram_and_disabled_zswap_are_not_reported() {
+    let status = ProcMemInfoSensorReader::new(fixture_root(LINUX_6_18))
+        .with_zfs_arc(true)
+        .with_compression(true)
+        .read()
+        .unwrap();
+
+    assert_eq!(status.arc_reclaimable_kib, 0);
+    assert_eq!(status.zram, None);
+    assert_eq!(status.zswap, None);
+}
+
+#[test]
+fn proc_net_dev_marks_physical_interfaces() {
+    let expected: [(&str, &[(u64, u64, bool)]); 3] = [
+        (
+            LINUX_4_9,
+            &[
+                (93372936184, 38251093873, true),
+                (5381295006, 5381295006, false),
+                (11838912, 2103874, false),
+            ],
+        ),
+        (
+            LINUX_5_4,
+            &[
+                (9784414, 9784414, false),
+                (8251432897, 981346812, true),
+                (1530082, 421773, true),
+                (104826, 7234183, false),
+                (52837749, 13852244, false),
+            ],
+        ),
+        (
+            LINUX_6_18,
+            &[
+                (29426421, 29426421, false),
+                (0, 0, false),
+                (0, 0, false),
+                (3450, 4616, true),
+            ],
+        ),
+    ];
+
+    for (kernel, devices) in expected {
+        let status = ProcNetDevReader::new(fixture_root(kernel)).read().unwrap();
+
+        let actual: Vec<(u64, u64, bool)> = status
+            .device_statuses
+            .iter()
+            .map(|device| (device.rx_bytes, device.tx_bytes, device.is_physical_device))
+            .collect();
+
+        assert_eq!(actual, devices.to_vec(), "{kernel}");
+    }
+}
+
+#[test]
+fn proc_diskstats_is_read_with_any_number_of_fields() {
+    // 4.9 has 14 fields per line, 5.4 adds the discard stats and 6.x adds the flush stats too
+    let expected = [
+        (LINUX_4_9, 4, "sda", 100734466, 219108424),
+        (LINUX_5_4, 8, "nvme0n1", 82937438, 126614648),
+        (LINUX_6_18, 11, "vda", 1998946, 950864),
+    ];
+
+    for (kernel, device_count, device_name, sectors_read, sectors_written) in expected {
+        let status = ProcDiskStatsReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.device_statuses.len(), device_count, "{kernel}");
+
+        let device = status
+            .device_statuses
+            .iter()
+            .find(|device| device.device_name == device_name)
+            .unwrap();
+
+        assert_eq!(device.sectors_read, sectors_read, "{kernel}");
+        assert_eq!(device.sectors_written, sectors_written, "{kernel}");
+    }
+}
+
+#[test]
+fn proc_vmstat_is_read_on_all_kernels() {
+    // 4.9 is older than the oom_kill counter
+    let expected = [
+        (LINUX_4_9, 2367, 4741, 40132, 0),
+        (LINUX_5_4, 311740, 262144, 129830, 2),
+        (LINUX_6_18, 0, 0, 476, 0),
+    ];
+
+    for (kernel, pswpin, pswpout, pgmajfault, oom_kill) in expected {
+        let status = ProcVmStatReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.pswpin, pswpin, "{kernel}");
+        assert_eq!(status.pswpout, pswpout, "{kernel}");
+        assert_eq!(status.pgmajfault, pgmajfault, "{kernel}");
+        assert_eq!(status.oom_kill, oom_kill, "{kernel}");
+    }
+}
+
+#[test]
+fn powercap_zones_are_read() {
+    let status = SysPowercapReader::new(fixture_root(LINUX_5_4)).read().unwrap();
+
+    let zones: Vec<(&str, &str, u64)> = status
+        .zones
+        .iter()
+        .map(|zone| (zone.zone_id.as_str(), zone.name.as_str(), zone.energy_uj))
+        .collect();
+
+    assert_eq!(status.access, PowercapAccess::Readable);
+    assert_eq!(
+        zones,
+        vec![
+            ("intel-rapl:0", "package-0", 49371563722),
+            ("intel-rapl:0:0", "core", 21743127402),
+            ("intel-rapl:0:1", "uncore", 612784331),
+            ("intel-rapl:0:2", "dram", 5308231449),
+        ]
+    );
+}
+
+#[test]
+fn missing_powercap_is_unsupported() {
+    for kernel in [LINUX_4_9, LINUX_6_18] {
+        let status = SysPowercapReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.access, PowercapAccess::Unsupported, "{kernel}");
+    }
+}
+
+#[test]
+fn arcstats_are_read() {
+    let status = ProcArcStatsReader::new(fixture_root(LINUX_5_4)).read().unwrap();
+
+    assert_eq!(status.size_bytes, 8556201984);
+    assert_eq!(status.target_bytes, 12884901888);
+    assert_eq!(status.min_target_bytes, 1049306112);
+    assert_eq!(status.hits, 48613283);
+    assert_eq!(status.misses, 1533478);
+}
+
+#[test]
+fn missing_arcstats_is_unavailable() {
+    let err = ProcArcStatsReader::new(fixture_root(LINUX_6_18)).read().unwrap_err();
+
+    assert!(matches!(err, SensorError::Unavailable(_)));
+    assert!(err.is_permanent());
+}
+
+#[test]
+fn missing_sys_file_is_unavailable_and_bad_contents_are_a_parse_error() {
+    let missing = SysFileReader::new(
+        &fixture_root(LINUX_5_4),
+        PathBuf::from("/sys/block/sdz/size"),
+        None,
+        None,
+    );
+    let unparsable = SysFileReader::new(&fixture_root(LINUX_5_4), PathBuf::from("/proc/mdstat"), None, None);
+
+    assert!(matches!(missing.read(), Err(SensorError::Unavailable(_))));
+    assert!(matches!(unparsable.read(), Err(SensorError::Parse(_))));
+    assert!(!unparsable.read().unwrap_err().is_permanent());
+}
+
+#[test]
+fn mdstat_reports_degraded_and_rebuilding_arrays() {
+    let status = ProcMdStatReader::new(fixture_root(LINUX_5_4)).read().unwrap();
+
+    assert_eq!(status.arrays.len(), 2);
+
+    let rebuilding = &status.arrays[0];
+    assert_eq!(rebuilding.name, "md0");
+    assert_eq!(rebuilding.level.as_deref(), Some("raid1"));
+    assert_eq!((rebuilding.expected_members, rebuilding.working_members), (2, 1));
+    assert_eq!(rebuilding.member_health, "U_");
+    assert_eq!(rebuilding.sync_action.as_deref(), Some("recovery"));
+    assert_eq!(rebuilding.sync_progress, Some(21.6));
+
+    let degraded = &status.arrays[1];
+    assert_eq!(degraded.name, "md1");
+    assert_eq!(degraded.member_health, "_U");
+    assert_eq!(degraded.failed_members, 1);
+    assert_eq!(degraded.sync_action, None);
+
+    let mut monitor = RaidMonitor::new(
+        ProcMdStatReader::new(fixture_root(LINUX_5_4)),
+        &RaidConfiguration::default(),
+    );
+    assert_eq!(monitor.poll().unwrap().health(), RaidHealth::Degraded);
+}
+
+#[test]
+fn mdstat_check_is_healthy() {
+    let status = ProcMdStatReader::new(fixture_root(LINUX_4_9)).read().unwrap();
+
+    assert_eq!(status.arrays.len(), 1);
+    assert_eq!(status.arrays[0].sync_action.as_deref(), Some("check"));
+    assert_eq!(status.arrays[0].sync_progress, Some(52.3));
+
+    let mut monitor = RaidMonitor::new(
+        ProcMdStatReader::new(fixture_root(LINUX_4_9)),
+        &RaidConfiguration::default(),
+    );
+    assert_eq!(monitor.poll().unwrap().health(), RaidHealth::Healthy);
+}
+
+#[test]
+fn missing_mdstat_has_no_arrays() {
+    let status = ProcMdStatReader::new(fixture_root(LINUX_6_18)).read().unwrap();
+
+    assert!(status.arrays.is_empty());
+}
+
+#[test]
+fn proc_uptime_is_read_on_all_kernels() {
+    let expected = [(LINUX_4_9, 1533879.41), (LINUX_5_4, 864211.07), (LINUX_6_18, 1975.55)];
+
+    for (kernel, uptime) in expected {
+        let status = ProcUptimeReader::new(fixture_root(kernel)).read().unwrap();
+
+        assert_eq!(status.uptime, Duration::from_secs_f64(uptime), "{kernel}");
+    }
+}
+
+#[test]
+fn sys_file_path_is_resolved_against_the_root() {
+    let reader = SysFileReader::new(
+        &fixture_root(LINUX_5_4),
+        PathBuf::from("/sys/block/sda/size"),
+        None,
+        None,
+    );
+
+    assert_eq!(reader.read().unwrap().value, 1953525168.0);
+}
+
+#[test]
+fn statvfs_reads_the_filesystems_that_exist() {
+    let paths = vec!["/".to_string(), "/missing".to_string()];
+    let status = StatvfsReader::new(fixture_root(LINUX_6_18), paths).read().unwrap();
+
+    assert_eq!(status.filesystems.len(), 1);
+    assert_eq!(status.filesystems[0].path, "/");
+    assert!(status.filesystems[0].total_bytes > 0);
+    assert!((0.0..=100.0).contains(&status.filesystems[0].used_percent()));
+}
+
+#[test]
+fn page_size_is_read_from_the_system() {
+    let page_size = page_size_bytes();
+
+    assert!(page_size >= 4096);
+    assert!(page_size.is_power_of_two());
+}
+
+#[test]
+fn filesystem_usage_leaves_out_the_reserved_blocks() {
+    let filesystem = FilesystemStatus {
+        path: "/".to_string(),
+        total_bytes: 1000,
+        free_bytes: 300,
+        available_bytes: 200,
+    };
+
+    // 700 used of the 900 that are usable without root
+    assert!((filesystem.used_percent() - 700.0 / 900.0 * 100.0).abs() < 1e-9);
+}
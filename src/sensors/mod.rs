@@ -1,6 +1,21 @@
 pub mod proc_meminfo_reader;
 pub mod proc_stat_reader;
 pub mod sensor_traits;
+pub mod sensor_error;
+pub mod sensor_root;
 pub mod proc_net_dev_reader;
 pub mod network_utilities;
-pub mod proc_disk_stats_reader;
\ No newline at end of file
+pub mod proc_disk_stats_reader;
+pub mod sys_file_reader;
+pub mod sys_powercap_reader;
+pub mod proc_vmstat_reader;
+pub mod proc_arcstats_reader;
+pub mod sys_zram_reader;
+pub mod proc_mdstat_reader;
+pub mod proc_uptime_reader;
+pub mod proc_pid_stat_reader;
+pub mod statvfs_reader;
+pub mod page_size;
+
+#[cfg(test)]
+mod fixture_tests;
\ No newline at end of file
@@ -1,9 +1,10 @@
+use crate::sensors::sensor_root::SensorRoot;
 use std::fs;
 
 const SYS_CLASS_NET_PATH: &str = "/sys/class/net";
 
-pub fn is_physical_interface(device_name: &str) -> bool {
-    let device_file_path = format!("{}/{}/device", SYS_CLASS_NET_PATH, device_name);
+pub fn is_physical_interface(root: &SensorRoot, device_name: &str) -> bool {
+    let device_file_path = root.resolve(SYS_CLASS_NET_PATH).join(device_name).join("device");
 
     if let Ok(value) = fs::exists(device_file_path) {
         value
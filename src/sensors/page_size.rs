@@ -0,0 +1,20 @@
+use std::sync::OnceLock;
+
+/// The page size to fall back on if the system doesn't report one. 4KiB is the page size on almost all desktops.
+const DEFAULT_PAGE_SIZE_BYTES: u64 = 4096;
+
+/// The size of a memory page in bytes. The kernel counts swapped and compressed memory in pages rather than bytes,
+/// and the page size differs between architectures, i.e. 16KiB on Apple silicon and 64KiB on some ARM servers.
+pub fn page_size_bytes() -> u64 {
+    static PAGE_SIZE_BYTES: OnceLock<u64> = OnceLock::new();
+
+    *PAGE_SIZE_BYTES.get_or_init(|| {
+        // SAFETY: sysconf only reads a system value and has no preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+        u64::try_from(page_size)
+            .ok()
+            .filter(|&size| size > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE_BYTES)
+    })
+}
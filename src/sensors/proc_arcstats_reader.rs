@@ -0,0 +1,114 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+
+const PROC_ARCSTATS_FILE: &str = "/proc/spl/kstat/zfs/arcstats";
+const ARCSTATS_SIZE_KEY: &str = "size";
+const ARCSTATS_TARGET_KEY: &str = "c";
+const ARCSTATS_MIN_TARGET_KEY: &str = "c_min";
+const ARCSTATS_HITS_KEY: &str = "hits";
+const ARCSTATS_MISSES_KEY: &str = "misses";
+
+/// The ZFS ARC counters from the SPL kstat file. Sizes are in bytes, hits and misses only increase.
+#[derive(Clone, Debug, Default)]
+pub struct ProcArcStatsStatus {
+    /// The current size of the ARC
+    pub size_bytes: u64,
+    /// The size the ARC is currently trying to reach
+    pub target_bytes: u64,
+    /// The size the ARC will not shrink below under memory pressure
+    pub min_target_bytes: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl ProcArcStatsStatus {
+    pub fn new(size_bytes: u64, target_bytes: u64, min_target_bytes: u64, hits: u64, misses: u64) -> Self {
+        Self {
+            size_bytes,
+            target_bytes,
+            min_target_bytes,
+            hits,
+            misses,
+        }
+    }
+
+    /// The part of the ARC the kernel can give back to applications, i.e. everything above c_min.
+    pub fn reclaimable_bytes(&self) -> u64 {
+        self.size_bytes.saturating_sub(self.min_target_bytes)
+    }
+}
+
+#[derive(Default)]
+pub struct ProcArcStatsReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for ProcArcStatsReader {
+    type Output = ProcArcStatsStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_ARCSTATS_FILE);
+
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(SensorError::Unavailable(format!(
+                    "{} does not exist. Is the ZFS module loaded?",
+                    path.display()
+                )))
+            }
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let mut contents = String::new();
+
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
+        }
+
+        Ok(self.parse_arcstats(&contents))
+    }
+}
+
+impl ProcArcStatsReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    /// Whether the ZFS module is loaded, as the arcstats file only exists then
+    pub fn is_available(&self) -> bool {
+        self.root.resolve(PROC_ARCSTATS_FILE).exists()
+    }
+
+    /// The file starts with a kstat header line and a column header line, followed by
+    /// "name type data" rows. Anything that isn't a numeric row is skipped.
+    fn parse_arcstats(&self, contents: &str) -> ProcArcStatsStatus {
+        let mut status = ProcArcStatsStatus::default();
+
+        for line in contents.lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+
+            if parts.len() != 3 {
+                continue;
+            }
+
+            let Ok(value) = parts[2].parse::<u64>() else {
+                continue;
+            };
+
+            match parts[0] {
+                ARCSTATS_SIZE_KEY => status.size_bytes = value,
+                ARCSTATS_TARGET_KEY => status.target_bytes = value,
+                ARCSTATS_MIN_TARGET_KEY => status.min_target_bytes = value,
+                ARCSTATS_HITS_KEY => status.hits = value,
+                ARCSTATS_MISSES_KEY => status.misses = value,
+                _ => (),
+            }
+        }
+
+        status
+    }
+}
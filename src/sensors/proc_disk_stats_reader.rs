@@ -1,10 +1,15 @@
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
 
 const PROC_DISK_STATS_FILE: &str = "/proc/diskstats";
 
+/// Kernels before 4.18 only have the original 11 stats after the device major, minor and name. Later
+/// kernels append discard (4.18) and flush (5.5) stats, which aren't used here.
+const MIN_DISK_STATS_FIELDS: usize = 14;
+
 const DISK_NAME_INDEX: usize = 2;
 const SECTORS_READ_INDEX: usize = 5;
 const SECTORS_WRITTEN_INDEX: usize = 9;
@@ -40,23 +45,25 @@ impl ProcDiskStats {
 }
 
 #[derive(Default, Debug)]
-pub struct ProcDiskStatsReader;
+pub struct ProcDiskStatsReader {
+    root: SensorRoot,
+}
 
 impl SensorReader for ProcDiskStatsReader {
     type Output = ProcDiskStats;
 
-    fn read(&self) -> Result<Self::Output, String> {
-        let path = Path::new(PROC_DISK_STATS_FILE);
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_DISK_STATS_FILE);
 
-        let mut file = match File::open(path) {
+        let mut file = match File::open(&path) {
             Ok(file) => file,
-            Err(e) => return Err(format!("Unable to open file: {} {}", PROC_DISK_STATS_FILE, e)),
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
         };
 
         let mut contents = String::new();
 
-        if let Err(_) = file.read_to_string(&mut contents) {
-            return Err(format!("Unable to read {}", PROC_DISK_STATS_FILE));
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
         }
 
         let mut statuses = Vec::new();
@@ -73,13 +80,17 @@ impl SensorReader for ProcDiskStatsReader {
 }
 
 impl ProcDiskStatsReader {
-    fn parse_disk_stats_line(&self, line: &str) -> Result<ProcDiskStatsStatus, String> {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    fn parse_disk_stats_line(&self, line: &str) -> Result<ProcDiskStatsStatus, SensorError> {
         let values: Vec<&str> = line
             .split_whitespace()
             .collect();
 
-        if values.len() < 20 {
-            return Err(format!("Invalid file format {}", PROC_DISK_STATS_FILE));
+        if values.len() < MIN_DISK_STATS_FIELDS {
+            return Err(SensorError::Parse(format!("Invalid file format {}", PROC_DISK_STATS_FILE)));
         }
 
         let device_name = values[DISK_NAME_INDEX].to_string();
@@ -0,0 +1,167 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs;
+use std::io::ErrorKind;
+
+const PROC_MDSTAT_FILE: &str = "/proc/mdstat";
+const MDSTAT_PERSONALITIES_PREFIX: &str = "Personalities";
+const MDSTAT_UNUSED_DEVICES_PREFIX: &str = "unused devices";
+const MDSTAT_FAILED_MEMBER_SUFFIX: &str = "(F)";
+const MDSTAT_SYNC_ACTIONS: [&str; 4] = ["resync", "recovery", "reshape", "check"];
+
+/// The state of a single md array as reported in /proc/mdstat
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MdArrayStatus {
+    /// The array device name, i.e. md0
+    pub name: String,
+    /// Whether the array is assembled and running
+    pub active: bool,
+    /// The RAID level, i.e. raid1. Inactive arrays don't report one.
+    pub level: Option<String>,
+    /// The number of member devices the array should have
+    pub expected_members: usize,
+    /// The number of member devices currently working
+    pub working_members: usize,
+    /// The member health map, i.e. "UU" or "U_"
+    pub member_health: String,
+    /// The number of members marked as failed
+    pub failed_members: usize,
+    /// The running sync action, i.e. recovery, resync, reshape or check
+    pub sync_action: Option<String>,
+    /// The sync action progress as a percentage
+    pub sync_progress: Option<f64>,
+}
+
+/// The md arrays on the system. Empty when the md driver isn't loaded.
+#[derive(Clone, Debug, Default)]
+pub struct ProcMdStatStatus {
+    pub arrays: Vec<MdArrayStatus>,
+}
+
+#[derive(Default)]
+pub struct ProcMdStatReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for ProcMdStatReader {
+    type Output = ProcMdStatStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_MDSTAT_FILE);
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // No md driver means no arrays rather than an error
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(ProcMdStatStatus::default()),
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        self.parse_mdstat(&contents)
+    }
+}
+
+impl ProcMdStatReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    /// Each array starts with an unindented "mdX : state level members..." line, followed by
+    /// indented lines for the block count / member health and any sync progress.
+    fn parse_mdstat(&self, contents: &str) -> Result<ProcMdStatStatus, SensorError> {
+        let mut status = ProcMdStatStatus::default();
+
+        for line in contents.lines() {
+            if line.trim().is_empty()
+                || line.starts_with(MDSTAT_PERSONALITIES_PREFIX)
+                || line.starts_with(MDSTAT_UNUSED_DEVICES_PREFIX)
+            {
+                continue;
+            }
+
+            if !line.starts_with(char::is_whitespace) {
+                status.arrays.push(self.parse_array_line(line)?);
+                continue;
+            }
+
+            let Some(array) = status.arrays.last_mut() else {
+                return Err(SensorError::Parse(format!(
+                    "Invalid file format: {} on line: {}",
+                    PROC_MDSTAT_FILE, line
+                )));
+            };
+
+            self.parse_detail_line(array, line);
+        }
+
+        Ok(status)
+    }
+
+    /// i.e. "md0 : active raid1 sdb1[1] sda1[0](F)"
+    fn parse_array_line(&self, line: &str) -> Result<MdArrayStatus, SensorError> {
+        let Some((name, details)) = line.split_once(':') else {
+            return Err(SensorError::Parse(format!(
+                "Invalid file format: {} on line: {}",
+                PROC_MDSTAT_FILE, line
+            )));
+        };
+
+        let mut array = MdArrayStatus {
+            name: name.trim().to_string(),
+            ..Default::default()
+        };
+
+        let mut parts = details.split_whitespace().peekable();
+        array.active = parts.next() == Some("active");
+
+        // Read-only arrays have a bracketed flag before the level, i.e. "(auto-read-only)"
+        while parts.peek().is_some_and(|part| part.starts_with('(')) {
+            parts.next();
+        }
+
+        if array.active {
+            array.level = parts.next().map(|level| level.to_string());
+        }
+
+        array.failed_members = parts
+            .filter(|member| member.ends_with(MDSTAT_FAILED_MEMBER_SUFFIX))
+            .count();
+
+        Ok(array)
+    }
+
+    /// Picks the member counts "[2/1] [U_]" and any sync progress "recovery =  8.5%" from an indented line.
+    fn parse_detail_line(&self, array: &mut MdArrayStatus, line: &str) {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+
+        for (index, part) in parts.iter().enumerate() {
+            if let Some((expected, working)) = part
+                .strip_prefix('[')
+                .and_then(|part| part.strip_suffix(']'))
+                .and_then(|counts| counts.split_once('/'))
+            {
+                if let (Ok(expected), Ok(working)) = (expected.parse(), working.parse()) {
+                    array.expected_members = expected;
+                    array.working_members = working;
+
+                    if let Some(health) = parts.get(index + 1) {
+                        array.member_health = health.trim_matches(|c| c == '[' || c == ']').to_string();
+                    }
+                }
+            }
+
+            if MDSTAT_SYNC_ACTIONS.contains(part) && parts.get(index + 1) == Some(&"=") {
+                array.sync_action = Some(part.to_string());
+                array.sync_progress = parts
+                    .get(index + 2)
+                    .and_then(|progress| progress.trim_end_matches('%').parse().ok());
+            }
+        }
+
+        // Arrays waiting for another one to finish syncing show "resync=DELAYED" instead
+        if let Some(delayed) = parts.iter().find_map(|part| part.strip_suffix("=DELAYED")) {
+            array.sync_action = Some(delayed.to_string());
+            array.sync_progress = Some(0.0);
+        }
+    }
+}
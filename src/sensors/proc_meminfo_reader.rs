@@ -1,16 +1,63 @@
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use crate::sensors::page_size::page_size_bytes;
+use crate::sensors::proc_arcstats_reader::ProcArcStatsReader;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
+use crate::sensors::sys_zram_reader::SysZramReader;
 
 const MEMORY_INFO_FILE: &str = "/proc/meminfo";
 const MEMORY_INFO_TOTAL_KEY: &str = "MemTotal";
 const MEMORY_INFO_AVAILABLE_KEY: &str = "MemAvailable";
+const MEMORY_INFO_FREE_KEY: &str = "MemFree";
+const MEMORY_INFO_BUFFERS_KEY: &str = "Buffers";
+const MEMORY_INFO_CACHED_KEY: &str = "Cached";
+const MEMORY_INFO_SHMEM_KEY: &str = "Shmem";
+const MEMORY_INFO_SLAB_RECLAIMABLE_KEY: &str = "SReclaimable";
+const MEMORY_INFO_SWAP_TOTAL_KEY: &str = "SwapTotal";
+const MEMORY_INFO_SWAP_FREE_KEY: &str = "SwapFree";
+const MEMORY_INFO_ZSWAP_KEY: &str = "Zswap";
+const MEMORY_INFO_ZSWAPPED_KEY: &str = "Zswapped";
+const ZSWAP_ENABLED_FILE: &str = "/sys/module/zswap/parameters/enabled";
+const ZSWAP_DEBUGFS_POOL_SIZE_FILE: &str = "/sys/kernel/debug/zswap/pool_total_size";
+const ZSWAP_DEBUGFS_STORED_PAGES_FILE: &str = "/sys/kernel/debug/zswap/stored_pages";
+
+/// The original and compressed size of the data held by zram or zswap
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryCompressionStatus {
+    pub original_kib: u64,
+    pub compressed_kib: u64,
+}
+
+impl MemoryCompressionStatus {
+    pub fn new(original_kib: u64, compressed_kib: u64) -> Self {
+        Self {
+            original_kib,
+            compressed_kib,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct ProcMemInfoStatus {
     pub total_kib: u64,
     pub available_kib: u64,
+    pub free_kib: u64,
+    pub buffers_kib: u64,
+    /// The page cache, which includes shared memory
+    pub cached_kib: u64,
+    pub shmem_kib: u64,
+    pub slab_reclaimable_kib: u64,
+    /// The shrinkable part of the ZFS ARC, which MemAvailable does not include. Zero without ZFS.
+    pub arc_reclaimable_kib: u64,
+    /// The size of all swap devices, including zram. Zero without swap.
+    pub swap_total_kib: u64,
+    pub swap_free_kib: u64,
+    /// The totals across all zram devices, if there are any
+    pub zram: Option<MemoryCompressionStatus>,
+    /// The zswap pool totals, if zswap is enabled
+    pub zswap: Option<MemoryCompressionStatus>,
 }
 
 impl ProcMemInfoStatus {
@@ -18,62 +65,181 @@ impl ProcMemInfoStatus {
         Self {
             total_kib,
             available_kib,
+            free_kib: 0,
+            buffers_kib: 0,
+            cached_kib: 0,
+            shmem_kib: 0,
+            slab_reclaimable_kib: 0,
+            arc_reclaimable_kib: 0,
+            swap_total_kib: 0,
+            swap_free_kib: 0,
+            zram: None,
+            zswap: None,
         }
     }   
 }
 
-pub struct ProcMemInfoSensorReader;
+#[derive(Default)]
+pub struct ProcMemInfoSensorReader {
+    root: SensorRoot,
+    /// Whether to read the ZFS ARC, which is otherwise reported as zero
+    read_zfs_arc: bool,
+    /// Whether to read the zram and zswap totals, which are otherwise reported as None
+    read_compression: bool,
+}
 
 impl SensorReader for ProcMemInfoSensorReader {
     type Output = ProcMemInfoStatus;
 
-    fn read(&self) -> Result<ProcMemInfoStatus, String> {
-        let path = Path::new(MEMORY_INFO_FILE);
+    fn read(&self) -> Result<ProcMemInfoStatus, SensorError> {
+        let path = self.root.resolve(MEMORY_INFO_FILE);
 
-        let mut file = match File::open(path) {
+        let mut file = match File::open(&path) {
             Ok(file) => file,
-            Err(e) => return Err(format!("Unable to open file: {} {}", MEMORY_INFO_FILE, e)),
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
         };
 
         let mut contents = String::new();
 
-        if let Err(_) = file.read_to_string(&mut contents) {
-            return Err(format!("Unable to read {}", MEMORY_INFO_FILE))
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
         }
 
         let mut total = 0;
         let mut available = 0;
+        let mut free = 0;
+        let mut buffers = 0;
+        let mut cached = 0;
+        let mut shmem = 0;
+        let mut slab_reclaimable = 0;
+        let mut swap_total = 0;
+        let mut swap_free = 0;
+        let mut zswap_compressed = None;
+        let mut zswap_original = None;
 
         for line in contents.lines() {
             let result = self.parse_proc_file_line(line);
 
             match result {
-                Err(e) => return Err(format!("Invalid file format: {} {}", MEMORY_INFO_FILE, e)),
+                Err(e) => return Err(SensorError::Parse(format!("Invalid file format: {} {}", path.display(), e.message()))),
                 Ok((key, value)) => match key {
                     MEMORY_INFO_TOTAL_KEY => total = value,
                     MEMORY_INFO_AVAILABLE_KEY => available = value,
+                    MEMORY_INFO_FREE_KEY => free = value,
+                    MEMORY_INFO_BUFFERS_KEY => buffers = value,
+                    MEMORY_INFO_CACHED_KEY => cached = value,
+                    MEMORY_INFO_SHMEM_KEY => shmem = value,
+                    MEMORY_INFO_SLAB_RECLAIMABLE_KEY => slab_reclaimable = value,
+                    MEMORY_INFO_SWAP_TOTAL_KEY => swap_total = value,
+                    MEMORY_INFO_SWAP_FREE_KEY => swap_free = value,
+                    MEMORY_INFO_ZSWAP_KEY => zswap_compressed = Some(value),
+                    MEMORY_INFO_ZSWAPPED_KEY => zswap_original = Some(value),
                     _ => ()
                 },
             }
         }
 
-        Ok(ProcMemInfoStatus::new(total, available))
+        let mut status = ProcMemInfoStatus::new(total, available);
+        status.free_kib = free;
+        status.buffers_kib = buffers;
+        status.cached_kib = cached;
+        status.shmem_kib = shmem;
+        status.slab_reclaimable_kib = slab_reclaimable;
+        status.swap_total_kib = swap_total;
+        status.swap_free_kib = swap_free;
+
+        if self.read_zfs_arc {
+            status.arc_reclaimable_kib = self.read_arc_reclaimable_kib();
+        }
+
+        if !self.read_compression {
+            return Ok(status);
+        }
+
+        status.zram = self.read_zram();
+        status.zswap = match (zswap_original, zswap_compressed) {
+            (Some(original), Some(compressed)) if self.is_zswap_enabled() || original > 0 => {
+                Some(MemoryCompressionStatus::new(original, compressed))
+            }
+            // Kernels before 5.19 only expose the zswap totals in debugfs
+            (None, _) if self.is_zswap_enabled() => self.read_zswap_debugfs(),
+            _ => None,
+        };
+
+        Ok(status)
     }
 }
 
 impl ProcMemInfoSensorReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self {
+            root,
+            read_zfs_arc: false,
+            read_compression: false,
+        }
+    }
+
+    pub fn with_zfs_arc(mut self, read_zfs_arc: bool) -> Self {
+        self.read_zfs_arc = read_zfs_arc;
+        self
+    }
+
+    pub fn with_compression(mut self, read_compression: bool) -> Self {
+        self.read_compression = read_compression;
+        self
+    }
+
+    fn read_zram(&self) -> Option<MemoryCompressionStatus> {
+        let zram = SysZramReader::new(self.root.clone()).read().ok()?;
+
+        if zram.device_count == 0 {
+            return None;
+        }
+
+        Some(MemoryCompressionStatus::new(zram.original_bytes / 1024, zram.compressed_bytes / 1024))
+    }
+
+    fn is_zswap_enabled(&self) -> bool {
+        std::fs::read_to_string(self.root.resolve(ZSWAP_ENABLED_FILE))
+            .map(|enabled| enabled.trim() == "Y")
+            .unwrap_or(false)
+    }
+
+    /// debugfs is usually only readable by root, in which case zswap is not reported.
+    fn read_zswap_debugfs(&self) -> Option<MemoryCompressionStatus> {
+        let read_value =
+            |file: &str| std::fs::read_to_string(self.root.resolve(file)).ok()?.trim().parse::<u64>().ok();
+
+        let pool_size_bytes = read_value(ZSWAP_DEBUGFS_POOL_SIZE_FILE)?;
+        let stored_pages = read_value(ZSWAP_DEBUGFS_STORED_PAGES_FILE)?;
+
+        Some(MemoryCompressionStatus::new(stored_pages * page_size_bytes() / 1024, pool_size_bytes / 1024))
+    }
+
+    fn read_arc_reclaimable_kib(&self) -> u64 {
+        let arc_reader = ProcArcStatsReader::new(self.root.clone());
+
+        if !arc_reader.is_available() {
+            return 0;
+        }
+
+        arc_reader
+            .read()
+            .map(|arc| arc.reclaimable_bytes() / 1024)
+            .unwrap_or_default()
+    }
 
-    fn parse_proc_file_line<'a>(&self, line: &'a str) -> Result<(&'a str, u64), String> {
+    fn parse_proc_file_line<'a>(&self, line: &'a str) -> Result<(&'a str, u64), SensorError> {
         let parts: Vec<&str> = line.split(':').collect();
 
         if parts.len() != 2 {
-            return Err(format!("Invalid line format on line: {line}"));
+            return Err(SensorError::Parse(format!("Invalid line format on line: {line}")));
         }
 
         let value_parts: Vec<&str> = parts[1].split_whitespace().collect();
 
         if value_parts.len() < 1 {
-            return Err(format!("Invalid line format on line: {line}"));
+            return Err(SensorError::Parse(format!("Invalid line format on line: {line}")));
         }
 
         let key = parts[0].trim();
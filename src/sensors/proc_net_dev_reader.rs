@@ -1,8 +1,9 @@
 use crate::sensors::network_utilities::is_physical_interface;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
 
 const PROC_NET_DEV_FILE: &str = "/proc/net/dev";
 const PROC_NET_DEV_DEVICE_NAME_INDEX: usize = 0;
@@ -22,6 +23,7 @@ impl ProcNetDevStatus {
 
 #[derive(Clone, Debug)]
 pub struct ProcNetDevDeviceStatus {
+    pub device_name: String,
     pub rx_bytes: u64,
     pub tx_bytes: u64,
     pub is_physical_device: bool,
@@ -34,8 +36,9 @@ struct ProcNetDevLine {
 }
 
 impl ProcNetDevDeviceStatus {
-    pub fn new(rx_bytes: u64, tx_bytes: u64, is_physical_device: bool) -> Self {
+    pub fn new(device_name: String, rx_bytes: u64, tx_bytes: u64, is_physical_device: bool) -> Self {
         Self {
+            device_name,
             rx_bytes,
             tx_bytes,
             is_physical_device,
@@ -43,23 +46,26 @@ impl ProcNetDevDeviceStatus {
     }
 }
 
-pub struct ProcNetDevReader;
+#[derive(Default)]
+pub struct ProcNetDevReader {
+    root: SensorRoot,
+}
 
 impl SensorReader for ProcNetDevReader {
     type Output = ProcNetDevStatus;
 
-    fn read(&self) -> Result<Self::Output, String> {
-        let path = Path::new(PROC_NET_DEV_FILE);
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_NET_DEV_FILE);
 
-        let mut file = match File::open(path) {
+        let mut file = match File::open(&path) {
             Ok(file) => file,
-            Err(e) => return Err(format!("Unable to open file: {} {}", PROC_NET_DEV_FILE, e)),
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
         };
 
         let mut contents = String::new();
 
-        if let Err(_) = file.read_to_string(&mut contents) {
-            return Err(format!("Unable to read {}", PROC_NET_DEV_FILE));
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
         }
 
         let mut statuses = Vec::new();
@@ -67,10 +73,13 @@ impl SensorReader for ProcNetDevReader {
         for line in contents.lines().skip(2) {
             let device_status = self.parse_proc_file_line(line)?;
 
+            let is_physical_device = is_physical_interface(&self.root, device_status.device_name.as_str());
+
             statuses.push(ProcNetDevDeviceStatus::new(
+                device_status.device_name,
                 device_status.rx_bytes,
                 device_status.tx_bytes,
-                is_physical_interface(device_status.device_name.as_str()),
+                is_physical_device,
             ));
         }
 
@@ -79,11 +88,15 @@ impl SensorReader for ProcNetDevReader {
 }
 
 impl ProcNetDevReader {
-    fn parse_proc_file_line(&self, line: &str) -> Result<ProcNetDevLine, String> {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    fn parse_proc_file_line(&self, line: &str) -> Result<ProcNetDevLine, SensorError> {
         let parts: Vec<&str> = line.split_whitespace().collect();
 
         if parts.len() < 17 {
-            Err(format!("Invalid file on line: {}", line))?
+            Err(SensorError::Parse(format!("Invalid file on line: {}", line)))?
         }
 
         let device_name = parts[PROC_NET_DEV_DEVICE_NAME_INDEX].replace(":", "");
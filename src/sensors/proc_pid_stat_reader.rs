@@ -0,0 +1,83 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs;
+
+const PROC_DIRECTORY: &str = "/proc";
+const PID_STAT_FILE: &str = "stat";
+/// The indexes of utime and stime in the fields after the command name, which are the 14th and
+/// 15th fields of the file
+const PID_STAT_UTIME_INDEX: usize = 11;
+const PID_STAT_STIME_INDEX: usize = 12;
+
+/// The CPU time a process has used, in clock ticks like /proc/stat
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProcPidStatus {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_ticks: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProcPidStatStatus {
+    pub processes: Vec<ProcPidStatus>,
+}
+
+/// Reads the CPU time of every process from /proc/[pid]/stat
+#[derive(Default)]
+pub struct ProcPidStatReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for ProcPidStatReader {
+    type Output = ProcPidStatStatus;
+
+    fn read(&self) -> Result<ProcPidStatStatus, SensorError> {
+        let path = self.root.resolve(PROC_DIRECTORY);
+
+        let entries = match fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let processes = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+                // A process can exit between listing /proc and reading its stat file
+                let contents = fs::read_to_string(entry.path().join(PID_STAT_FILE)).ok()?;
+
+                parse_pid_stat(pid, &contents)
+            })
+            .collect();
+
+        Ok(ProcPidStatStatus { processes })
+    }
+}
+
+impl ProcPidStatReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+}
+
+/// The command name is in brackets and can contain spaces and brackets itself, so the fields are
+/// counted from the last closing bracket.
+fn parse_pid_stat(pid: u32, contents: &str) -> Option<ProcPidStatus> {
+    let name_start = contents.find('(')?;
+    let name_end = contents.rfind(')')?;
+
+    if name_end < name_start {
+        return None;
+    }
+
+    let fields: Vec<&str> = contents[name_end + 1..].split_whitespace().collect();
+    let utime = fields.get(PID_STAT_UTIME_INDEX)?.parse::<u64>().ok()?;
+    let stime = fields.get(PID_STAT_STIME_INDEX)?.parse::<u64>().ok()?;
+
+    Some(ProcPidStatus {
+        pid,
+        name: contents[name_start + 1..name_end].to_string(),
+        cpu_ticks: utime + stime,
+    })
+}
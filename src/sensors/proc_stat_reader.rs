@@ -1,57 +1,101 @@
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_error::SensorError;
 use crate::sensors::sensor_traits::SensorReader;
 
 const PROC_STAT_FILE: &str = "/proc/stat";
 const CPU_LINE_PREFIX: &str = "cpu ";
+/// The prefix of the per core lines, i.e. "cpu0"
+const CORE_LINE_PREFIX: &str = "cpu";
 
 const PROC_STAT_IDLE_INDEX: usize = 3;
 const PROC_STAT_IOWAIT_INDEX: usize = 4;
 
 
+/// The clock ticks a single core has spent idle, waiting on IO and in total
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct ProcStatCoreStatus {
+    pub idle: u64,
+    pub iowait: u64,
+    pub total: u64,
+}
+
 #[derive(Default, Clone)]
 pub struct ProcStatStatus {
     pub idle: u64,
     pub iowait: u64,
     pub total: u64,
+    /// The ticks of each core, in core order
+    pub cores: Vec<ProcStatCoreStatus>,
 }
 
 impl ProcStatStatus {
     pub fn new(idle: u64, iowait: u64, total: u64) -> Self {
-        ProcStatStatus { idle, iowait, total }
+        ProcStatStatus {
+            idle,
+            iowait,
+            total,
+            cores: Vec::new(),
+        }
     }
 }
 
 #[derive(Default)]
-pub struct ProcStatSensorReader;
+pub struct ProcStatSensorReader {
+    root: SensorRoot,
+}
 
 impl SensorReader for ProcStatSensorReader {
     type Output = ProcStatStatus;
 
-    fn read(&self) -> Result<ProcStatStatus, String> {
-        let path = Path::new(PROC_STAT_FILE);
+    fn read(&self) -> Result<ProcStatStatus, SensorError> {
+        let path = self.root.resolve(PROC_STAT_FILE);
 
-        let mut file = match File::open(path) {
+        let mut file = match File::open(&path) {
             Ok(file) => file,
-            Err(e) => return Err(format!("Unable to open file: {} {}", PROC_STAT_FILE, e)),
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
         };
 
         let mut contents = String::new();
 
-        if let Err(_) = file.read_to_string(&mut contents) {
-            return Err(format!("Unable to read {}", PROC_STAT_FILE));
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
         }
 
         let first_line = contents.lines().next().unwrap_or("");
 
-        self.parse_cpu_stats_line(first_line)
+        let mut status = self.parse_cpu_stats_line(first_line)?;
+
+        for line in contents.lines().skip(1) {
+            let is_core_line = line
+                .strip_prefix(CORE_LINE_PREFIX)
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()));
+
+            if !is_core_line {
+                continue;
+            }
+
+            let core_values = line.split_once(' ').map_or("", |(_, values)| values);
+            let core = self.parse_cpu_stats_line(core_values)?;
+
+            status.cores.push(ProcStatCoreStatus {
+                idle: core.idle,
+                iowait: core.iowait,
+                total: core.total,
+            });
+        }
+
+        Ok(status)
     }
 }
 
 impl ProcStatSensorReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
 
-    fn parse_cpu_stats_line(&self, line: &str) -> Result<ProcStatStatus, String> {
+    fn parse_cpu_stats_line(&self, line: &str) -> Result<ProcStatStatus, SensorError> {
         let values: Vec<u64> = line
             .strip_prefix(CPU_LINE_PREFIX)
             .unwrap_or(line)
@@ -63,12 +107,12 @@ impl ProcStatSensorReader {
 
         let idle = match values.get(PROC_STAT_IDLE_INDEX) {
             Some(&value) => value,
-            None => return Err(format!("{PROC_STAT_FILE} is not in a valid format.")),
+            None => return Err(SensorError::Parse(format!("{PROC_STAT_FILE} is not in a valid format."))),
         };
 
         let iowait = match values.get(PROC_STAT_IOWAIT_INDEX) {
             Some(&value) => value,
-            None => return Err(format!("{PROC_STAT_FILE} is not in a valid format.")),
+            None => return Err(SensorError::Parse(format!("{PROC_STAT_FILE} is not in a valid format."))),
         };
 
         Ok(ProcStatStatus::new(idle, iowait, total))
@@ -0,0 +1,64 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs::File;
+use std::io::Read;
+use std::time::Duration;
+
+const PROC_UPTIME_FILE: &str = "/proc/uptime";
+
+/// The time since boot from /proc/uptime. Unlike `Instant`, this keeps counting while the system
+/// is suspended.
+#[derive(Clone, Debug, Default)]
+pub struct ProcUptimeStatus {
+    pub uptime: Duration,
+}
+
+impl ProcUptimeStatus {
+    pub fn new(uptime: Duration) -> Self {
+        Self { uptime }
+    }
+}
+
+#[derive(Default)]
+pub struct ProcUptimeReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for ProcUptimeReader {
+    type Output = ProcUptimeStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_UPTIME_FILE);
+
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let mut contents = String::new();
+
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
+        }
+
+        self.parse_uptime(&contents)
+    }
+}
+
+impl ProcUptimeReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    /// The file is the uptime and the summed idle time of all cpus, both in seconds, i.e. "1968.67 1805.87"
+    fn parse_uptime(&self, contents: &str) -> Result<ProcUptimeStatus, SensorError> {
+        contents
+            .split_whitespace()
+            .next()
+            .and_then(|uptime| uptime.parse::<f64>().ok())
+            .filter(|uptime| uptime.is_finite() && *uptime >= 0.0)
+            .map(|uptime| ProcUptimeStatus::new(Duration::from_secs_f64(uptime)))
+            .ok_or_else(|| SensorError::Parse(format!("Invalid file format: {}", PROC_UPTIME_FILE)))
+    }
+}
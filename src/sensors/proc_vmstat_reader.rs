@@ -0,0 +1,93 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs::File;
+use std::io::Read;
+
+const PROC_VMSTAT_FILE: &str = "/proc/vmstat";
+const VMSTAT_SWAP_IN_KEY: &str = "pswpin";
+const VMSTAT_SWAP_OUT_KEY: &str = "pswpout";
+const VMSTAT_MAJOR_FAULT_KEY: &str = "pgmajfault";
+const VMSTAT_OOM_KILL_KEY: &str = "oom_kill";
+
+/// The paging counters from /proc/vmstat. These only ever increase from boot.
+#[derive(Clone, Debug, Default)]
+pub struct ProcVmStatStatus {
+    /// The number of pages swapped in
+    pub pswpin: u64,
+    /// The number of pages swapped out
+    pub pswpout: u64,
+    /// The number of major page faults, i.e. faults that needed disk IO
+    pub pgmajfault: u64,
+    /// The number of processes killed by the OOM killer. Only present from kernel 4.13.
+    pub oom_kill: u64,
+}
+
+impl ProcVmStatStatus {
+    pub fn new(pswpin: u64, pswpout: u64, pgmajfault: u64, oom_kill: u64) -> Self {
+        Self {
+            pswpin,
+            pswpout,
+            pgmajfault,
+            oom_kill,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ProcVmStatReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for ProcVmStatReader {
+    type Output = ProcVmStatStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(PROC_VMSTAT_FILE);
+
+        let mut file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let mut contents = String::new();
+
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&path, &e));
+        }
+
+        let mut status = ProcVmStatStatus::default();
+
+        for line in contents.lines() {
+            let (key, value) = self.parse_vmstat_line(line)?;
+
+            match key {
+                VMSTAT_SWAP_IN_KEY => status.pswpin = value,
+                VMSTAT_SWAP_OUT_KEY => status.pswpout = value,
+                VMSTAT_MAJOR_FAULT_KEY => status.pgmajfault = value,
+                VMSTAT_OOM_KILL_KEY => status.oom_kill = value,
+                _ => (),
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+impl ProcVmStatReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    fn parse_vmstat_line<'a>(&self, line: &'a str) -> Result<(&'a str, u64), SensorError> {
+        let mut parts = line.split_whitespace();
+
+        match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => Ok((key, value.parse().unwrap_or_default())),
+            _ => Err(SensorError::Parse(format!(
+                "Invalid file format: {} on line: {}",
+                PROC_VMSTAT_FILE, line
+            ))),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Why a sensor could not be read. Unavailable and PermissionDenied won't change between polls,
+/// while a parse failure or transient error may clear up on the next read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SensorError {
+    /// The sensor doesn't exist on this system, i.e. the kernel module isn't loaded
+    Unavailable(String),
+    /// The sensor exists but the user isn't allowed to read it
+    PermissionDenied(String),
+    /// The sensor was read but its contents were not in the expected format
+    Parse(String),
+    /// Any other failure, i.e. an interrupted read
+    Transient(String),
+}
+
+impl SensorError {
+    /// Classifies a failed file or directory read by its io error kind.
+    pub fn from_io(path: &Path, error: &io::Error) -> Self {
+        let message = format!("Unable to read {}: {}", path.display(), error);
+
+        match error.kind() {
+            ErrorKind::NotFound => SensorError::Unavailable(message),
+            ErrorKind::PermissionDenied => SensorError::PermissionDenied(message),
+            ErrorKind::InvalidData => SensorError::Parse(message),
+            _ => SensorError::Transient(message),
+        }
+    }
+
+    /// Whether the sensor will keep failing, so polling it again is pointless.
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, SensorError::Unavailable(_) | SensorError::PermissionDenied(_))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            SensorError::Unavailable(message)
+            | SensorError::PermissionDenied(message)
+            | SensorError::Parse(message)
+            | SensorError::Transient(message) => message,
+        }
+    }
+}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SensorError::Unavailable(message) => write!(f, "Sensor unavailable: {message}"),
+            SensorError::PermissionDenied(message) => write!(f, "Permission denied: {message}"),
+            SensorError::Parse(message) => write!(f, "Invalid sensor data: {message}"),
+            SensorError::Transient(message) => write!(f, "Sensor read failed: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SensorError {}
@@ -0,0 +1,28 @@
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_SENSOR_ROOT: &str = "/";
+
+/// The directory the readers resolve /proc and /sys paths against. This is "/" on a normal install,
+/// the host mount (i.e. /run/host) under Flatpak, or a directory of captured files in tests.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SensorRoot {
+    path: PathBuf,
+}
+
+impl SensorRoot {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Resolves an absolute path, i.e. /proc/stat, against the root.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        self.path.join(path.strip_prefix("/").unwrap_or(path))
+    }
+}
+
+impl Default for SensorRoot {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENSOR_ROOT)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::ffi::CString;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// The size and free space of the filesystem mounted at a path
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilesystemStatus {
+    /// The path as it was asked for, not resolved against the sensor root
+    pub path: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    /// The free space unprivileged users can write to, which leaves out the blocks reserved for root
+    pub available_bytes: u64,
+}
+
+impl FilesystemStatus {
+    /// How full the filesystem is the way df reports it, where the reserved blocks don't count as
+    /// free space
+    pub fn used_percent(&self) -> f64 {
+        let used_bytes = self.total_bytes.saturating_sub(self.free_bytes);
+        let usable_bytes = used_bytes + self.available_bytes;
+
+        if usable_bytes == 0 {
+            return 0.0;
+        }
+
+        used_bytes as f64 / usable_bytes as f64 * 100.0
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct StatvfsStatus {
+    pub filesystems: Vec<FilesystemStatus>,
+}
+
+/// Reads the usage of the filesystems mounted at the given paths with statvfs. A path that can't
+/// be read, i.e. the mount point of a removable drive that has been removed, is left out rather
+/// than failing the others.
+pub struct StatvfsReader {
+    root: SensorRoot,
+    paths: Vec<String>,
+}
+
+impl SensorReader for StatvfsReader {
+    type Output = StatvfsStatus;
+
+    fn read(&self) -> Result<StatvfsStatus, SensorError> {
+        let filesystems = self
+            .paths
+            .iter()
+            .filter_map(|path| {
+                statvfs(&self.root.resolve(path)).ok().map(|status| FilesystemStatus {
+                    path: path.clone(),
+                    ..status
+                })
+            })
+            .collect();
+
+        Ok(StatvfsStatus { filesystems })
+    }
+}
+
+impl StatvfsReader {
+    pub fn new(root: SensorRoot, paths: Vec<String>) -> Self {
+        Self { root, paths }
+    }
+}
+
+fn statvfs(path: &Path) -> Result<FilesystemStatus, SensorError> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| SensorError::Parse(format!("Invalid path {}", path.display())))?;
+    // SAFETY: statvfs only writes to the struct, which is plain data that is valid zeroed
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    // SAFETY: the path is a valid NUL terminated string and the struct outlives the call
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(SensorError::from_io(path, &io::Error::last_os_error()));
+    }
+
+    let block_size = stat.f_frsize as u64;
+
+    Ok(FilesystemStatus {
+        path: path.display().to_string(),
+        total_bytes: stat.f_blocks as u64 * block_size,
+        free_bytes: stat.f_bfree as u64 * block_size,
+        available_bytes: stat.f_bavail as u64 * block_size,
+    })
+}
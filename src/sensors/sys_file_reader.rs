@@ -0,0 +1,90 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use regex::Regex;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Default)]
+pub struct SysFileStatus {
+    pub value: f64,
+}
+
+impl SysFileStatus {
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+}
+
+/// Reads a single numeric value from any sysfs / procfs file, i.e. a backlight brightness or an
+/// hwmon voltage. The value can optionally be selected with a regex and / or a field index.
+/// The path is resolved against the sensor root, like the fixed /proc and /sys paths.
+pub struct SysFileReader {
+    path: PathBuf,
+    pattern: Option<Regex>,
+    field_index: Option<usize>,
+}
+
+impl SysFileReader {
+    pub fn new(root: &SensorRoot, path: PathBuf, pattern: Option<Regex>, field_index: Option<usize>) -> Self {
+        Self {
+            path: root.resolve(path),
+            pattern,
+            field_index,
+        }
+    }
+}
+
+impl SensorReader for SysFileReader {
+    type Output = SysFileStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) => return Err(SensorError::from_io(&self.path, &e)),
+        };
+
+        let mut contents = String::new();
+
+        if let Err(e) = file.read_to_string(&mut contents) {
+            return Err(SensorError::from_io(&self.path, &e));
+        }
+
+        let value = self.parse_value(&contents)?;
+
+        Ok(SysFileStatus::new(value))
+    }
+}
+
+impl SysFileReader {
+    fn parse_value(&self, contents: &str) -> Result<f64, SensorError> {
+        // The pattern narrows the contents down first, using the first capture group if there is one.
+        let selected = match &self.pattern {
+            Some(pattern) => {
+                let captures = pattern.captures(contents).ok_or_else(|| {
+                    SensorError::Parse(format!("Pattern did not match the contents of {}", self.path.display()))
+                })?;
+
+                captures.get(1).or_else(|| captures.get(0)).map_or("", |m| m.as_str())
+            }
+            None => contents,
+        };
+
+        let raw_value = match self.field_index {
+            Some(index) => selected
+                .split_whitespace()
+                .nth(index)
+                .ok_or_else(|| SensorError::Parse(format!("No field at index {} in {}", index, self.path.display())))?,
+            None => selected.trim(),
+        };
+
+        raw_value.trim().parse::<f64>().map_err(|_| {
+            SensorError::Parse(format!(
+                "Unable to parse '{}' from {}",
+                raw_value.trim(),
+                self.path.display()
+            ))
+        })
+    }
+}
@@ -0,0 +1,133 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+const SYS_CLASS_POWERCAP_PATH: &str = "/sys/class/powercap";
+const RAPL_ZONE_PREFIX: &str = "intel-rapl:";
+const RAPL_NAME_FILE: &str = "name";
+const RAPL_ENERGY_FILE: &str = "energy_uj";
+const RAPL_MAX_ENERGY_RANGE_FILE: &str = "max_energy_range_uj";
+
+/// Whether the system has RAPL energy counters. Since CVE-2020-8694 most distributions only allow
+/// root to read `energy_uj`, which the reader reports as a permission denied error.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PowercapAccess {
+    #[default]
+    Readable,
+    Unsupported,
+}
+
+#[derive(Clone, Debug)]
+pub struct RaplZoneStatus {
+    /// The powercap zone directory name, i.e. intel-rapl:0:1
+    pub zone_id: String,
+    /// The RAPL domain name, i.e. package-0, core, uncore, dram
+    pub name: String,
+    pub energy_uj: u64,
+    pub max_energy_range_uj: u64,
+}
+
+impl RaplZoneStatus {
+    pub fn new(zone_id: String, name: String, energy_uj: u64, max_energy_range_uj: u64) -> Self {
+        Self {
+            zone_id,
+            name,
+            energy_uj,
+            max_energy_range_uj,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SysPowercapStatus {
+    pub access: PowercapAccess,
+    pub zones: Vec<RaplZoneStatus>,
+}
+
+impl SysPowercapStatus {
+    pub fn new(access: PowercapAccess, zones: Vec<RaplZoneStatus>) -> Self {
+        Self { access, zones }
+    }
+}
+
+#[derive(Default)]
+pub struct SysPowercapReader {
+    root: SensorRoot,
+}
+
+impl SysPowercapReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+}
+
+impl SensorReader for SysPowercapReader {
+    type Output = SysPowercapStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(SYS_CLASS_POWERCAP_PATH);
+
+        let entries = match fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Ok(SysPowercapStatus::new(PowercapAccess::Unsupported, Vec::new()))
+            }
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let mut zones = Vec::new();
+
+        for entry in entries.flatten() {
+            let zone_id = entry.file_name().to_string_lossy().to_string();
+
+            if !zone_id.starts_with(RAPL_ZONE_PREFIX) {
+                continue;
+            }
+
+            let zone_path = entry.path();
+
+            let energy_path = zone_path.join(RAPL_ENERGY_FILE);
+
+            let energy_uj = match read_u64(&energy_path) {
+                Ok(value) => value,
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+                    return Err(SensorError::PermissionDenied(format!(
+                        "{} is only readable by root",
+                        energy_path.display()
+                    )))
+                }
+                Err(e) => return Err(SensorError::from_io(&energy_path, &e)),
+            };
+
+            let max_energy_range_path = zone_path.join(RAPL_MAX_ENERGY_RANGE_FILE);
+            let max_energy_range_uj =
+                read_u64(&max_energy_range_path).map_err(|e| SensorError::from_io(&max_energy_range_path, &e))?;
+
+            let name_path = zone_path.join(RAPL_NAME_FILE);
+            let name = fs::read_to_string(&name_path)
+                .map_err(|e| SensorError::from_io(&name_path, &e))?
+                .trim()
+                .to_string();
+
+            zones.push(RaplZoneStatus::new(zone_id, name, energy_uj, max_energy_range_uj));
+        }
+
+        if zones.is_empty() {
+            return Ok(SysPowercapStatus::new(PowercapAccess::Unsupported, zones));
+        }
+
+        zones.sort_by(|a, b| a.zone_id.cmp(&b.zone_id));
+
+        Ok(SysPowercapStatus::new(PowercapAccess::Readable, zones))
+    }
+}
+
+fn read_u64(path: &Path) -> io::Result<u64> {
+    fs::read_to_string(path)?
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
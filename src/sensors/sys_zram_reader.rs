@@ -0,0 +1,100 @@
+use crate::sensors::sensor_error::SensorError;
+use crate::sensors::sensor_root::SensorRoot;
+use crate::sensors::sensor_traits::SensorReader;
+use std::fs;
+
+const SYS_BLOCK_PATH: &str = "/sys/block";
+const ZRAM_DEVICE_PREFIX: &str = "zram";
+const ZRAM_MM_STAT_FILE: &str = "mm_stat";
+const ZRAM_DISK_SIZE_FILE: &str = "disksize";
+
+/// The compression totals across all zram devices. Both sizes are in bytes.
+#[derive(Clone, Debug, Default)]
+pub struct SysZramStatus {
+    /// The number of zram devices found
+    pub device_count: usize,
+    /// The uncompressed size of the data stored
+    pub original_bytes: u64,
+    /// The compressed size of the data stored
+    pub compressed_bytes: u64,
+}
+
+#[derive(Default)]
+pub struct SysZramReader {
+    root: SensorRoot,
+}
+
+impl SensorReader for SysZramReader {
+    type Output = SysZramStatus;
+
+    fn read(&self) -> Result<Self::Output, SensorError> {
+        let path = self.root.resolve(SYS_BLOCK_PATH);
+
+        let entries = match fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(e) => return Err(SensorError::from_io(&path, &e)),
+        };
+
+        let mut status = SysZramStatus::default();
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            if !name.starts_with(ZRAM_DEVICE_PREFIX) {
+                continue;
+            }
+
+            // A device that hasn't been given a disksize isn't in use. Older kernels have no mm_stat for it at all.
+            let disk_size = fs::read_to_string(entry.path().join(ZRAM_DISK_SIZE_FILE))
+                .ok()
+                .and_then(|size| size.trim().parse::<u64>().ok());
+
+            if disk_size == Some(0) {
+                continue;
+            }
+
+            let mm_stat_path = entry.path().join(ZRAM_MM_STAT_FILE);
+
+            let Ok(contents) = fs::read_to_string(&mm_stat_path) else {
+                continue;
+            };
+
+            let (original_bytes, compressed_bytes) = self.parse_mm_stat(&contents).map_err(|e| {
+                SensorError::Parse(format!(
+                    "Invalid file format: {} {}",
+                    mm_stat_path.display(),
+                    e.message()
+                ))
+            })?;
+
+            status.device_count += 1;
+            status.original_bytes += original_bytes;
+            status.compressed_bytes += compressed_bytes;
+        }
+
+        Ok(status)
+    }
+}
+
+impl SysZramReader {
+    pub fn new(root: SensorRoot) -> Self {
+        Self { root }
+    }
+
+    /// mm_stat is a single line that starts with orig_data_size and compr_data_size, in bytes.
+    fn parse_mm_stat(&self, contents: &str) -> Result<(u64, u64), SensorError> {
+        let mut parts = contents.split_whitespace();
+
+        match (parts.next(), parts.next()) {
+            (Some(original), Some(compressed)) => Ok((
+                original
+                    .parse()
+                    .map_err(|_| SensorError::Parse(format!("Invalid value: {original}")))?,
+                compressed
+                    .parse()
+                    .map_err(|_| SensorError::Parse(format!("Invalid value: {compressed}")))?,
+            )),
+            _ => Err(SensorError::Parse(format!("Invalid line format: {}", contents.trim()))),
+        }
+    }
+}
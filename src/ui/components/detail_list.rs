@@ -0,0 +1,53 @@
+use crate::app::Message;
+use crate::fl;
+use cosmic::widget::settings;
+use cosmic::{widget, Element};
+
+const HEADING_FONT_SIZE: u16 = 13;
+
+/// One row of a detail popup
+pub enum DetailListRow {
+    /// Starts a group of items, i.e. the processes of the CPU popup
+    Heading(String),
+    /// A name and its reading, i.e. a core and its usage
+    Item(String, String),
+}
+
+pub struct DetailListProps {
+    pub title: String,
+    pub rows: Vec<DetailListRow>,
+}
+
+/// The content of an indicator's detail popup, with a button across to the dashboard
+pub fn detail_list<'a>(props: DetailListProps) -> Element<'a, Message> {
+    let dashboard_button = widget::button::custom(
+        widget::icon::from_name("utilities-system-monitor-symbolic")
+            .size(16)
+            .icon(),
+    )
+    .on_press(Message::DashboardPopupOpened);
+
+    let mut column = widget::list_column()
+        .padding(2)
+        .spacing(0)
+        .divider_padding(2)
+        .add(settings::item(props.title, dashboard_button));
+
+    if props.rows.is_empty() {
+        column = column.add(widget::text(fl!("detail-no-data")));
+    }
+
+    for row in props.rows {
+        column = match row {
+            DetailListRow::Heading(heading) => {
+                column.add(widget::text(heading).size(HEADING_FONT_SIZE).font(cosmic::iced::Font {
+                    weight: cosmic::iced::font::Weight::Bold,
+                    ..Default::default()
+                }))
+            }
+            DetailListRow::Item(name, value) => column.add(settings::item(name, widget::text(value))),
+        };
+    }
+
+    column.into()
+}
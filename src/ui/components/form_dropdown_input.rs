@@ -0,0 +1,51 @@
+use crate::app::Message;
+use crate::core::settings::{SettingsFormEvent, SettingsFormEventValue};
+use cosmic::iced::window;
+use cosmic::widget::{self, settings};
+use cosmic::Element;
+
+pub struct FormDropdownInputProps<'a> {
+    pub settings_window_id: window::Id,
+    pub label: String,
+    pub value: &'a str,
+    pub values: Vec<&'static str>,
+    pub labels: &'a [String],
+    pub form_value_key: &'static str,
+    pub helper_text: Option<String>,
+    pub helper_text_color: cosmic::iced::Color,
+}
+
+pub fn form_dropdown_input<'a>(props: FormDropdownInputProps<'a>) -> Element<'a, Message> {
+    let FormDropdownInputProps {
+        settings_window_id,
+        label,
+        value,
+        values,
+        labels,
+        form_value_key,
+        helper_text,
+        helper_text_color,
+    } = props;
+
+    let selected = values.iter().position(|option| *option == value);
+
+    let dropdown_input = widget::dropdown(labels, selected, move |index| {
+        Message::SettingsFormUpdate(SettingsFormEvent::StringFieldUpdated(SettingsFormEventValue {
+            settings_window_id,
+            form_value_key,
+            value: values[index].to_string(),
+        }))
+    });
+
+    let mut column = widget::column().spacing(6);
+    column = column.push(settings::item(label, dropdown_input));
+
+    if let Some(helper) = helper_text {
+        let helper_text_widget = widget::text(helper)
+            .size(12)
+            .class(cosmic::theme::Text::from(helper_text_color));
+        column = column.push(widget::container(helper_text_widget).width(cosmic::iced::Length::Fill));
+    }
+
+    column.into()
+}
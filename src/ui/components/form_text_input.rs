@@ -11,6 +11,8 @@ pub struct FormTextInputProps<'a> {
     pub value: &'a str,
     pub form_value_key: &'static str,
     pub validator: Option<fn(&str) -> Result<(), String>>,
+    /// An error found by comparing the value with the other fields of the form
+    pub form_error: Option<String>,
     pub error_color: Color,
     pub helper_text: Option<String>,
     pub helper_text_color: Color,
@@ -23,6 +25,7 @@ pub fn form_text_input<'a>(props: FormTextInputProps<'a>) -> Element<'a, Message
         value,
         form_value_key,
         validator,
+        form_error,
         error_color,
         helper_text,
         helper_text_color,
@@ -46,7 +49,7 @@ pub fn form_text_input<'a>(props: FormTextInputProps<'a>) -> Element<'a, Message
         });
 
     let validator = validator.unwrap_or(|_| Ok(()));
-    let validation_result = validator(value);
+    let validation_result = validator(value).and_then(|_| form_error.map_or(Ok(()), Err));
 
     let field = match &validation_result {
         Ok(_) => text_input.width(150),
@@ -0,0 +1,139 @@
+use crate::app::Message;
+use crate::core::metric_history::MetricSample;
+use cosmic::iced::mouse::Cursor;
+use cosmic::iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use cosmic::iced::{Color, Length, Point, Rectangle};
+use cosmic::{Element, Renderer, Theme};
+use std::time::{Duration, Instant};
+
+const LINE_WIDTH: f32 = 1.5;
+/// The horizontal lines drawn across the chart, as fractions of its height
+const GRID_LINES: [f32; 3] = [0.25, 0.5, 0.75];
+const FILL_ALPHA: f32 = 0.15;
+
+/// One line of a history chart, i.e. the receive rate in the network chart
+pub struct HistoryChartSeries {
+    /// The samples to draw, oldest first
+    pub samples: Vec<MetricSample>,
+    pub colour: Color,
+}
+
+pub struct HistoryChartProps {
+    pub series: Vec<HistoryChartSeries>,
+    /// The span of time across the chart, ending at `now`
+    pub span: Duration,
+    pub now: Instant,
+    /// The value at the top of the chart, or None to scale to the largest value shown
+    pub max_value: Option<f64>,
+    pub width: f32,
+    pub height: f32,
+    pub grid_colour: Color,
+}
+
+struct HistoryChart {
+    series: Vec<HistoryChartSeries>,
+    span: Duration,
+    now: Instant,
+    max_value: f64,
+    grid_colour: Color,
+}
+
+impl HistoryChart {
+    fn point(&self, timestamp: Instant, value: f64, bounds: &Rectangle) -> Point {
+        let age = self.now.saturating_duration_since(timestamp).as_secs_f32();
+        let x = bounds.width * (1.0 - age / self.span.as_secs_f32()).clamp(0.0, 1.0);
+        let ratio = (value / self.max_value).clamp(0.0, 1.0) as f32;
+        let y = LINE_WIDTH / 2.0 + (bounds.height - LINE_WIDTH).max(0.0) * (1.0 - ratio);
+
+        Point::new(x, y)
+    }
+}
+
+impl canvas::Program<Message, Theme, Renderer> for HistoryChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+
+        for fraction in GRID_LINES {
+            let y = bounds.height * fraction;
+            let line = Path::line(Point::new(0.0, y), Point::new(bounds.width, y));
+            frame.stroke(&line, Stroke::default().with_color(self.grid_colour).with_width(1.0));
+        }
+
+        for series in self.series.iter().filter(|series| series.samples.len() > 1) {
+            let points: Vec<Point> = series
+                .samples
+                .iter()
+                .map(|sample| self.point(sample.timestamp, sample.average, &bounds))
+                .collect();
+
+            // The area under the line, or between the minimum and maximum of downsampled samples
+            let area = Path::new(|builder| {
+                for (index, sample) in series.samples.iter().enumerate() {
+                    let point = self.point(sample.timestamp, sample.max, &bounds);
+                    if index == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+                for sample in series.samples.iter().rev() {
+                    let is_downsampled = sample.min < sample.max;
+                    let value = if is_downsampled { sample.min } else { 0.0 };
+                    builder.line_to(self.point(sample.timestamp, value, &bounds));
+                }
+                builder.close();
+            });
+            frame.fill(
+                &area,
+                Color {
+                    a: FILL_ALPHA,
+                    ..series.colour
+                },
+            );
+
+            let line = Path::new(|builder| {
+                builder.move_to(points[0]);
+                for point in points.iter().skip(1) {
+                    builder.line_to(*point);
+                }
+            });
+            frame.stroke(
+                &line,
+                Stroke::default().with_color(series.colour).with_width(LINE_WIDTH),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+pub fn history_chart<'a>(props: HistoryChartProps) -> Element<'a, Message> {
+    let largest = props
+        .series
+        .iter()
+        .flat_map(|series| series.samples.iter().map(|sample| sample.max))
+        .fold(0.0, f64::max);
+    let max_value = props.max_value.unwrap_or(largest);
+    // A flat line along the bottom when every value is zero
+    let max_value = if max_value > 0.0 { max_value } else { 1.0 };
+
+    canvas::Canvas::new(HistoryChart {
+        series: props.series,
+        span: props.span,
+        now: props.now,
+        max_value,
+        grid_colour: props.grid_colour,
+    })
+    .width(Length::Fixed(props.width))
+    .height(Length::Fixed(props.height))
+    .into()
+}
@@ -1,10 +1,13 @@
 use crate::app::Message;
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
 use crate::ui::components::indicator_label::{indicator_label, IndicatorLabelProps};
 use crate::ui::components::indicator_value::{indicator_value, IndicatorValueProps};
+use crate::ui::components::sparkline::{sparkline, SparklineProps};
 use crate::ui::components::svg_icon::{svg_icon, SvgIconProps};
 use cosmic::iced::{Alignment, Color};
 use cosmic::iced_widget::Row;
-use cosmic::widget::Column;
+use cosmic::widget::tooltip::Position;
+use cosmic::widget::{tooltip, Column};
 use cosmic::Element;
 
 pub struct IndicatorValueItem<'a> {
@@ -12,6 +15,23 @@ pub struct IndicatorValueItem<'a> {
     pub icon: Option<SvgIconProps<'a>>,
 }
 
+/// Shown when the monitor behind an indicator is failing
+pub struct IndicatorPlaceholder {
+    /// Replaces the values, i.e. when there has never been a value to show
+    pub text: Option<String>,
+    /// The colour the label and values are dimmed to
+    pub colour: Color,
+    /// The error, shown on hover
+    pub tooltip: String,
+}
+
+/// The colour of a value that has crossed one of its monitor's thresholds
+pub struct IndicatorThreshold {
+    pub colour: Color,
+    /// Whether the label takes the colour as well as the value
+    pub colour_label: bool,
+}
+
 pub struct IndicatorProps<'a> {
     pub label_text: Option<String>,
     pub label_colour: Color,
@@ -22,16 +42,53 @@ pub struct IndicatorProps<'a> {
     pub spacing: u16,
     pub icon_spacing: u16,
     pub values: Vec<IndicatorValueItem<'a>>,
+    pub display_mode: IndicatorDisplayMode,
+    /// The recent values, drawn when the display mode includes a graph
+    pub graph: Option<SparklineProps>,
+    /// The full reading, shown on hover
+    pub tooltip: Option<String>,
+    pub threshold: Option<IndicatorThreshold>,
+    pub placeholder: Option<IndicatorPlaceholder>,
 }
 
-pub fn indicator<'a>(core: &'a cosmic::Core, props: IndicatorProps<'a>) -> Option<Element<'a, Message>> {
+pub fn indicator<'a>(core: &'a cosmic::Core, mut props: IndicatorProps<'a>) -> Option<Element<'a, Message>> {
+    let placeholder = props.placeholder.take();
+    let tooltip_text = props.tooltip.take();
+    let dimmed_colour = placeholder.as_ref().map(|placeholder| placeholder.colour);
+    // A failing monitor's reading is stale, so it is dimmed rather than coloured by its thresholds
+    let value_colour = dimmed_colour.or(props.threshold.as_ref().map(|threshold| threshold.colour));
+    let label_colour = match &props.threshold {
+        Some(threshold) if threshold.colour_label => threshold.colour,
+        _ => props.label_colour,
+    };
+
+    let mut graph = props.graph.take().filter(|_| props.display_mode.shows_graph());
+
+    if let Some(text) = placeholder.as_ref().and_then(|placeholder| placeholder.text.clone()) {
+        props.values = vec![IndicatorValueItem { text, icon: None }];
+        graph = None;
+    }
+
+    if let (Some(graph), Some(colour)) = (graph.as_mut(), dimmed_colour) {
+        for series in graph.series.iter_mut() {
+            series.colour = colour;
+        }
+    }
+
+    let graph = graph.and_then(sparkline);
+
+    // The values are still shown in graph mode until there is a history to draw
+    if !props.display_mode.shows_number() && graph.is_some() {
+        props.values.clear();
+    }
+
     let label = indicator_label(
         core,
         IndicatorLabelProps {
             text: props.label_text,
             font_size: props.font_size,
             font: props.font,
-            colour: props.label_colour,
+            colour: dimmed_colour.unwrap_or(label_colour),
         },
     );
 
@@ -52,6 +109,7 @@ pub fn indicator<'a>(core: &'a cosmic::Core, props: IndicatorProps<'a>) -> Optio
                 font: props.font,
                 width: props.value_width,
                 horizontal: props.horizontal,
+                colour: value_colour,
             },
         ) {
             row = row.push(value_el);
@@ -66,26 +124,32 @@ pub fn indicator<'a>(core: &'a cosmic::Core, props: IndicatorProps<'a>) -> Optio
         value_rows.push(row.into());
     }
 
-    if props.horizontal {
+    let element: Element<Message> = if props.horizontal {
         let mut values_container = Row::new().align_y(Alignment::Center);
         for v in value_rows {
             values_container = values_container.spacing(props.spacing).push(v);
         }
+        values_container = values_container.push_maybe(graph);
         content.push(values_container.into());
 
-        Some(
-            Row::from_vec(content)
-                .spacing(props.spacing)
-                .align_y(Alignment::Center)
-                .into(),
-        )
+        Row::from_vec(content)
+            .spacing(props.spacing)
+            .align_y(Alignment::Center)
+            .into()
     } else {
         let mut values_container = Column::new().align_x(Alignment::Center);
         for v in value_rows {
             values_container = values_container.push(v);
         }
+        values_container = values_container.push_maybe(graph);
         content.push(values_container.into());
 
-        Some(Column::from_vec(content).align_x(Alignment::Center).into())
+        Column::from_vec(content).align_x(Alignment::Center).into()
+    };
+
+    // A failing monitor's error matters more than its last reading
+    match placeholder.map(|placeholder| placeholder.tooltip).or(tooltip_text) {
+        Some(text) => Some(tooltip(element, core.applet.text(text), Position::FollowCursor).into()),
+        None => Some(element),
     }
 }
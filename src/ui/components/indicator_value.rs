@@ -1,5 +1,5 @@
 use crate::app::Message;
-use cosmic::iced::Alignment;
+use cosmic::iced::{Alignment, Color};
 use cosmic::{widget, Element};
 use cosmic::font::Font;
 
@@ -9,15 +9,20 @@ pub struct IndicatorValueProps<'a> {
     pub font: &'a Font,
     pub width: Option<f32>,
     pub horizontal: bool,
+    /// Overrides the theme text colour
+    pub colour: Option<Color>,
 }
 
 pub fn indicator_value<'core>(
     core: &'core cosmic::Core,
     props: IndicatorValueProps,
 ) -> Option<Element<'core, Message>> {
-    let value_text = core.applet.text(props.text)
+    let mut value_text = core.applet.text(props.text)
         .size(props.font_size)
         .font(*props.font);
+    if let Some(colour) = props.colour {
+        value_text = value_text.class(cosmic::theme::Text::from(colour));
+    }
     if props.horizontal {
         let mut container = widget::container(value_text)
             .align_x(Alignment::End)
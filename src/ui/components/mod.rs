@@ -5,4 +5,8 @@ pub mod svg_icon;
 pub mod indicator;
 pub mod form_text_input;
 pub mod form_checkbox_input;
-pub mod form_theme_colour_picker_input;
\ No newline at end of file
+pub mod form_theme_colour_picker_input;
+pub mod form_dropdown_input;
+pub mod sparkline;
+pub mod history_chart;
+pub mod detail_list;
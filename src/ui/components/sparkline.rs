@@ -0,0 +1,106 @@
+use crate::app::Message;
+use cosmic::iced::mouse::Cursor;
+use cosmic::iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use cosmic::iced::{Color, Length, Point, Rectangle};
+use cosmic::{Element, Renderer, Theme};
+
+/// How many times the font size a graph is wide when there is no fixed value width to match
+const HORIZONTAL_WIDTH_FACTOR: f32 = 3.0;
+/// A vertical panel has less room across, so the graph is narrower there
+const VERTICAL_WIDTH_FACTOR: f32 = 2.0;
+const LINE_WIDTH: f32 = 1.5;
+
+/// One line of a sparkline, i.e. the receive rate of the network indicator
+pub struct SparklineSeries {
+    /// The values to draw, oldest first
+    pub values: Vec<f64>,
+    pub colour: Color,
+}
+
+pub struct SparklineProps {
+    pub series: Vec<SparklineSeries>,
+    /// The value at the top of the graph, or None to scale to the largest value shown
+    pub max_value: Option<f64>,
+    pub font_size: u16,
+    /// The width of the values, so the graph takes up the same space when the indicator size is fixed
+    pub width: Option<f32>,
+    pub horizontal: bool,
+}
+
+struct Sparkline {
+    series: Vec<SparklineSeries>,
+    max_value: f64,
+}
+
+impl canvas::Program<Message, Theme, Renderer> for Sparkline {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        // Keep the line inside the frame when it is at the top or the bottom
+        let inset = LINE_WIDTH / 2.0;
+        let height = (bounds.height - LINE_WIDTH).max(0.0);
+
+        for series in self.series.iter().filter(|series| series.values.len() > 1) {
+            let step = bounds.width / (series.values.len() - 1) as f32;
+            let path = Path::new(|builder| {
+                for (index, value) in series.values.iter().enumerate() {
+                    let ratio = (value / self.max_value).clamp(0.0, 1.0) as f32;
+                    let point = Point::new(index as f32 * step, inset + height * (1.0 - ratio));
+
+                    if index == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            });
+
+            frame.stroke(
+                &path,
+                Stroke::default().with_color(series.colour).with_width(LINE_WIDTH),
+            );
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+pub fn sparkline<'a>(props: SparklineProps) -> Option<Element<'a, Message>> {
+    if props.series.iter().all(|series| series.values.is_empty()) {
+        return None;
+    }
+
+    let largest = props
+        .series
+        .iter()
+        .flat_map(|series| series.values.iter().copied())
+        .fold(0.0, f64::max);
+    let max_value = props.max_value.unwrap_or(largest);
+    // A flat line along the bottom when every value is zero
+    let max_value = if max_value > 0.0 { max_value } else { 1.0 };
+
+    let font_size = f32::from(props.font_size);
+    let width = props.width.unwrap_or(if props.horizontal {
+        font_size * HORIZONTAL_WIDTH_FACTOR
+    } else {
+        font_size * VERTICAL_WIDTH_FACTOR
+    });
+
+    Some(
+        canvas::Canvas::new(Sparkline {
+            series: props.series,
+            max_value,
+        })
+        .width(Length::Fixed(width))
+        .height(Length::Fixed(font_size))
+        .into(),
+    )
+}
@@ -0,0 +1,235 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, MAIN_SETTINGS_WINDOW_ID};
+use crate::core::app_colours::{ACCENT_GREEN, ACCENT_RED, EXT_WARM_GREY};
+use crate::fl;
+use crate::monitors::cpu_monitor::CPU_USAGE_METRIC;
+use crate::monitors::disk_monitor::{DISK_READ_METRIC, DISK_WRITE_METRIC};
+use crate::monitors::memory_monitor::{MEMORY_TOTAL_METRIC, MEMORY_USED_METRIC};
+use crate::monitors::network_monitor::{NETWORK_RX_METRIC, NETWORK_TX_METRIC};
+use crate::ui::components::history_chart::{history_chart, HistoryChartProps, HistoryChartSeries};
+use crate::ui::indicators::format_bytes_per_second;
+use crate::ui::indicators::memory::format_kib;
+use cosmic::iced::Color;
+use cosmic::iced_widget::{container, Container};
+use cosmic::widget::settings;
+use cosmic::{widget, Theme};
+use std::time::{Duration, Instant};
+
+const CHART_WIDTH: f32 = 360.0;
+const CHART_HEIGHT: f32 = 72.0;
+const READOUT_FONT_SIZE: u16 = 12;
+
+/// The span of history shown by the dashboard charts
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum DashboardRange {
+    #[default]
+    OneMinute,
+    TenMinutes,
+    OneHour,
+}
+
+impl DashboardRange {
+    pub const ALL: [DashboardRange; 3] = [
+        DashboardRange::OneMinute,
+        DashboardRange::TenMinutes,
+        DashboardRange::OneHour,
+    ];
+
+    pub fn span(&self) -> Duration {
+        match self {
+            DashboardRange::OneMinute => Duration::from_secs(60),
+            DashboardRange::TenMinutes => Duration::from_secs(10 * 60),
+            DashboardRange::OneHour => Duration::from_secs(60 * 60),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            DashboardRange::OneMinute => fl!("dashboard-range-one-minute"),
+            DashboardRange::TenMinutes => fl!("dashboard-range-ten-minutes"),
+            DashboardRange::OneHour => fl!("dashboard-range-one-hour"),
+        }
+    }
+}
+
+struct DashboardSeries {
+    name: String,
+    metric: &'static str,
+    colour: Color,
+    format: fn(f64, &AppConfiguration) -> String,
+}
+
+struct DashboardSection {
+    title: String,
+    series: Vec<DashboardSeries>,
+    max_value: Option<f64>,
+}
+
+pub struct Dashboard;
+
+impl Dashboard {
+    pub fn draw(app_state: &AppState) -> Container<'_, Message, Theme> {
+        let range = app_state.dashboard_range();
+        let now = Instant::now();
+
+        let settings_button = widget::button::custom(widget::icon::from_name("emblem-system-symbolic").size(16).icon())
+            .on_press(Message::SettingsPopupOpened(MAIN_SETTINGS_WINDOW_ID.clone()));
+
+        let mut range_buttons = widget::row().spacing(4);
+        for option in DashboardRange::ALL {
+            let button = if option == range {
+                widget::button::suggested(option.label())
+            } else {
+                widget::button::standard(option.label())
+            };
+
+            range_buttons = range_buttons.push(button.on_press(Message::DashboardRangeSelected(option)));
+        }
+
+        let mut column = widget::list_column()
+            .padding(2)
+            .spacing(0)
+            .divider_padding(2)
+            .add(settings::item(fl!("dashboard-title"), settings_button))
+            .add(range_buttons);
+
+        for section in Self::sections(app_state) {
+            column = column.add(Self::draw_section(app_state, section, range.span(), now));
+        }
+
+        container(column)
+    }
+
+    fn draw_section(
+        app_state: &AppState,
+        section: DashboardSection,
+        span: Duration,
+        now: Instant,
+    ) -> cosmic::Element<'_, Message> {
+        let configuration = app_state.configuration();
+        let history = app_state.metric_history();
+
+        let mut content = widget::column()
+            .spacing(4)
+            .push(widget::text(section.title).font(cosmic::iced::Font {
+                weight: cosmic::iced::font::Weight::Bold,
+                ..Default::default()
+            }));
+
+        let mut chart_series = Vec::new();
+
+        for series in section.series {
+            let readout = match history.summary(series.metric, span, now) {
+                Some(summary) => fl!(
+                    "dashboard-readout",
+                    name = series.name,
+                    current = (series.format)(summary.latest, configuration),
+                    min = (series.format)(summary.min, configuration),
+                    max = (series.format)(summary.max, configuration),
+                    average = (series.format)(summary.average, configuration)
+                ),
+                None => fl!("dashboard-no-data", name = series.name),
+            };
+
+            content = content.push(
+                widget::text(readout)
+                    .size(READOUT_FONT_SIZE)
+                    .class(cosmic::theme::Text::from(series.colour)),
+            );
+
+            chart_series.push(HistoryChartSeries {
+                samples: history.query(series.metric, span, now),
+                colour: series.colour,
+            });
+        }
+
+        let grid_colour = app_state
+            .app_colours()
+            .get(EXT_WARM_GREY)
+            .map_or(Color::from_rgba(0.5, 0.5, 0.5, 0.3), |c| {
+                Color::new(c.red, c.green, c.blue, 0.3)
+            });
+
+        content
+            .push(history_chart(HistoryChartProps {
+                series: chart_series,
+                span,
+                now,
+                max_value: section.max_value,
+                width: CHART_WIDTH,
+                height: CHART_HEIGHT,
+                grid_colour,
+            }))
+            .into()
+    }
+
+    fn sections(app_state: &AppState) -> Vec<DashboardSection> {
+        let configuration = app_state.configuration();
+        let colour = |key: Option<&str>| {
+            key.and_then(|key| app_state.app_colours().get(key))
+                .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+        };
+
+        vec![
+            DashboardSection {
+                title: fl!("dashboard-cpu"),
+                series: vec![DashboardSeries {
+                    name: fl!("dashboard-cpu-usage"),
+                    metric: CPU_USAGE_METRIC,
+                    colour: colour(configuration.cpu.label_colour.as_deref()),
+                    format: |value, _| format!("{:.1}%", value),
+                }],
+                max_value: Some(100.0),
+            },
+            DashboardSection {
+                title: fl!("dashboard-memory"),
+                series: vec![DashboardSeries {
+                    name: fl!("dashboard-memory-used"),
+                    metric: MEMORY_USED_METRIC,
+                    colour: colour(configuration.memory.label_colour.as_deref()),
+                    format: |value, configuration| format_kib(value as u64, configuration),
+                }],
+                max_value: app_state
+                    .metric_history()
+                    .latest(MEMORY_TOTAL_METRIC)
+                    .map(|sample| sample.average),
+            },
+            DashboardSection {
+                title: fl!("dashboard-network"),
+                series: vec![
+                    DashboardSeries {
+                        name: fl!("dashboard-network-receive"),
+                        metric: NETWORK_RX_METRIC,
+                        colour: colour(Some(ACCENT_GREEN)),
+                        format: |value, configuration| format_bytes_per_second(value as u64, configuration),
+                    },
+                    DashboardSeries {
+                        name: fl!("dashboard-network-transmit"),
+                        metric: NETWORK_TX_METRIC,
+                        colour: colour(Some(ACCENT_RED)),
+                        format: |value, configuration| format_bytes_per_second(value as u64, configuration),
+                    },
+                ],
+                max_value: None,
+            },
+            DashboardSection {
+                title: fl!("dashboard-disk"),
+                series: vec![
+                    DashboardSeries {
+                        name: fl!("dashboard-disk-read"),
+                        metric: DISK_READ_METRIC,
+                        colour: colour(Some(ACCENT_GREEN)),
+                        format: |value, configuration| format_bytes_per_second(value as u64, configuration),
+                    },
+                    DashboardSeries {
+                        name: fl!("dashboard-disk-write"),
+                        metric: DISK_WRITE_METRIC,
+                        colour: colour(Some(ACCENT_RED)),
+                        format: |value, configuration| format_bytes_per_second(value as u64, configuration),
+                    },
+                ],
+                max_value: None,
+            },
+        ]
+    }
+}
@@ -0,0 +1,188 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, ARC_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::arc_monitor::{ArcStats, ARC_SIZE_METRIC};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::indicators::{indicator_threshold, sparkline_series, template_value_text};
+use cosmic::iced::Color;
+use cosmic::Element;
+
+impl MonitorStats for ArcStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+        let core = app_state.core();
+        let configuration = app_state.configuration();
+
+        if self.is_hidden(configuration) {
+            return None;
+        }
+
+        let text = if configuration.arc.hide_label {
+            None
+        } else {
+            configuration.arc.label_text.clone()
+        };
+
+        let display_item_color = self.label_colour(app_state);
+        let font_size = app_state.font_size(horizontal);
+
+        let template_value =
+            configuration.arc.format_template.as_ref().map(|template| {
+                template_value_text(app_state, template, &self.template_values(configuration), font_size)
+            });
+
+        let max_text_width = match &template_value {
+            Some((_, width)) => *width,
+            None if configuration.general.fix_indicator_size => app_state
+                .app_text_measurements()
+                .measure(self.max_label_text(configuration), font_size),
+            None => None,
+        };
+
+        let values = match template_value {
+            // The template shows the size and the hit ratio as one value
+            Some((text, _)) => vec![IndicatorValueItem { text, icon: None }],
+            None => {
+                let size_text = if configuration.arc.show_target {
+                    format!(
+                        "{}/{}",
+                        format_size(self.size_bytes, configuration),
+                        format_size(self.target_bytes, configuration)
+                    )
+                } else {
+                    format_size(self.size_bytes, configuration)
+                };
+
+                vec![
+                    IndicatorValueItem {
+                        text: size_text,
+                        icon: None,
+                    },
+                    IndicatorValueItem {
+                        text: format!("{:.1}%", self.hit_ratio),
+                        icon: None,
+                    },
+                ]
+            }
+        };
+
+        let display_mode = configuration.arc.display_mode;
+        let graph = display_mode.shows_graph().then(|| SparklineProps {
+            series: vec![sparkline_series(app_state, ARC_SIZE_METRIC, display_item_color)],
+            max_value: None,
+            font_size,
+            width: max_text_width,
+            horizontal,
+        });
+
+        indicator(
+            core,
+            IndicatorProps {
+                label_text: text,
+                label_colour: display_item_color,
+                font_size,
+                font: app_state.active_interface_font(),
+                value_width: max_text_width,
+                horizontal,
+                spacing: app_state.core().applet.suggested_padding(true),
+                icon_spacing: 2,
+                values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.arc.thresholds),
+                placeholder: app_state.indicator_placeholder(&ARC_SETTINGS_WINDOW_ID),
+            },
+        )
+    }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        ArcStats::metrics(self)
+    }
+}
+
+impl ArcStats {
+    fn label_colour(&self, app_state: &AppState) -> Color {
+        app_state
+            .configuration()
+            .arc
+            .label_colour
+            .as_deref()
+            .and_then(|key| app_state.app_colours().get(key))
+            .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+    }
+
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        let (bytes_per_unit, unit) = if app_config.general.use_iec_units {
+            (1024.0 * 1024.0 * 1024.0, "GiB")
+        } else {
+            (1_000_000_000.0, "GB")
+        };
+        // The cache can run a little over its target, so size both values against the larger
+        let max = self.size_bytes.max(self.target_bytes) as f64 / bytes_per_unit;
+        let size = |bytes: u64| TemplateValue::Number {
+            value: bytes as f64 / bytes_per_unit,
+            max,
+            precision: 1,
+            unit: "",
+        };
+
+        vec![
+            ("size", size(self.size_bytes)),
+            ("target", size(self.target_bytes)),
+            (
+                "ratio",
+                TemplateValue::Number {
+                    value: self.hit_ratio,
+                    max: 100.0,
+                    precision: 1,
+                    unit: "",
+                },
+            ),
+            ("unit", TemplateValue::Text(unit.to_string())),
+        ]
+    }
+
+    fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
+        match (app_config.arc.show_target, app_config.general.use_iec_units) {
+            (true, true) => "99.9GiB/99.9GiB",
+            (true, false) => "99.9GB/99.9GB",
+            (false, true) => "99.9GiB",
+            (false, false) => "99.9GB",
+        }
+    }
+
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        fl!(
+            "tooltip-arc",
+            size = format_size(self.size_bytes, app_config),
+            target = format_size(self.target_bytes, app_config),
+            ratio = format!("{:.1}", self.hit_ratio)
+        )
+    }
+
+    fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
+        app_config.arc.hide_indicator
+    }
+}
+
+fn format_size(bytes: u64, app_config: &AppConfiguration) -> String {
+    if app_config.general.use_iec_units {
+        let gib = bytes as f64 / 1024.0 / 1024.0 / 1024.0;
+        if gib > 99.9 {
+            format!("{:.0}GiB", gib.round())
+        } else {
+            format!("{:.1}GiB", gib)
+        }
+    } else {
+        let gb = bytes as f64 / 1_000_000_000.0;
+        if gb > 99.9 {
+            format!("{:.0}GB", gb.round())
+        } else {
+            format!("{:.1}GB", gb)
+        }
+    }
+}
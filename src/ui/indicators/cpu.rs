@@ -1,13 +1,20 @@
+use crate::alerts::alert_evaluator::AlertValue;
 use crate::app::{AppState, Message};
-use crate::configuration::app_configuration::AppConfiguration;
-use crate::monitors::cpu_monitor::CpuStats;
+use crate::configuration::app_configuration::{AppConfiguration, CPU_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::cpu_monitor::{CpuStats, CPU_USAGE_METRIC};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::detail_list::{detail_list, DetailListProps, DetailListRow};
 use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::indicators::{indicator_threshold, sparkline_series, template_value_text};
 use cosmic::iced::Color;
 use cosmic::Element;
 
-impl CpuStats {
-
-    pub fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+impl MonitorStats for CpuStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
         let core = app_state.core();
         let configuration = app_state.configuration();
 
@@ -20,19 +27,31 @@ impl CpuStats {
         let display_item_color = self.label_colour(app_state);
         let font_size = app_state.font_size(horizontal);
 
-        let max_text_width = if configuration.general.fix_indicator_size {
-            app_state
-                .app_text_measurements()
-                .measure(self.max_label_text(), font_size)
-        } else {
-            None
+        let (value_text, max_text_width) = match &configuration.cpu.format_template {
+            Some(template) => template_value_text(app_state, template, &self.template_values(), font_size),
+            None if configuration.general.fix_indicator_size => (
+                self.value(configuration),
+                app_state
+                    .app_text_measurements()
+                    .measure(self.max_label_text(), font_size),
+            ),
+            None => (self.value(configuration), None),
         };
 
         let values = vec![IndicatorValueItem {
-            text: self.value(app_state.configuration()),
+            text: value_text,
             icon: None,
         }];
 
+        let display_mode = configuration.cpu.display_mode;
+        let graph = display_mode.shows_graph().then(|| SparklineProps {
+            series: vec![sparkline_series(app_state, CPU_USAGE_METRIC, display_item_color)],
+            max_value: Some(100.0),
+            font_size,
+            width: max_text_width,
+            horizontal,
+        });
+
         indicator(
             core,
             IndicatorProps {
@@ -45,10 +64,51 @@ impl CpuStats {
                 spacing: app_state.core().applet.suggested_padding(true),
                 icon_spacing: 2,
                 values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.cpu.thresholds),
+                placeholder: app_state.indicator_placeholder(&CPU_SETTINGS_WINDOW_ID),
             },
         )
     }
 
+    fn metrics(&self) -> Vec<MetricValue> {
+        CpuStats::metrics(self)
+    }
+
+    fn alert_values(&self) -> Vec<AlertValue> {
+        CpuStats::alert_values(self)
+    }
+
+    fn draw_detail<'app>(&self, app_state: &'app AppState) -> Option<Element<'app, Message>> {
+        let mut rows = vec![DetailListRow::Item(fl!("detail-cpu-total"), self.value(app_state.configuration()))];
+
+        if !self.core_usage_percent.is_empty() {
+            rows.push(DetailListRow::Heading(fl!("detail-cpu-cores")));
+            rows.extend(self.core_usage_percent.iter().enumerate().map(|(index, usage)| {
+                DetailListRow::Item(fl!("detail-cpu-core", index = index), format!("{:.1}%", usage))
+            }));
+        }
+
+        if !self.top_processes.is_empty() {
+            rows.push(DetailListRow::Heading(fl!("detail-cpu-processes")));
+            rows.extend(self.top_processes.iter().map(|process| {
+                DetailListRow::Item(
+                    fl!("detail-cpu-process", name = process.name.clone(), pid = process.pid),
+                    format!("{:.1}%", process.usage_percent),
+                )
+            }));
+        }
+
+        Some(detail_list(DetailListProps {
+            title: fl!("detail-cpu-title"),
+            rows,
+        }))
+    }
+}
+
+impl CpuStats {
     fn label_colour(&self, app_state: &AppState) -> Color {
         app_state
             .configuration()
@@ -67,10 +127,26 @@ impl CpuStats {
         }
     }
 
+    fn template_values(&self) -> Vec<(&'static str, TemplateValue)> {
+        vec![(
+            "usage",
+            TemplateValue::Number {
+                value: self.cpu_usage_percent,
+                max: 100.0,
+                precision: 1,
+                unit: "",
+            },
+        )]
+    }
+
     fn max_label_text(&self) -> &'static str {
         "99.9%"
     }
 
+    fn tooltip(&self, _app_config: &AppConfiguration) -> String {
+        fl!("tooltip-cpu", usage = format!("{:.1}%", self.cpu_usage_percent))
+    }
+
     fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
         app_config.cpu.hide_indicator
     }
@@ -1,16 +1,25 @@
+use crate::alerts::alert_evaluator::AlertValue;
 use crate::app::{AppState, Message};
-use crate::configuration::app_configuration::AppConfiguration;
-use crate::monitors::disk_monitor::DiskStats;
+use crate::configuration::app_configuration::{AppConfiguration, DISK_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::disk_monitor::{DiskStats, DISK_READ_METRIC, DISK_WRITE_METRIC};
 use crate::core::app_colours::{ACCENT_GREEN, ACCENT_RED};
 use crate::core::app_icons::{READ_ICON, WRITE_ICON};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::detail_list::{detail_list, DetailListProps, DetailListRow};
 use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
 use crate::ui::components::svg_icon::SvgIconProps;
 use cosmic::iced::Color;
 use cosmic::Element;
-use crate::ui::indicators::format_bytes_per_second;
+use crate::ui::indicators::{
+    bytes_per_second_template_value, format_bytes_per_second, indicator_threshold, sparkline_series, template_value_text,
+};
 
-impl DiskStats {
-    pub fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+impl MonitorStats for DiskStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
         let core = app_state.core();
         let configuration = app_state.configuration();
 
@@ -24,45 +33,77 @@ impl DiskStats {
         let font_size = app_state.font_size(horizontal);
         let icon_size = app_state.icon_size();
 
-        let max_text_width = if configuration.general.fix_indicator_size {
-            app_state
+        let template_value = configuration
+            .disk
+            .format_template
+            .as_ref()
+            .map(|template| template_value_text(app_state, template, &self.template_values(configuration), font_size));
+
+        let max_text_width = match &template_value {
+            Some((_, width)) => *width,
+            None if configuration.general.fix_indicator_size => app_state
                 .app_text_measurements()
-                .measure(self.max_label_text(configuration), font_size)
-        } else {
-            None
+                .measure(self.max_label_text(configuration), font_size),
+            None => None,
         };
 
         let mut values: Vec<IndicatorValueItem> = Vec::new();
-        values.push(IndicatorValueItem {
-            text: self.read_value(app_state.configuration()),
-            icon: if horizontal {
-                Some(SvgIconProps {
-                    icon: app_state.app_icons().get(READ_ICON),
-                    size: icon_size,
-                    colour: app_state
-                        .app_colours()
-                        .get(ACCENT_GREEN)
-                        .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
-                })
-            } else {
-                None
-            },
-        });
+        if let Some((text, _)) = template_value {
+            // The template shows both rates as one value, so without their icons
+            values.push(IndicatorValueItem { text, icon: None });
+        } else {
+            values.push(IndicatorValueItem {
+                text: self.read_value(app_state.configuration()),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(READ_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_GREEN)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
 
-        values.push(IndicatorValueItem {
-            text: self.write_value(app_state.configuration()),
-            icon: if horizontal {
-                Some(SvgIconProps {
-                    icon: app_state.app_icons().get(WRITE_ICON),
-                    size: icon_size,
-                    colour: app_state
-                        .app_colours()
-                        .get(ACCENT_RED)
-                        .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
-                })
-            } else {
-                None
-            },
+            values.push(IndicatorValueItem {
+                text: self.write_value(app_state.configuration()),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(WRITE_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_RED)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
+        }
+
+        let display_mode = configuration.disk.display_mode;
+        let graph = display_mode.shows_graph().then(|| {
+            let accent = |key: &str| {
+                app_state
+                    .app_colours()
+                    .get(key)
+                    .map_or(display_item_color, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+            };
+
+            SparklineProps {
+                series: vec![
+                    sparkline_series(app_state, DISK_READ_METRIC, accent(ACCENT_GREEN)),
+                    sparkline_series(app_state, DISK_WRITE_METRIC, accent(ACCENT_RED)),
+                ],
+                max_value: None,
+                font_size,
+                width: max_text_width,
+                horizontal,
+            }
         });
 
         indicator(
@@ -77,9 +118,49 @@ impl DiskStats {
                 spacing: app_state.core().applet.suggested_padding(true),
                 icon_spacing: 2,
                 values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.disk.thresholds),
+                placeholder: app_state.indicator_placeholder(&DISK_SETTINGS_WINDOW_ID),
             },
         )
     }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        DiskStats::metrics(self)
+    }
+
+    fn alert_values(&self) -> Vec<AlertValue> {
+        DiskStats::alert_values(self)
+    }
+
+    fn draw_detail<'app>(&self, app_state: &'app AppState) -> Option<Element<'app, Message>> {
+        let configuration = app_state.configuration();
+
+        let rows = self
+            .devices
+            .iter()
+            .map(|device| {
+                DetailListRow::Item(
+                    device.name.clone(),
+                    fl!(
+                        "detail-disk-rates",
+                        read = format_bytes_per_second(device.read_bytes, configuration),
+                        write = format_bytes_per_second(device.written_bytes, configuration)
+                    ),
+                )
+            })
+            .collect();
+
+        Some(detail_list(DetailListProps {
+            title: fl!("detail-disk-title"),
+            rows,
+        }))
+    }
+}
+
+impl DiskStats {
     
     fn label_colour(&self, app_state: &AppState) -> Color {
         app_state
@@ -99,6 +180,13 @@ impl DiskStats {
         format_bytes_per_second(self.avg_bytes_written, app_config)
     }
 
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        vec![
+            ("read", bytes_per_second_template_value(self.avg_bytes_read, app_config)),
+            ("write", bytes_per_second_template_value(self.avg_bytes_written, app_config)),
+        ]
+    }
+
     fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
         if app_config.general.use_iec_units {
             "99.9MiB/s"
@@ -107,6 +195,14 @@ impl DiskStats {
         }
     }
 
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        fl!(
+            "tooltip-disk",
+            read = self.read_value(app_config),
+            write = self.write_value(app_config)
+        )
+    }
+
     fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
         app_config.disk.hide_indicator
     }
@@ -0,0 +1,136 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, FILE_SETTINGS_WINDOW_ID};
+use crate::configuration::file::FileValueMode;
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::file_monitor::{FileStats, FILE_VALUE_METRIC};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::indicators::{indicator_threshold, sparkline_series, template_value_text};
+use cosmic::iced::Color;
+use cosmic::Element;
+
+impl MonitorStats for FileStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+        let core = app_state.core();
+        let configuration = app_state.configuration();
+
+        if self.is_hidden(configuration) {
+            return None;
+        }
+
+        let text = if configuration.file.hide_label {
+            None
+        } else {
+            configuration.file.label_text.clone()
+        };
+
+        let display_item_color = self.label_colour(app_state);
+        let font_size = app_state.font_size(horizontal);
+
+        let (value_text, max_text_width) = match &configuration.file.format_template {
+            Some(template) => template_value_text(app_state, template, &self.template_values(configuration), font_size),
+            None if configuration.general.fix_indicator_size => (
+                self.value(configuration),
+                app_state
+                    .app_text_measurements()
+                    .measure(self.max_label_text(configuration).as_str(), font_size),
+            ),
+            None => (self.value(configuration), None),
+        };
+
+        let values = vec![IndicatorValueItem {
+            text: value_text,
+            icon: None,
+        }];
+
+        let display_mode = configuration.file.display_mode;
+        let graph = display_mode.shows_graph().then(|| SparklineProps {
+            series: vec![sparkline_series(app_state, FILE_VALUE_METRIC, display_item_color)],
+            max_value: None,
+            font_size,
+            width: max_text_width,
+            horizontal,
+        });
+
+        indicator(
+            core,
+            IndicatorProps {
+                label_text: text,
+                label_colour: display_item_color,
+                font_size,
+                font: app_state.active_interface_font(),
+                value_width: max_text_width,
+                horizontal,
+                spacing: app_state.core().applet.suggested_padding(true),
+                icon_spacing: 2,
+                values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.file.thresholds),
+                placeholder: app_state.indicator_placeholder(&FILE_SETTINGS_WINDOW_ID),
+            },
+        )
+    }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        FileStats::metrics(self)
+    }
+}
+
+impl FileStats {
+    fn label_colour(&self, app_state: &AppState) -> Color {
+        app_state
+            .configuration()
+            .file
+            .label_colour
+            .as_deref()
+            .and_then(|key| app_state.app_colours().get(key))
+            .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+    }
+
+    fn value(&self, app_config: &AppConfiguration) -> String {
+        format!("{:.1}{}", self.value, self.unit_suffix(app_config))
+    }
+
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        vec![
+            (
+                "value",
+                TemplateValue::Number {
+                    value: self.value,
+                    max: 9999.9,
+                    precision: 1,
+                    unit: "",
+                },
+            ),
+            ("unit", TemplateValue::Text(self.unit_suffix(app_config))),
+        ]
+    }
+
+    fn max_label_text(&self, app_config: &AppConfiguration) -> String {
+        format!("9999.9{}", self.unit_suffix(app_config))
+    }
+
+    fn unit_suffix(&self, app_config: &AppConfiguration) -> String {
+        match app_config.file.mode {
+            FileValueMode::Absolute => app_config.file.unit.clone(),
+            FileValueMode::Rate => format!("{}/s", app_config.file.unit),
+        }
+    }
+
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        fl!(
+            "tooltip-file",
+            path = app_config.file.file_path.clone(),
+            value = format!("{} {}", self.value, self.unit_suffix(app_config))
+        )
+    }
+
+    fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
+        app_config.file.hide_indicator || app_config.file.file_path.is_empty()
+    }
+}
@@ -1,12 +1,21 @@
+use crate::alerts::alert_evaluator::AlertValue;
 use crate::app::{AppState, Message};
-use crate::configuration::app_configuration::AppConfiguration;
-use crate::monitors::memory_monitor::MemoryStats;
+use crate::configuration::app_configuration::{AppConfiguration, MEMORY_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::configuration::memory::MemoryValueMode;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::memory_monitor::{MemoryStats, MEMORY_USED_METRIC};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::detail_list::{detail_list, DetailListProps, DetailListRow};
 use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::indicators::{indicator_threshold, sparkline_series, template_value_text};
 use cosmic::iced::Color;
 use cosmic::Element;
 
-impl MemoryStats {
-    pub fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+impl MonitorStats for MemoryStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
         let core = app_state.core();
         let configuration = app_state.configuration();
 
@@ -19,19 +28,64 @@ impl MemoryStats {
         let display_item_color = self.label_colour(app_state);
         let font_size = app_state.font_size(horizontal);
 
-        let max_text_width = if configuration.general.fix_indicator_size {
-            app_state
-                .app_text_measurements()
-                .measure(self.max_label_text(configuration), font_size)
-        } else {
-            None
+        let (value_text, max_text_width) = match &configuration.memory.format_template {
+            Some(template) => {
+                let (text, width) =
+                    template_value_text(app_state, template, &self.template_values(configuration), font_size);
+                // The compression values share the width of the template, so size for the wider
+                let compression_width = self
+                    .compression_label_text(configuration)
+                    .and_then(|text| app_state.app_text_measurements().measure(text, font_size));
+
+                (text, width.map(|width| compression_width.map_or(width, |other| other.max(width))))
+            }
+            None if configuration.general.fix_indicator_size => (
+                self.value(configuration),
+                app_state
+                    .app_text_measurements()
+                    .measure(self.max_label_text(configuration), font_size),
+            ),
+            None => (self.value(configuration), None),
         };
 
-        let values = vec![IndicatorValueItem {
-            text: self.value(app_state.configuration()),
+        let mut values = vec![IndicatorValueItem {
+            text: value_text,
             icon: None,
         }];
 
+        if configuration.memory.show_compression {
+            if let Some(zram) = &self.zram {
+                values.push(IndicatorValueItem {
+                    text: fl!(
+                        "indicator-memory-zram",
+                        size = format_kib(zram.original_kib, configuration),
+                        ratio = format!("{:.1}", zram.ratio())
+                    ),
+                    icon: None,
+                });
+            }
+
+            if let Some(zswap) = &self.zswap {
+                values.push(IndicatorValueItem {
+                    text: fl!(
+                        "indicator-memory-zswap",
+                        size = format_kib(zswap.original_kib, configuration),
+                        ratio = format!("{:.1}", zswap.ratio())
+                    ),
+                    icon: None,
+                });
+            }
+        }
+
+        let display_mode = configuration.memory.display_mode;
+        let graph = display_mode.shows_graph().then(|| SparklineProps {
+            series: vec![sparkline_series(app_state, MEMORY_USED_METRIC, display_item_color)],
+            max_value: Some(self.total_kib as f64),
+            font_size,
+            width: max_text_width,
+            horizontal,
+        });
+
         indicator(
             core,
             IndicatorProps {
@@ -44,10 +98,52 @@ impl MemoryStats {
                 spacing: app_state.core().applet.suggested_padding(true),
                 icon_spacing: 2,
                 values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.memory.thresholds),
+                placeholder: app_state.indicator_placeholder(&MEMORY_SETTINGS_WINDOW_ID),
             },
         )
     }
 
+    fn metrics(&self) -> Vec<MetricValue> {
+        MemoryStats::metrics(self)
+    }
+
+    fn alert_values(&self) -> Vec<AlertValue> {
+        MemoryStats::alert_values(self)
+    }
+
+    fn draw_detail<'app>(&self, app_state: &'app AppState) -> Option<Element<'app, Message>> {
+        let configuration = app_state.configuration();
+        let breakdown = &self.breakdown;
+        let item = |name: String, kib: u64| DetailListRow::Item(name, format_kib(kib, configuration));
+
+        let mut rows = vec![
+            item(fl!("detail-memory-total"), self.total_kib),
+            item(fl!("detail-memory-used"), self.used_kib),
+            item(fl!("detail-memory-available"), breakdown.available_kib),
+            item(fl!("detail-memory-free"), breakdown.free_kib),
+            item(fl!("detail-memory-buffers"), breakdown.buffers_kib),
+            item(fl!("detail-memory-cache"), breakdown.cache_kib),
+            item(fl!("detail-memory-shared"), breakdown.shmem_kib),
+        ];
+
+        if self.swap_total_kib > 0 {
+            rows.push(DetailListRow::Heading(fl!("detail-memory-swap")));
+            rows.push(item(fl!("detail-memory-total"), self.swap_total_kib));
+            rows.push(item(fl!("detail-memory-used"), self.swap_used_kib));
+        }
+
+        Some(detail_list(DetailListProps {
+            title: fl!("detail-memory-title"),
+            rows,
+        }))
+    }
+}
+
+impl MemoryStats {
     fn label_colour(&self, app_state: &AppState) -> Color {
         app_state
             .configuration()
@@ -59,32 +155,134 @@ impl MemoryStats {
     }
 
     fn value(&self, app_config: &AppConfiguration) -> String {
-        if app_config.general.use_iec_units {
-            let used_gib = self.used_kib as f64 / 1024.0 / 1024.0;
-            if used_gib > 99.9 {
-                format!("{:.0}GiB", used_gib.round())
-            } else {
-                format!("{:.1}GiB", used_gib)
-            }
+        match app_config.memory.value_mode {
+            MemoryValueMode::Used => format_kib(self.used_kib, app_config),
+            MemoryValueMode::PercentUsed if self.used_percent() >= 100.0 => "100%".to_string(),
+            MemoryValueMode::PercentUsed => format!("{:.1}%", self.used_percent()),
+            MemoryValueMode::UsedOfTotal => format!(
+                "{}/{}",
+                format_kib(self.used_kib, app_config),
+                format_kib(self.total_kib, app_config)
+            ),
+            MemoryValueMode::Available => format_kib(self.available_kib(), app_config),
+        }
+    }
+
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        let (kib_per_unit, unit) = if app_config.general.use_iec_units {
+            (1024.0 * 1024.0, "GiB")
         } else {
-            let used_gb = self.used_kib as f64 * 1024.0 / 1_000_000_000.0;
-            if used_gb > 99.9 {
-                format!("{:.0}GB", used_gb.round())
-            } else {
-                format!("{:.1}GB", used_gb)
-            }
+            (1_000_000_000.0 / 1024.0, "GB")
+        };
+        let total = self.total_kib as f64 / kib_per_unit;
+        let size = |kib: u64| TemplateValue::Number {
+            value: kib as f64 / kib_per_unit,
+            max: total,
+            precision: 1,
+            unit: "",
+        };
+
+        vec![
+            ("used", size(self.used_kib)),
+            ("total", size(self.total_kib)),
+            ("available", size(self.available_kib())),
+            (
+                "percent",
+                TemplateValue::Number {
+                    value: self.used_percent(),
+                    max: 100.0,
+                    precision: 1,
+                    unit: "",
+                },
+            ),
+            ("unit", TemplateValue::Text(unit.to_string())),
+        ]
+    }
+
+    /// The widest compression value, if the compression values are shown
+    fn compression_label_text(&self, app_config: &AppConfiguration) -> Option<&'static str> {
+        let shows_compression =
+            app_config.memory.show_compression && (self.zram.is_some() || self.zswap.is_some());
+
+        match (shows_compression, app_config.general.use_iec_units) {
+            (false, _) => None,
+            (true, true) => Some("zswap 99.9GiB 9.9x"),
+            (true, false) => Some("zswap 99.9GB 9.9x"),
         }
     }
 
     fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
-        if app_config.general.use_iec_units {
-            "99.9MiB"
+        // The compression values share the width of the memory value, so size for the longest
+        if let Some(compression_label_text) = self.compression_label_text(app_config) {
+            return compression_label_text;
+        }
+
+        match (app_config.memory.value_mode, app_config.general.use_iec_units) {
+            (MemoryValueMode::PercentUsed, _) => "99.9%",
+            (MemoryValueMode::UsedOfTotal, true) => "99.9GiB/99.9GiB",
+            (MemoryValueMode::UsedOfTotal, false) => "99.9GB/99.9GB",
+            (MemoryValueMode::Used | MemoryValueMode::Available, true) => "99.9MiB",
+            (MemoryValueMode::Used | MemoryValueMode::Available, false) => "99.9MB",
+        }
+    }
+
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        let percent = self.used_percent();
+        let used = format_kib(self.used_kib, app_config);
+        let total = format_kib(self.total_kib, app_config);
+
+        let mut lines = vec![if self.swap_total_kib == 0 {
+            fl!("tooltip-memory-no-swap", used = used, total = total, percent = format!("{:.0}", percent))
         } else {
-            "99.9MB"
+            fl!(
+                "tooltip-memory",
+                used = used,
+                total = total,
+                percent = format!("{:.0}", percent),
+                swap = format_kib(self.swap_used_kib, app_config)
+            )
+        }];
+
+        if let Some(zram) = &self.zram {
+            lines.push(fl!(
+                "tooltip-memory-zram",
+                original = format_kib(zram.original_kib, app_config),
+                compressed = format_kib(zram.compressed_kib, app_config),
+                ratio = format!("{:.1}", zram.ratio())
+            ));
         }
+
+        if let Some(zswap) = &self.zswap {
+            lines.push(fl!(
+                "tooltip-memory-zswap",
+                original = format_kib(zswap.original_kib, app_config),
+                compressed = format_kib(zswap.compressed_kib, app_config),
+                ratio = format!("{:.1}", zswap.ratio())
+            ));
+        }
+
+        lines.join("\n")
     }
 
     fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
         app_config.memory.hide_indicator
     }
 }
+
+pub fn format_kib(kib: u64, app_config: &AppConfiguration) -> String {
+    if app_config.general.use_iec_units {
+        let gib = kib as f64 / 1024.0 / 1024.0;
+        if gib > 99.9 {
+            format!("{:.0}GiB", gib.round())
+        } else {
+            format!("{:.1}GiB", gib)
+        }
+    } else {
+        let gb = kib as f64 * 1024.0 / 1_000_000_000.0;
+        if gb > 99.9 {
+            format!("{:.0}GB", gb.round())
+        } else {
+            format!("{:.1}GB", gb)
+        }
+    }
+}
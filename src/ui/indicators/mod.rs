@@ -1,30 +1,111 @@
+use crate::app::AppState;
 use crate::configuration::app_configuration::AppConfiguration;
+use crate::configuration::format_template::{FormatTemplate, TemplateValue};
+use crate::configuration::thresholds::ThresholdConfiguration;
+use crate::core::app_colours::{ACCENT_RED, ACCENT_YELLOW};
+use crate::monitors::thresholds::ThresholdLevel;
+use crate::ui::components::indicator::IndicatorThreshold;
+use crate::ui::components::sparkline::SparklineSeries;
+use cosmic::iced::Color;
+use std::time::{Duration, Instant};
 
 pub mod disk;
 pub mod network;
 pub mod cpu;
 pub mod memory;
+pub mod file;
+pub mod power;
+pub mod paging;
+pub mod arc;
+pub mod raid;
 
-fn format_bytes_per_second(bytes_per_sec: u64, app_config: &AppConfiguration) -> String {
-    if app_config.general.use_iec_units {
-        let mib_per_second = bytes_per_sec as f64 / 1024.0 / 1024.0;
-        if mib_per_second > 999.9 {
-            let gib_per_second = bytes_per_sec as f64 / 1024.0 / 1024.0 / 1024.0;
-            format!("{:.1}GiB/s", gib_per_second)
-        } else if mib_per_second > 99.9 {
-            format!("{:.0}MiB/s", mib_per_second.round())
-        } else {
-            format!("{:.1}MiB/s", mib_per_second)
-        }
+/// How far back the graphs on the panel go
+const SPARKLINE_SPAN: Duration = Duration::from_secs(60);
+
+fn sparkline_series(app_state: &AppState, metric: &str, colour: Color) -> SparklineSeries {
+    SparklineSeries {
+        values: app_state
+            .metric_history()
+            .query(metric, SPARKLINE_SPAN, Instant::now())
+            .iter()
+            .map(|sample| sample.average)
+            .collect(),
+        colour,
+    }
+}
+
+/// The palette colour of the threshold a monitor's value has crossed, if it has crossed one
+fn indicator_threshold(
+    app_state: &AppState,
+    level: ThresholdLevel,
+    thresholds: &ThresholdConfiguration,
+) -> Option<IndicatorThreshold> {
+    let key = match level {
+        ThresholdLevel::Normal => return None,
+        ThresholdLevel::Warning => ACCENT_YELLOW,
+        ThresholdLevel::Critical => ACCENT_RED,
+    };
+
+    app_state.app_colours().get(key).map(|c| IndicatorThreshold {
+        colour: Color::new(c.red, c.green, c.blue, c.alpha),
+        colour_label: thresholds.colour_label,
+    })
+}
+
+/// A byte rate in megabytes per second, or gigabytes per second once it doesn't fit in three digits,
+/// with its unit
+fn scale_bytes_per_second(bytes_per_sec: u64, app_config: &AppConfiguration) -> (f64, &'static str) {
+    let (mega, giga, mega_unit, giga_unit) = if app_config.general.use_iec_units {
+        ((1u64 << 20) as f64, (1u64 << 30) as f64, "MiB/s", "GiB/s")
     } else {
-        let mb_per_second = bytes_per_sec as f64 / 1_000_000.0;
-        if mb_per_second > 999.9 {
-            let gb_per_second = bytes_per_sec as f64 / 1_000_000_000.0;
-            format!("{:.1}GB/s", gb_per_second)
-        } else if mb_per_second > 99.9 {
-            format!("{:.0}MB/s", mb_per_second.round())
-        } else {
-            format!("{:.1}MB/s", mb_per_second)
-        }
+        (1_000_000.0, 1_000_000_000.0, "MB/s", "GB/s")
+    };
+
+    let mega_per_second = bytes_per_sec as f64 / mega;
+    if mega_per_second > 999.9 {
+        (bytes_per_sec as f64 / giga, giga_unit)
+    } else {
+        (mega_per_second, mega_unit)
     }
+}
+
+pub fn format_bytes_per_second(bytes_per_sec: u64, app_config: &AppConfiguration) -> String {
+    let (value, unit) = scale_bytes_per_second(bytes_per_sec, app_config);
+
+    if unit.starts_with('M') && value > 99.9 {
+        format!("{:.0}{}", value.round(), unit)
+    } else {
+        format!("{:.1}{}", value, unit)
+    }
+}
+
+/// A byte rate for a format template, carrying its unit as each rate is scaled on its own
+fn bytes_per_second_template_value(bytes_per_sec: u64, app_config: &AppConfiguration) -> TemplateValue {
+    let (value, unit) = scale_bytes_per_second(bytes_per_sec, app_config);
+
+    TemplateValue::Number {
+        value,
+        max: 999.9,
+        precision: 1,
+        unit,
+    }
+}
+
+/// The value of an indicator rendered from its format template, and the width of the template's
+/// widest rendering when the indicator size is fixed
+fn template_value_text(
+    app_state: &AppState,
+    template: &FormatTemplate,
+    values: &[(&str, TemplateValue)],
+    font_size: u16,
+) -> (String, Option<f32>) {
+    let width = if app_state.configuration().general.fix_indicator_size {
+        app_state
+            .app_text_measurements()
+            .measure(&template.render_widest(values), font_size)
+    } else {
+        None
+    };
+
+    (template.render(values), width)
 }
\ No newline at end of file
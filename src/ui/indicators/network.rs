@@ -1,16 +1,25 @@
+use crate::alerts::alert_evaluator::AlertValue;
 use crate::app::{AppState, Message};
-use crate::configuration::app_configuration::AppConfiguration;
-use crate::monitors::network_monitor::NetworkStats;
+use crate::configuration::app_configuration::{AppConfiguration, NETWORK_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::network_monitor::{NetworkStats, NETWORK_RX_METRIC, NETWORK_TX_METRIC};
 use crate::core::app_colours::{ACCENT_GREEN, ACCENT_RED};
 use crate::core::app_icons::{DOWN_ARROW_ICON, UP_ARROW_ICON};
+use crate::monitors::monitor::MonitorStats;
+use crate::ui::components::detail_list::{detail_list, DetailListProps, DetailListRow};
 use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
 use crate::ui::components::svg_icon::SvgIconProps;
 use cosmic::iced::Color;
 use cosmic::Element;
-use crate::ui::indicators::format_bytes_per_second;
+use crate::ui::indicators::{
+    bytes_per_second_template_value, format_bytes_per_second, indicator_threshold, sparkline_series, template_value_text,
+};
 
-impl NetworkStats {
-    pub fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+impl MonitorStats for NetworkStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
         let core = app_state.core();
         let configuration = app_state.configuration();
 
@@ -24,45 +33,77 @@ impl NetworkStats {
         let font_size = app_state.font_size(horizontal);
         let icon_size = app_state.icon_size();
 
-        let max_text_width = if configuration.general.fix_indicator_size {
-            app_state
+        let template_value = configuration
+            .network
+            .format_template
+            .as_ref()
+            .map(|template| template_value_text(app_state, template, &self.template_values(configuration), font_size));
+
+        let max_text_width = match &template_value {
+            Some((_, width)) => *width,
+            None if configuration.general.fix_indicator_size => app_state
                 .app_text_measurements()
-                .measure(self.max_label_text(configuration), font_size)
-        } else {
-            None
+                .measure(self.max_label_text(configuration), font_size),
+            None => None,
         };
 
         let mut values: Vec<IndicatorValueItem> = Vec::new();
-        values.push(IndicatorValueItem {
-            text: self.read_value(app_state.configuration()),
-            icon: if horizontal {
-                Some(SvgIconProps {
-                    icon: app_state.app_icons().get(DOWN_ARROW_ICON),
-                    size: icon_size,
-                    colour: app_state
-                        .app_colours()
-                        .get(ACCENT_GREEN)
-                        .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
-                })
-            } else {
-                None
-            },
-        });
+        if let Some((text, _)) = template_value {
+            // The template shows both rates as one value, so without their icons
+            values.push(IndicatorValueItem { text, icon: None });
+        } else {
+            values.push(IndicatorValueItem {
+                text: self.read_value(app_state.configuration()),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(DOWN_ARROW_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_GREEN)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
 
-        values.push(IndicatorValueItem {
-            text: self.write_value(app_state.configuration()),
-            icon: if horizontal {
-                Some(SvgIconProps {
-                    icon: app_state.app_icons().get(UP_ARROW_ICON),
-                    size: icon_size,
-                    colour: app_state
-                        .app_colours()
-                        .get(ACCENT_RED)
-                        .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
-                })
-            } else {
-                None
-            },
+            values.push(IndicatorValueItem {
+                text: self.write_value(app_state.configuration()),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(UP_ARROW_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_RED)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
+        }
+
+        let display_mode = configuration.network.display_mode;
+        let graph = display_mode.shows_graph().then(|| {
+            let accent = |key: &str| {
+                app_state
+                    .app_colours()
+                    .get(key)
+                    .map_or(display_item_color, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+            };
+
+            SparklineProps {
+                series: vec![
+                    sparkline_series(app_state, NETWORK_RX_METRIC, accent(ACCENT_GREEN)),
+                    sparkline_series(app_state, NETWORK_TX_METRIC, accent(ACCENT_RED)),
+                ],
+                max_value: None,
+                font_size,
+                width: max_text_width,
+                horizontal,
+            }
         });
 
         indicator(
@@ -77,9 +118,49 @@ impl NetworkStats {
                 spacing: app_state.core().applet.suggested_padding(true),
                 icon_spacing: 2,
                 values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.network.thresholds),
+                placeholder: app_state.indicator_placeholder(&NETWORK_SETTINGS_WINDOW_ID),
             },
         )
     }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        NetworkStats::metrics(self)
+    }
+
+    fn alert_values(&self) -> Vec<AlertValue> {
+        NetworkStats::alert_values(self)
+    }
+
+    fn draw_detail<'app>(&self, app_state: &'app AppState) -> Option<Element<'app, Message>> {
+        let configuration = app_state.configuration();
+
+        let rows = self
+            .interfaces
+            .iter()
+            .map(|device| {
+                DetailListRow::Item(
+                    device.name.clone(),
+                    fl!(
+                        "detail-network-rates",
+                        rx = format_bytes_per_second(device.rx_bytes, configuration),
+                        tx = format_bytes_per_second(device.tx_bytes, configuration)
+                    ),
+                )
+            })
+            .collect();
+
+        Some(detail_list(DetailListProps {
+            title: fl!("detail-network-title"),
+            rows,
+        }))
+    }
+}
+
+impl NetworkStats {
     
     fn label_colour(&self, app_state: &AppState) -> Color {
         app_state
@@ -99,6 +180,13 @@ impl NetworkStats {
         format_bytes_per_second(self.tx_bytes, app_config)
     }
 
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        vec![
+            ("rx", bytes_per_second_template_value(self.rx_bytes, app_config)),
+            ("tx", bytes_per_second_template_value(self.tx_bytes, app_config)),
+        ]
+    }
+
     fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
         if app_config.general.use_iec_units {
             "99.9MiB/s"
@@ -107,6 +195,25 @@ impl NetworkStats {
         }
     }
 
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        let mut lines = vec![fl!(
+            "tooltip-network",
+            rx = self.read_value(app_config),
+            tx = self.write_value(app_config)
+        )];
+
+        for interface in &self.interfaces {
+            lines.push(fl!(
+                "tooltip-network-interface",
+                name = interface.name.clone(),
+                rx = format_bytes_per_second(interface.rx_bytes, app_config),
+                tx = format_bytes_per_second(interface.tx_bytes, app_config)
+            ));
+        }
+
+        lines.join("\n")
+    }
+
     fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
         app_config.network.hide_indicator
     }
@@ -0,0 +1,230 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, PAGING_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::app_colours::{ACCENT_GREEN, ACCENT_RED, BRIGHT_RED};
+use crate::core::app_icons::{READ_ICON, WRITE_ICON};
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::monitor::MonitorStats;
+use crate::monitors::paging_monitor::{PagingStats, PAGING_SWAP_IN_METRIC, PAGING_SWAP_OUT_METRIC};
+use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::components::svg_icon::SvgIconProps;
+use crate::ui::indicators::{
+    bytes_per_second_template_value, format_bytes_per_second, indicator_threshold, sparkline_series,
+    template_value_text,
+};
+use cosmic::iced::Color;
+use cosmic::Element;
+
+impl MonitorStats for PagingStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+        let core = app_state.core();
+        let configuration = app_state.configuration();
+
+        if self.is_hidden(configuration) {
+            return None;
+        }
+
+        let text = if configuration.paging.hide_label {
+            None
+        } else {
+            configuration.paging.label_text.clone()
+        };
+
+        let display_item_color = self.label_colour(app_state);
+        let font_size = app_state.font_size(horizontal);
+        let icon_size = app_state.icon_size();
+
+        let template_value =
+            configuration.paging.format_template.as_ref().map(|template| {
+                template_value_text(app_state, template, &self.template_values(configuration), font_size)
+            });
+
+        let max_text_width = match &template_value {
+            Some((_, width)) => *width,
+            None if configuration.general.fix_indicator_size => app_state
+                .app_text_measurements()
+                .measure(self.max_label_text(configuration), font_size),
+            None => None,
+        };
+
+        let mut values: Vec<IndicatorValueItem> = Vec::new();
+        if let Some((text, _)) = template_value {
+            // The template shows the rates and counts as one value, so without their icons
+            values.push(IndicatorValueItem { text, icon: None });
+        } else {
+            values.push(IndicatorValueItem {
+                text: format_bytes_per_second(self.swap_in_bytes_per_second, configuration),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(READ_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_GREEN)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
+
+            values.push(IndicatorValueItem {
+                text: format_bytes_per_second(self.swap_out_bytes_per_second, configuration),
+                icon: if horizontal {
+                    Some(SvgIconProps {
+                        icon: app_state.app_icons().get(WRITE_ICON),
+                        size: icon_size,
+                        colour: app_state
+                            .app_colours()
+                            .get(ACCENT_RED)
+                            .map(|c| Color::new(c.red, c.green, c.blue, c.alpha)),
+                    })
+                } else {
+                    None
+                },
+            });
+
+            if configuration.paging.show_major_faults {
+                values.push(IndicatorValueItem {
+                    text: fl!(
+                        "indicator-paging-major-faults",
+                        count = format!("{:.0}", self.major_faults_per_second)
+                    ),
+                    icon: None,
+                });
+            }
+
+            if self.oom_killer_fired() {
+                values.push(IndicatorValueItem {
+                    text: fl!("indicator-paging-oom-kills", count = self.oom_kills_since_start),
+                    icon: None,
+                });
+            }
+        }
+
+        let display_mode = configuration.paging.display_mode;
+        let graph = display_mode.shows_graph().then(|| {
+            let accent = |key: &str| {
+                app_state
+                    .app_colours()
+                    .get(key)
+                    .map_or(display_item_color, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+            };
+
+            SparklineProps {
+                series: vec![
+                    sparkline_series(app_state, PAGING_SWAP_IN_METRIC, accent(ACCENT_GREEN)),
+                    sparkline_series(app_state, PAGING_SWAP_OUT_METRIC, accent(ACCENT_RED)),
+                ],
+                max_value: None,
+                font_size,
+                width: max_text_width,
+                horizontal,
+            }
+        });
+
+        indicator(
+            core,
+            IndicatorProps {
+                label_text: text,
+                label_colour: display_item_color,
+                font_size,
+                font: app_state.active_interface_font(),
+                value_width: max_text_width,
+                horizontal,
+                spacing: app_state.core().applet.suggested_padding(true),
+                icon_spacing: 2,
+                values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.paging.thresholds),
+                placeholder: app_state.indicator_placeholder(&PAGING_SETTINGS_WINDOW_ID),
+            },
+        )
+    }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        PagingStats::metrics(self)
+    }
+}
+
+impl PagingStats {
+    /// The label is highlighted once the OOM killer has fired since the applet started.
+    fn label_colour(&self, app_state: &AppState) -> Color {
+        let colour_key = if self.oom_killer_fired() {
+            Some(BRIGHT_RED)
+        } else {
+            app_state.configuration().paging.label_colour.as_deref()
+        };
+
+        colour_key
+            .and_then(|key| app_state.app_colours().get(key))
+            .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+    }
+
+    fn oom_killer_fired(&self) -> bool {
+        self.oom_kills_since_start > 0
+    }
+
+    fn template_values(&self, app_config: &AppConfiguration) -> Vec<(&'static str, TemplateValue)> {
+        vec![
+            (
+                "in",
+                bytes_per_second_template_value(self.swap_in_bytes_per_second, app_config),
+            ),
+            (
+                "out",
+                bytes_per_second_template_value(self.swap_out_bytes_per_second, app_config),
+            ),
+            (
+                "faults",
+                TemplateValue::Number {
+                    value: self.major_faults_per_second,
+                    max: 9999.0,
+                    precision: 0,
+                    unit: "",
+                },
+            ),
+            (
+                "oom",
+                TemplateValue::Number {
+                    value: self.oom_kills_since_start as f64,
+                    max: 99.0,
+                    precision: 0,
+                    unit: "",
+                },
+            ),
+        ]
+    }
+
+    fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
+        if app_config.general.use_iec_units {
+            "99.9MiB/s"
+        } else {
+            "99.9MB/s"
+        }
+    }
+
+    fn tooltip(&self, app_config: &AppConfiguration) -> String {
+        [
+            fl!(
+                "tooltip-paging",
+                swap_in = format_bytes_per_second(self.swap_in_bytes_per_second, app_config),
+                swap_out = format_bytes_per_second(self.swap_out_bytes_per_second, app_config)
+            ),
+            fl!(
+                "tooltip-paging-major-faults",
+                count = format!("{:.1}", self.major_faults_per_second)
+            ),
+            fl!("tooltip-paging-oom-kills", count = self.oom_kills_since_start),
+        ]
+        .join("\n")
+    }
+
+    fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
+        app_config.paging.hide_indicator
+    }
+}
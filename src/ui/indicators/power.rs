@@ -0,0 +1,184 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, POWER_SETTINGS_WINDOW_ID};
+use crate::configuration::format_template::TemplateValue;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::monitor::MonitorStats;
+use crate::monitors::power_monitor::{PowerStats, POWER_PACKAGE_METRIC};
+use crate::sensors::sys_powercap_reader::PowercapAccess;
+use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use crate::ui::components::sparkline::SparklineProps;
+use crate::ui::indicators::{indicator_threshold, sparkline_series, template_value_text};
+use cosmic::iced::Color;
+use cosmic::Element;
+
+impl MonitorStats for PowerStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+        let core = app_state.core();
+        let configuration = app_state.configuration();
+
+        if self.is_hidden(configuration) {
+            return None;
+        }
+
+        let text = if configuration.power.hide_label {
+            None
+        } else {
+            configuration.power.label_text.clone()
+        };
+
+        let display_item_color = self.label_colour(app_state);
+        let font_size = app_state.font_size(horizontal);
+
+        let template_value = configuration
+            .power
+            .format_template
+            .as_ref()
+            .filter(|_| self.access == PowercapAccess::Readable)
+            .map(|template| template_value_text(app_state, template, &self.template_values(), font_size));
+
+        let max_text_width = match &template_value {
+            Some((_, width)) => *width,
+            // The unavailable message isn't a number, so let it size itself.
+            None if configuration.general.fix_indicator_size && self.access == PowercapAccess::Readable => app_state
+                .app_text_measurements()
+                .measure(self.max_label_text(configuration), font_size),
+            None => None,
+        };
+
+        let values = match template_value {
+            // The template shows all the domains it uses as one value
+            Some((text, _)) => vec![IndicatorValueItem { text, icon: None }],
+            None => self.values(configuration),
+        };
+
+        let display_mode = configuration.power.display_mode;
+        let graph = display_mode.shows_graph().then(|| SparklineProps {
+            series: vec![sparkline_series(app_state, POWER_PACKAGE_METRIC, display_item_color)],
+            max_value: None,
+            font_size,
+            width: max_text_width,
+            horizontal,
+        });
+
+        indicator(
+            core,
+            IndicatorProps {
+                label_text: text,
+                label_colour: display_item_color,
+                font_size,
+                font: app_state.active_interface_font(),
+                value_width: max_text_width,
+                horizontal,
+                spacing: app_state.core().applet.suggested_padding(true),
+                icon_spacing: 2,
+                values,
+                display_mode,
+                graph,
+                tooltip: Some(self.tooltip(configuration)),
+                threshold: indicator_threshold(app_state, self.threshold_level, &configuration.power.thresholds),
+                placeholder: app_state.indicator_placeholder(&POWER_SETTINGS_WINDOW_ID),
+            },
+        )
+    }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        PowerStats::metrics(self)
+    }
+}
+
+impl PowerStats {
+    fn label_colour(&self, app_state: &AppState) -> Color {
+        app_state
+            .configuration()
+            .power
+            .label_colour
+            .as_deref()
+            .and_then(|key| app_state.app_colours().get(key))
+            .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+    }
+
+    fn values<'a>(&self, app_config: &AppConfiguration) -> Vec<IndicatorValueItem<'a>> {
+        if self.access == PowercapAccess::Unsupported {
+            return vec![IndicatorValueItem {
+                text: fl!("indicator-power-unsupported"),
+                icon: None,
+            }];
+        }
+
+        let mut values = vec![IndicatorValueItem {
+            text: format_watts(self.package_watts.unwrap_or_default()),
+            icon: None,
+        }];
+
+        if app_config.power.show_all_domains {
+            let domains = [
+                (fl!("indicator-power-core"), self.core_watts),
+                (fl!("indicator-power-uncore"), self.uncore_watts),
+                (fl!("indicator-power-dram"), self.dram_watts),
+            ];
+
+            for (prefix, watts) in domains {
+                if let Some(watts) = watts {
+                    values.push(IndicatorValueItem {
+                        text: format!("{} {}", prefix, format_watts(watts)),
+                        icon: None,
+                    });
+                }
+            }
+        }
+
+        values
+    }
+
+    fn template_values(&self) -> Vec<(&'static str, TemplateValue)> {
+        let watts = |watts: Option<f64>| TemplateValue::Number {
+            value: watts.unwrap_or_default(),
+            max: 999.9,
+            precision: 1,
+            unit: "",
+        };
+
+        vec![
+            ("package", watts(self.package_watts)),
+            ("core", watts(self.core_watts)),
+            ("uncore", watts(self.uncore_watts)),
+            ("dram", watts(self.dram_watts)),
+        ]
+    }
+
+    fn max_label_text(&self, app_config: &AppConfiguration) -> &'static str {
+        if app_config.power.show_all_domains {
+            "M 999.9W"
+        } else {
+            "999.9W"
+        }
+    }
+
+    fn tooltip(&self, _app_config: &AppConfiguration) -> String {
+        if self.access == PowercapAccess::Unsupported {
+            return fl!("tooltip-power-unsupported");
+        }
+
+        let domains = [
+            (fl!("tooltip-power-package"), self.package_watts),
+            (fl!("tooltip-power-core"), self.core_watts),
+            (fl!("tooltip-power-uncore"), self.uncore_watts),
+            (fl!("tooltip-power-dram"), self.dram_watts),
+        ];
+
+        domains
+            .into_iter()
+            .filter_map(|(domain, watts)| watts.map(|watts| format!("{}: {}", domain, format_watts(watts))))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
+        app_config.power.hide_indicator
+    }
+}
+
+fn format_watts(watts: f64) -> String {
+    format!("{:.1}W", watts)
+}
@@ -0,0 +1,121 @@
+use crate::app::{AppState, Message};
+use crate::configuration::app_configuration::{AppConfiguration, RAID_SETTINGS_WINDOW_ID};
+use crate::configuration::indicator_display_mode::IndicatorDisplayMode;
+use crate::core::app_colours::BRIGHT_RED;
+use crate::core::metric_history::MetricValue;
+use crate::fl;
+use crate::monitors::monitor::MonitorStats;
+use crate::monitors::raid_monitor::{RaidArrayStats, RaidHealth, RaidStats};
+use crate::ui::components::indicator::{indicator, IndicatorProps, IndicatorValueItem};
+use cosmic::iced::Color;
+use cosmic::Element;
+
+impl MonitorStats for RaidStats {
+    fn draw<'app>(&self, app_state: &'app AppState, horizontal: bool) -> Option<Element<'app, Message>> {
+        let core = app_state.core();
+        let configuration = app_state.configuration();
+
+        let placeholder = app_state.indicator_placeholder(&RAID_SETTINGS_WINDOW_ID);
+
+        // A healthy set of arrays is hidden, but a failing read of mdstat is not.
+        if self.is_hidden(configuration) && placeholder.is_none() {
+            return None;
+        }
+
+        let text = if configuration.raid.hide_label {
+            None
+        } else {
+            configuration.raid.label_text.clone()
+        };
+
+        let display_item_color = self.label_colour(app_state);
+        let font_size = app_state.font_size(horizontal);
+
+        // The member map grows with the array size, so let the values size themselves.
+        let values = self
+            .unhealthy_arrays()
+            .map(|array| IndicatorValueItem {
+                text: array_value(array),
+                icon: None,
+            })
+            .collect();
+
+        indicator(
+            core,
+            IndicatorProps {
+                label_text: text,
+                label_colour: display_item_color,
+                font_size,
+                font: app_state.active_interface_font(),
+                value_width: None,
+                horizontal,
+                spacing: app_state.core().applet.suggested_padding(true),
+                icon_spacing: 2,
+                values,
+                display_mode: IndicatorDisplayMode::Number,
+                graph: None,
+                tooltip: Some(self.tooltip()),
+                threshold: None,
+                placeholder,
+            },
+        )
+    }
+
+    fn metrics(&self) -> Vec<MetricValue> {
+        RaidStats::metrics(self)
+    }
+}
+
+impl RaidStats {
+    /// Degraded and inactive arrays always show in red; rebuilding uses the configured colour.
+    fn label_colour(&self, app_state: &AppState) -> Color {
+        let colour_key = match self.health() {
+            RaidHealth::Degraded | RaidHealth::Inactive => Some(BRIGHT_RED),
+            _ => app_state.configuration().raid.label_colour.as_deref(),
+        };
+
+        colour_key
+            .and_then(|key| app_state.app_colours().get(key))
+            .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha))
+    }
+
+    /// Every array, including the healthy ones the panel leaves out
+    fn tooltip(&self) -> String {
+        self.arrays.iter().map(array_tooltip).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Only shown while an array needs attention
+    fn is_hidden(&self, app_config: &AppConfiguration) -> bool {
+        app_config.raid.hide_indicator || self.health() == RaidHealth::Healthy
+    }
+}
+
+fn array_tooltip(array: &RaidArrayStats) -> String {
+    let health = match (array.health, array.rebuild_progress) {
+        (RaidHealth::Healthy, _) => fl!("tooltip-raid-healthy"),
+        (RaidHealth::Rebuilding, Some(progress)) => {
+            fl!(
+                "tooltip-raid-rebuilding-progress",
+                progress = format!("{:.1}", progress)
+            )
+        }
+        (RaidHealth::Rebuilding, None) => fl!("tooltip-raid-rebuilding"),
+        (RaidHealth::Degraded, _) => fl!("tooltip-raid-degraded"),
+        (RaidHealth::Inactive, _) => fl!("tooltip-raid-inactive"),
+    };
+
+    fl!(
+        "tooltip-raid-array",
+        name = array.name.clone(),
+        health = health,
+        members = array.member_health.clone()
+    )
+}
+
+fn array_value(array: &RaidArrayStats) -> String {
+    match (array.health, array.rebuild_progress) {
+        (RaidHealth::Inactive, _) => fl!("indicator-raid-inactive", name = array.name.clone()),
+        (RaidHealth::Rebuilding, Some(progress)) => format!("{} {:.1}%", array.name, progress),
+        _ => format!("{} [{}]", array.name, array.member_health),
+    }
+}
@@ -1,3 +1,4 @@
 pub mod components;
 pub mod indicators;
-pub mod settings_forms;
\ No newline at end of file
+pub mod settings_forms;
+pub mod dashboard;
\ No newline at end of file
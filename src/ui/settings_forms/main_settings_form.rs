@@ -1,6 +1,7 @@
 use crate::app::Message;
 use crate::configuration::app_configuration::{AppConfiguration};
 use crate::fl;
+use crate::monitors::monitor_registry::MonitorRegistry;
 use cosmic::iced_widget::Container;
 use cosmic::widget::{container, settings};
 use cosmic::{widget, Theme};
@@ -9,7 +10,7 @@ use std::clone::Clone;
 pub struct MainSettingsForm;
 
 impl MainSettingsForm {
-    pub fn draw(app_config: &'_ AppConfiguration) -> Container<'_, Message, Theme> {
+    pub fn draw<'a>(app_config: &'a AppConfiguration, monitor_registry: &MonitorRegistry) -> Container<'a, Message, Theme> {
         let mut column = widget::list_column()
             .padding(2)
             .spacing(0)
@@ -19,7 +20,7 @@ impl MainSettingsForm {
                 ..Default::default()
             }));
 
-        let settings_form_options = app_config.settings_form_options();
+        let settings_form_options = app_config.settings_form_options(monitor_registry);
 
         for settings_form in settings_form_options.values() {
 
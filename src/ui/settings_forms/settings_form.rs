@@ -3,6 +3,7 @@ use crate::configuration::app_configuration::*;
 use crate::core::app_colours::{AppColours, BRIGHT_RED, EXT_WARM_GREY};
 use crate::core::settings::{SettingsForm, SettingsFormInputType};
 use crate::ui::components::form_checkbox_input::{form_checkbox_input, FormCheckboxInputProps};
+use crate::ui::components::form_dropdown_input::{form_dropdown_input, FormDropdownInputProps};
 use crate::ui::components::form_text_input::{form_text_input, FormTextInputProps};
 use crate::ui::components::form_theme_colour_picker_input::{
     form_theme_colour_picker_input, FormThemeColourPickerInputProps,
@@ -39,6 +40,7 @@ impl SettingsForm {
                         value: &settings_form_item.value,
                         form_value_key,
                         validator: settings_form_item.validator,
+                        form_error: self.validate_against_other_fields(form_value_key).err(),
                         error_color: red_color,
                         helper_text: settings_form_item.helper_text.clone(),
                         helper_text_color: helper_color,
@@ -82,6 +84,25 @@ impl SettingsForm {
                         helper_text_color: helper_color,
                     });
 
+                    column = column.add(el);
+                }
+                SettingsFormInputType::Dropdown { ref values, ref labels } => {
+                    let helper_color: Color = app_state
+                        .app_colours()
+                        .get(EXT_WARM_GREY)
+                        .map_or(Color::WHITE, |c| Color::new(c.red, c.green, c.blue, c.alpha));
+
+                    let el = form_dropdown_input(FormDropdownInputProps {
+                        settings_window_id: self.settings_window_id,
+                        label: settings_form_item.label.clone(),
+                        value: &settings_form_item.value,
+                        values: values.clone(),
+                        labels,
+                        form_value_key,
+                        helper_text: settings_form_item.helper_text.clone(),
+                        helper_text_color: helper_color,
+                    });
+
                     column = column.add(el);
                 }
             };